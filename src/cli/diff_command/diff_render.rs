@@ -0,0 +1,139 @@
+#[cfg(test)]
+#[path = "diff_render_test.rs"]
+mod diff_render_test;
+
+use super::diff_tree::{
+    count_changes, format_change, format_delta, format_size, DiffItem, DiffStatus,
+};
+use crate::cli::view_command::non_interactive_render::CANCEL_CHECK_INTERVAL;
+use space_rs::{SizeDisplayFormat, SizeMode};
+use std::{
+    io::Write,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The widths of the delta, change and size columns, which fit e.g. `+1023.9 KB`, `+1234.5%` and `1023.9 KB`.
+pub(crate) const DELTA_COLUMN_WIDTH: usize = 11;
+pub(crate) const CHANGE_COLUMN_WIDTH: usize = 9;
+pub(crate) const SIZE_COLUMN_WIDTH: usize = 10;
+
+pub(crate) struct DiffRenderOptions {
+    pub(crate) size_mode: SizeMode,
+    pub(crate) size_display_format: SizeDisplayFormat,
+    /// If `true` then the items that did not change, and have no descendants that changed, are also written.
+    pub(crate) show_unchanged: bool,
+}
+
+/// Returns `true` if the item is shown, i.e. if it or any of its descendants changed, or if unchanged items are
+/// shown too.
+pub(crate) fn is_shown(item: &DiffItem, show_unchanged: bool) -> bool {
+    show_unchanged || item.has_changes
+}
+
+/// Writes a line per item with its marker, change in size, percent change, old and new size and path, indented
+/// by its depth, after a line with the column headers. Returns the number of items written.
+pub(crate) fn render_diff<W: Write>(
+    items: &[DiffItem],
+    options: &DiffRenderOptions,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+) -> anyhow::Result<usize> {
+    writeln!(
+        writer,
+        "  {:>DELTA_COLUMN_WIDTH$} {:>CHANGE_COLUMN_WIDTH$} {:>SIZE_COLUMN_WIDTH$} \
+        {:>SIZE_COLUMN_WIDTH$}  Path",
+        "Delta", "Change", "Old", "New"
+    )?;
+    let mut items_since_check = 0;
+    render_items(
+        items,
+        0,
+        options,
+        writer,
+        should_exit,
+        &mut items_since_check,
+    )
+}
+
+fn render_items<W: Write>(
+    items: &[DiffItem],
+    depth: usize,
+    options: &DiffRenderOptions,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+    items_since_check: &mut usize,
+) -> anyhow::Result<usize> {
+    let mut rendered_count = 0;
+
+    for item in items {
+        if !is_shown(item, options.show_unchanged) {
+            continue;
+        }
+
+        // Periodically check for cancellation.
+        *items_since_check += 1;
+        if *items_since_check >= CANCEL_CHECK_INTERVAL {
+            *items_since_check = 0;
+            if should_exit.load(Ordering::Relaxed) {
+                anyhow::bail!("Cancelled.");
+            }
+        }
+
+        writeln!(
+            writer,
+            "{} {:>DELTA_COLUMN_WIDTH$} {:>CHANGE_COLUMN_WIDTH$} {:>SIZE_COLUMN_WIDTH$} \
+            {:>SIZE_COLUMN_WIDTH$}  {:indent$}{}",
+            item.status().marker(),
+            format_delta(item.delta(), options.size_mode, options.size_display_format),
+            format_change(item),
+            format_size(
+                item.old_size,
+                options.size_mode,
+                options.size_display_format
+            ),
+            format_size(
+                item.new_size,
+                options.size_mode,
+                options.size_display_format
+            ),
+            "",
+            item.path_segment,
+            indent = depth * 2
+        )?;
+        rendered_count += 1;
+
+        rendered_count += render_items(
+            &item.children,
+            depth + 1,
+            options,
+            writer,
+            should_exit,
+            items_since_check,
+        )?;
+    }
+
+    Ok(rendered_count)
+}
+
+/// Describes the change in the total size of the roots, and how many items were added, removed and changed, e.g.
+/// `Total +12 MB (+20.0%), from 60 MB to 72 MB. 3 added, 1 removed and 10 changed items`.
+pub(crate) fn describe_total(items: &[DiffItem], options: &DiffRenderOptions) -> String {
+    let total = DiffItem {
+        path_segment: String::default(),
+        old_size: Some(items.iter().filter_map(|item| item.old_size).sum()),
+        new_size: Some(items.iter().filter_map(|item| item.new_size).sum()),
+        children: vec![],
+        has_changes: false,
+    };
+    let (added, removed, changed) = count_changes(items);
+    let change = match total.status() {
+        DiffStatus::Unchanged => String::default(),
+        _ => format!(" ({})", format_change(&total)),
+    };
+    format!(
+        "Total {}{change}, from {} to {}. {added} added, {removed} removed and {changed} changed items",
+        format_delta(total.delta(), options.size_mode, options.size_display_format),
+        format_size(total.old_size, options.size_mode, options.size_display_format),
+        format_size(total.new_size, options.size_mode, options.size_display_format),
+    )
+}
@@ -0,0 +1,75 @@
+use super::{describe_total, render_diff, DiffRenderOptions};
+use crate::cli::diff_command::{diff_test_utils::make_test_diff, diff_tree::DiffItem};
+use rstest::rstest;
+use space_rs::{SizeDisplayFormat, SizeMode};
+use std::sync::atomic::AtomicBool;
+
+fn options(show_unchanged: bool) -> DiffRenderOptions {
+    DiffRenderOptions {
+        size_mode: SizeMode::Apparent,
+        size_display_format: SizeDisplayFormat::Metric,
+        show_unchanged,
+    }
+}
+
+fn render_to_string(
+    items: &[DiffItem],
+    options: &DiffRenderOptions,
+) -> anyhow::Result<(usize, String)> {
+    let mut output = vec![];
+    let rendered_count = render_diff(items, options, &mut output, &AtomicBool::new(false))?;
+    Ok((rendered_count, String::from_utf8(output)?))
+}
+
+#[rstest]
+fn render_diff_writes_header_and_changed_items_in_tree_order() -> anyhow::Result<()> {
+    // Arrange
+    let diff = make_test_diff();
+
+    // Act
+    let (rendered_count, output) = render_to_string(&diff, &options(false))?;
+
+    // Assert
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(
+        vec![
+            "        Delta    Change        Old        New  Path",
+            "       +12 KB    +21.1%      57 KB      69 KB  /data",
+            "       -30 KB    -75.0%      40 KB      10 KB    cache",
+            "+      +25 KB     added                 25 KB    new.bin",
+            "       +17 KB   +113.3%      15 KB      32 KB    logs",
+            "       +20 KB   +200.0%      10 KB      30 KB      a.log",
+            "-       -5 KB   removed       5 KB                 b.log",
+            "+       +2 KB     added                  2 KB      c.log",
+        ],
+        lines
+    );
+    assert_eq!(7, rendered_count);
+    Ok(())
+}
+
+#[rstest]
+fn render_diff_given_show_unchanged_writes_unchanged_items() -> anyhow::Result<()> {
+    // Arrange
+    let diff = make_test_diff();
+
+    // Act
+    let (rendered_count, output) = render_to_string(&diff, &options(true))?;
+
+    // Assert
+    assert_eq!(8, rendered_count);
+    assert!(output.contains("         0 KB     +0.0%       2 KB       2 KB    same.txt\n"));
+    Ok(())
+}
+
+#[rstest]
+fn describe_total_includes_total_change_and_counts() {
+    // Act
+    let description = describe_total(&make_test_diff(), &options(false));
+
+    // Assert
+    assert_eq!(
+        "Total +12 KB (+21.1%), from 57 KB to 69 KB. 2 added, 1 removed and 4 changed items",
+        description
+    );
+}
@@ -0,0 +1,74 @@
+use super::diff_tree::{diff_trees, DiffItem};
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, Size, SizeMode};
+
+/// Creates an item with the given size of its own, i.e. excluding its children, which is a directory if it has any
+/// children.
+pub(crate) fn item(name: &str, size: u64, children: Vec<DirectoryItem>) -> DirectoryItem {
+    let size = size
+        + children
+            .iter()
+            .map(|child| child.size_in_bytes.get_value())
+            .sum::<u64>();
+    DirectoryItem {
+        path_segment: name.to_string(),
+        item_type: if children.is_empty() {
+            DirectoryItemType::File
+        } else {
+            DirectoryItemType::Directory
+        },
+        size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::new(size),
+        descendant_count: children
+            .iter()
+            .map(|child| child.descendant_count + 1)
+            .sum(),
+        descendant_counts: DescendantCounts::default(),
+        children,
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
+    }
+}
+
+/// Returns the old tree of the diff that [`make_test_diff`] creates.
+pub(crate) fn make_old_tree() -> Vec<DirectoryItem> {
+    vec![item(
+        "/data",
+        0,
+        vec![
+            item(
+                "logs",
+                0,
+                vec![item("a.log", 10_000, vec![]), item("b.log", 5_000, vec![])],
+            ),
+            item("cache", 40_000, vec![]),
+            item("same.txt", 2_000, vec![]),
+        ],
+    )]
+}
+
+/// Returns the new tree of the diff that [`make_test_diff`] creates.
+pub(crate) fn make_new_tree() -> Vec<DirectoryItem> {
+    vec![item(
+        "/data",
+        0,
+        vec![
+            item(
+                "logs",
+                0,
+                vec![item("a.log", 30_000, vec![]), item("c.log", 2_000, vec![])],
+            ),
+            item("cache", 10_000, vec![]),
+            item("same.txt", 2_000, vec![]),
+            item("new.bin", 25_000, vec![]),
+        ],
+    )]
+}
+
+/// Returns the diff of a tree in which `logs/a.log` grew by 20 KB, `logs/b.log` of 5 KB was removed, `logs/c.log`
+/// of 2 KB was added, `cache` shrunk by 30 KB, `new.bin` of 25 KB was added and `same.txt` did not change.
+pub(crate) fn make_test_diff() -> Vec<DiffItem> {
+    diff_trees(&make_old_tree(), &make_new_tree(), SizeMode::Apparent)
+}
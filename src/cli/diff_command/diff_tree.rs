@@ -0,0 +1,209 @@
+//! Aligns two scanned trees by path, to show how the size of each item changed between them.
+
+#[cfg(test)]
+#[path = "diff_tree_test.rs"]
+mod diff_tree_test;
+
+use space_rs::{DirectoryItem, Size, SizeDisplayFormat, SizeMode};
+use std::{cmp::Ordering, collections::HashMap};
+
+/// How an item changed between the old and the new tree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum DiffStatus {
+    /// The item is only in the new tree.
+    Added,
+    /// The item is only in the old tree.
+    Removed,
+    /// The item is in both trees, with a different size.
+    Changed,
+    /// The item is in both trees, with the same size.
+    Unchanged,
+}
+
+impl DiffStatus {
+    /// Returns the marker that is shown before an added or removed item.
+    pub(crate) fn marker(&self) -> char {
+        match self {
+            DiffStatus::Added => '+',
+            DiffStatus::Removed => '-',
+            DiffStatus::Changed | DiffStatus::Unchanged => ' ',
+        }
+    }
+}
+
+/// An item in either or both of the trees, with its size in each.
+#[derive(Debug)]
+pub(crate) struct DiffItem {
+    pub(crate) path_segment: String,
+    /// The size in the old tree, or `None` if the item was added.
+    pub(crate) old_size: Option<u64>,
+    /// The size in the new tree, or `None` if the item was removed.
+    pub(crate) new_size: Option<u64>,
+    /// The children, sorted by the absolute size of their change, largest first.
+    pub(crate) children: Vec<DiffItem>,
+    /// `true` if the item or any of its descendants was added, removed or changed.
+    pub(crate) has_changes: bool,
+}
+
+impl DiffItem {
+    pub(crate) fn status(&self) -> DiffStatus {
+        match (self.old_size, self.new_size) {
+            (None, _) => DiffStatus::Added,
+            (_, None) => DiffStatus::Removed,
+            (old_size, new_size) if old_size == new_size => DiffStatus::Unchanged,
+            _ => DiffStatus::Changed,
+        }
+    }
+
+    /// Returns the change in size, which is negative if the item shrunk.
+    pub(crate) fn delta(&self) -> i64 {
+        self.new_size.unwrap_or_default() as i64 - self.old_size.unwrap_or_default() as i64
+    }
+
+    /// Returns the change in size as a percentage of the old size, or `None` if the old size is zero or the item
+    /// was added or removed.
+    pub(crate) fn percent_change(&self) -> Option<f64> {
+        match (self.old_size, self.new_size) {
+            (Some(old_size), Some(_)) if old_size > 0 => {
+                Some(self.delta() as f64 / old_size as f64 * 100f64)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Aligns the old and the new trees by path. The roots are matched by their full paths, except that a single old
+/// root is always matched with a single new root, so that scans of the same tree at different paths, e.g. on
+/// another host, can be compared too.
+pub(crate) fn diff_trees(
+    old_items: &[DirectoryItem],
+    new_items: &[DirectoryItem],
+    size_mode: SizeMode,
+) -> Vec<DiffItem> {
+    match (old_items, new_items) {
+        ([old_item], [new_item]) => vec![diff_item(Some(old_item), Some(new_item), size_mode)],
+        _ => diff_children(old_items, new_items, size_mode),
+    }
+}
+
+fn diff_children(
+    old_items: &[DirectoryItem],
+    new_items: &[DirectoryItem],
+    size_mode: SizeMode,
+) -> Vec<DiffItem> {
+    let mut old_items_by_name: HashMap<&str, &DirectoryItem> = old_items
+        .iter()
+        .map(|item| (item.path_segment.as_str(), item))
+        .collect();
+    let mut diff_items: Vec<_> = new_items
+        .iter()
+        .map(|new_item| {
+            let old_item = old_items_by_name.remove(new_item.path_segment.as_str());
+            diff_item(old_item, Some(new_item), size_mode)
+        })
+        .collect();
+    // The old items that are left were removed.
+    diff_items.extend(
+        old_items
+            .iter()
+            .filter(|item| old_items_by_name.contains_key(item.path_segment.as_str()))
+            .map(|old_item| diff_item(Some(old_item), None, size_mode)),
+    );
+    diff_items.sort_by(cmp_by_growth);
+    diff_items
+}
+
+/// Creates the diff of an item, of which at least one of `old_item` and `new_item` must be given.
+fn diff_item(
+    old_item: Option<&DirectoryItem>,
+    new_item: Option<&DirectoryItem>,
+    size_mode: SizeMode,
+) -> DiffItem {
+    let item = new_item
+        .or(old_item)
+        .expect("Either the old or the new item is given");
+    let children = diff_children(
+        old_item.map_or(&[], |item| &item.children),
+        new_item.map_or(&[], |item| &item.children),
+        size_mode,
+    );
+    let mut diff_item = DiffItem {
+        path_segment: item.path_segment.clone(),
+        old_size: old_item.map(|item| item.get_size(size_mode).get_value()),
+        new_size: new_item.map(|item| item.get_size(size_mode).get_value()),
+        has_changes: children.iter().any(|child| child.has_changes),
+        children,
+    };
+    diff_item.has_changes |= diff_item.status() != DiffStatus::Unchanged;
+    diff_item
+}
+
+/// Orders items by the absolute size of their change, largest first, so that the largest growth and shrinkage
+/// are listed first, and then by name.
+fn cmp_by_growth(a: &DiffItem, b: &DiffItem) -> Ordering {
+    b.delta()
+        .unsigned_abs()
+        .cmp(&a.delta().unsigned_abs())
+        .then_with(|| a.path_segment.cmp(&b.path_segment))
+}
+
+/// Removes the descendants of the items deeper than `max_depth`, where the roots are at depth 0.
+pub(crate) fn limit_depth(items: &mut [DiffItem], max_depth: usize) {
+    for item in items {
+        if max_depth == 0 {
+            item.children.clear();
+        } else {
+            limit_depth(&mut item.children, max_depth - 1);
+        }
+    }
+}
+
+/// Returns the number of added, removed and changed items, in that order.
+pub(crate) fn count_changes(items: &[DiffItem]) -> (usize, usize, usize) {
+    let mut counts = (0, 0, 0);
+    for item in items {
+        match item.status() {
+            DiffStatus::Added => counts.0 += 1,
+            DiffStatus::Removed => counts.1 += 1,
+            DiffStatus::Changed => counts.2 += 1,
+            DiffStatus::Unchanged => {}
+        }
+        let (added, removed, changed) = count_changes(&item.children);
+        counts = (counts.0 + added, counts.1 + removed, counts.2 + changed);
+    }
+    counts
+}
+
+/// Formats the change in size with its sign, e.g. `+1.2 MB` or `-40 KB`.
+pub(crate) fn format_delta(
+    delta: i64,
+    size_mode: SizeMode,
+    size_display_format: SizeDisplayFormat,
+) -> String {
+    let size = Size::new(delta.unsigned_abs()).to_string_for_mode(size_mode, size_display_format);
+    match delta.cmp(&0) {
+        Ordering::Greater => format!("+{size}"),
+        Ordering::Less => format!("-{size}"),
+        Ordering::Equal => size,
+    }
+}
+
+/// Formats the percent change of the item, or whether it was added or removed.
+pub(crate) fn format_change(item: &DiffItem) -> String {
+    match (item.status(), item.percent_change()) {
+        (DiffStatus::Added, _) => String::from("added"),
+        (DiffStatus::Removed, _) => String::from("removed"),
+        (_, Some(percent_change)) => format!("{percent_change:+.1}%"),
+        (_, None) => String::from("n/a"),
+    }
+}
+
+/// Formats the size of the item in one of the trees, or an empty string if it is not in that tree.
+pub(crate) fn format_size(
+    size: Option<u64>,
+    size_mode: SizeMode,
+    size_display_format: SizeDisplayFormat,
+) -> String {
+    size.map(|size| Size::new(size).to_string_for_mode(size_mode, size_display_format))
+        .unwrap_or_default()
+}
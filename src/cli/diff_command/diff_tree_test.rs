@@ -0,0 +1,194 @@
+use super::{
+    count_changes, diff_trees, format_change, format_delta, limit_depth, DiffItem, DiffStatus,
+};
+use crate::cli::diff_command::diff_test_utils::{item, make_old_tree, make_test_diff};
+use rstest::rstest;
+use space_rs::{SizeDisplayFormat, SizeMode};
+
+fn names(items: &[DiffItem]) -> Vec<&str> {
+    items
+        .iter()
+        .map(|item| item.path_segment.as_str())
+        .collect()
+}
+
+#[rstest]
+fn diff_trees_aligns_items_by_path_and_sorts_by_absolute_change() {
+    // Act
+    let diff = make_test_diff();
+
+    // Assert
+    assert_eq!(1, diff.len());
+    let root = &diff[0];
+    assert_eq!((Some(57_000), Some(69_000)), (root.old_size, root.new_size));
+    assert_eq!(12_000, root.delta());
+    assert_eq!(
+        vec!["cache", "new.bin", "logs", "same.txt"],
+        names(&root.children)
+    );
+    let logs = &root.children[2];
+    assert_eq!(vec!["a.log", "b.log", "c.log"], names(&logs.children));
+    assert_eq!(
+        vec![DiffStatus::Changed, DiffStatus::Removed, DiffStatus::Added],
+        logs.children
+            .iter()
+            .map(|child| child.status())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![20_000, -5_000, 2_000],
+        logs.children
+            .iter()
+            .map(|child| child.delta())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[rstest]
+fn diff_trees_marks_items_without_changes_in_their_tree() {
+    // Act
+    let diff = make_test_diff();
+
+    // Assert
+    let root = &diff[0];
+    assert!(root.has_changes);
+    let same = &root.children[3];
+    assert_eq!(DiffStatus::Unchanged, same.status());
+    assert!(!same.has_changes);
+}
+
+#[rstest]
+fn diff_trees_given_unchanged_item_with_moved_children_marks_it_as_having_changes() {
+    // Arrange
+    let old_items = vec![item(
+        "/a",
+        0,
+        vec![item("x", 10, vec![]), item("y", 5, vec![])],
+    )];
+    let new_items = vec![item(
+        "/a",
+        0,
+        vec![item("z", 10, vec![]), item("y", 5, vec![])],
+    )];
+
+    // Act
+    let diff = diff_trees(&old_items, &new_items, SizeMode::Apparent);
+
+    // Assert
+    assert_eq!(DiffStatus::Unchanged, diff[0].status());
+    assert!(diff[0].has_changes);
+}
+
+#[rstest]
+fn diff_trees_given_single_roots_with_different_paths_aligns_them() {
+    // Arrange
+    let new_items = vec![item("/mnt/backup/data", 0, vec![item("logs", 10, vec![])])];
+
+    // Act
+    let diff = diff_trees(&make_old_tree(), &new_items, SizeMode::Apparent);
+
+    // Assert
+    assert_eq!(vec!["/mnt/backup/data"], names(&diff));
+    assert_eq!(DiffStatus::Changed, diff[0].status());
+}
+
+#[rstest]
+fn diff_trees_given_multiple_roots_aligns_them_by_path() {
+    // Arrange
+    let old_items = vec![item("/a", 10, vec![]), item("/b", 20, vec![])];
+    let new_items = vec![item("/b", 20, vec![]), item("/c", 5, vec![])];
+
+    // Act
+    let diff = diff_trees(&old_items, &new_items, SizeMode::Apparent);
+
+    // Assert
+    assert_eq!(vec!["/a", "/c", "/b"], names(&diff));
+    assert_eq!(
+        vec![
+            DiffStatus::Removed,
+            DiffStatus::Added,
+            DiffStatus::Unchanged
+        ],
+        diff.iter().map(|item| item.status()).collect::<Vec<_>>()
+    );
+}
+
+#[rstest]
+fn diff_trees_given_count_size_mode_compares_entry_counts() {
+    // Act
+    let diff = diff_trees(
+        &make_old_tree(),
+        &crate::cli::diff_command::diff_test_utils::make_new_tree(),
+        SizeMode::Count,
+    );
+
+    // Assert
+    assert_eq!((Some(6), Some(7)), (diff[0].old_size, diff[0].new_size));
+}
+
+#[rstest]
+fn limit_depth_removes_descendants_below_max_depth() {
+    // Arrange
+    let mut diff = make_test_diff();
+
+    // Act
+    limit_depth(&mut diff, 1);
+
+    // Assert
+    assert_eq!(4, diff[0].children.len());
+    assert!(diff[0]
+        .children
+        .iter()
+        .all(|child| child.children.is_empty()));
+    // The changes below the max depth are still reflected.
+    assert!(diff[0].children[2].has_changes);
+}
+
+#[rstest]
+fn count_changes_counts_added_removed_and_changed_items() {
+    // Act
+    let counts = count_changes(&make_test_diff());
+
+    // Assert
+    assert_eq!((2, 1, 4), counts);
+}
+
+#[rstest]
+#[case(2000, "+2 KB")]
+#[case(-3000, "-3 KB")]
+#[case(0, "0 KB")]
+fn format_delta_includes_sign(#[case] delta: i64, #[case] expected: &str) {
+    // Act
+    let formatted = format_delta(delta, SizeMode::Apparent, SizeDisplayFormat::Metric);
+
+    // Assert
+    assert_eq!(expected, formatted);
+}
+
+#[rstest]
+#[case(Some(200), Some(250), "+25.0%")]
+#[case(Some(200), Some(150), "-25.0%")]
+#[case(Some(200), Some(200), "+0.0%")]
+#[case(Some(0), Some(10), "n/a")]
+#[case(None, Some(10), "added")]
+#[case(Some(10), None, "removed")]
+fn format_change_formats_percent_change_or_status(
+    #[case] old_size: Option<u64>,
+    #[case] new_size: Option<u64>,
+    #[case] expected: &str,
+) {
+    // Arrange
+    let item = DiffItem {
+        path_segment: String::from("x"),
+        old_size,
+        new_size,
+        children: vec![],
+        has_changes: true,
+    };
+
+    // Act
+    let formatted = format_change(&item);
+
+    // Assert
+    assert_eq!(expected, formatted);
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+#[path = "diff_view_state_test.rs"]
+mod diff_view_state_test;
+
+use super::{
+    diff_render::{is_shown, DiffRenderOptions},
+    diff_tree::DiffItem,
+};
+use std::collections::HashSet;
+
+/// A visible row of the diff TUI, i.e. an item whose ancestors are all expanded.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct DiffRow {
+    /// The index of the item and of each of its ancestors among their siblings, starting at the root.
+    pub(crate) index_path: Vec<usize>,
+    pub(crate) depth: usize,
+}
+
+/// The state of the diff TUI, i.e. the compared items and which of them are expanded and selected.
+pub(crate) struct DiffViewState {
+    pub(crate) items: Vec<DiffItem>,
+    pub(crate) options: DiffRenderOptions,
+    /// Describes the compared snapshots.
+    pub(crate) title: String,
+    /// The index paths of the expanded items.
+    expanded: HashSet<Vec<usize>>,
+    pub(crate) rows: Vec<DiffRow>,
+    pub(crate) selected_index: usize,
+    pub(crate) visible_offset: usize,
+    pub(crate) visible_height: usize,
+}
+
+impl DiffViewState {
+    /// Creates the state with the roots expanded and the first root selected.
+    pub(crate) fn new(items: Vec<DiffItem>, options: DiffRenderOptions, title: String) -> Self {
+        let mut state = DiffViewState {
+            expanded: (0..items.len()).map(|index| vec![index]).collect(),
+            items,
+            options,
+            title,
+            rows: vec![],
+            selected_index: 0,
+            visible_offset: 0,
+            visible_height: 1,
+        };
+        state.update_rows();
+        state
+    }
+
+    pub(crate) fn item(&self, index_path: &[usize]) -> &DiffItem {
+        let mut item = &self.items[index_path[0]];
+        for index in &index_path[1..] {
+            item = &item.children[*index];
+        }
+        item
+    }
+
+    pub(crate) fn selected_row(&self) -> Option<&DiffRow> {
+        self.rows.get(self.selected_index)
+    }
+
+    pub(crate) fn is_expanded(&self, index_path: &[usize]) -> bool {
+        self.expanded.contains(index_path)
+    }
+
+    /// Returns `true` if the item has any children that are shown, i.e. if it can be expanded.
+    pub(crate) fn has_shown_children(&self, index_path: &[usize]) -> bool {
+        self.item(index_path)
+            .children
+            .iter()
+            .any(|child| is_shown(child, self.options.show_unchanged))
+    }
+
+    /// Rebuilds the visible rows, keeping the selected item selected if it is still visible.
+    fn update_rows(&mut self) {
+        let selected_index_path = self.selected_row().map(|row| row.index_path.clone());
+        let mut rows = vec![];
+        let mut index_path = vec![];
+        self.add_rows(&self.items, &mut index_path, &mut rows);
+        self.rows = rows;
+
+        self.selected_index = selected_index_path
+            .and_then(|selected| self.rows.iter().position(|row| row.index_path == selected))
+            .unwrap_or(self.selected_index)
+            .min(self.rows.len().saturating_sub(1));
+        self.scroll_to_selected();
+    }
+
+    fn add_rows(&self, items: &[DiffItem], index_path: &mut Vec<usize>, rows: &mut Vec<DiffRow>) {
+        for (index, item) in items.iter().enumerate() {
+            if !is_shown(item, self.options.show_unchanged) {
+                continue;
+            }
+            index_path.push(index);
+            rows.push(DiffRow {
+                index_path: index_path.clone(),
+                depth: index_path.len() - 1,
+            });
+            if self.expanded.contains(index_path.as_slice()) {
+                self.add_rows(&item.children, index_path, rows);
+            }
+            index_path.pop();
+        }
+    }
+
+    /// Scrolls just enough for the selected row to be visible.
+    pub(crate) fn scroll_to_selected(&mut self) {
+        let visible_height = self.visible_height.max(1);
+        if self.selected_index < self.visible_offset {
+            self.visible_offset = self.selected_index;
+        } else if self.selected_index >= self.visible_offset + visible_height {
+            self.visible_offset = self.selected_index + 1 - visible_height;
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected_index = index.min(self.rows.len().saturating_sub(1));
+        self.scroll_to_selected();
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        self.select(self.selected_index + 1);
+    }
+
+    pub(crate) fn select_prev(&mut self) {
+        self.select(self.selected_index.saturating_sub(1));
+    }
+
+    pub(crate) fn select_next_page(&mut self) {
+        self.select(self.selected_index + self.visible_height.max(1));
+    }
+
+    pub(crate) fn select_prev_page(&mut self) {
+        self.select(
+            self.selected_index
+                .saturating_sub(self.visible_height.max(1)),
+        );
+    }
+
+    pub(crate) fn select_first(&mut self) {
+        self.select(0);
+    }
+
+    pub(crate) fn select_last(&mut self) {
+        self.select(self.rows.len().saturating_sub(1));
+    }
+
+    /// Expands the selected item, if it has any children that are shown.
+    pub(crate) fn expand_selected(&mut self) {
+        if let Some(index_path) = self.selected_row().map(|row| row.index_path.clone()) {
+            if self.has_shown_children(&index_path) && self.expanded.insert(index_path) {
+                self.update_rows();
+            }
+        }
+    }
+
+    /// Collapses the selected item if it is expanded, or else selects its parent.
+    pub(crate) fn collapse_selected(&mut self) {
+        let Some(index_path) = self.selected_row().map(|row| row.index_path.clone()) else {
+            return;
+        };
+        if self.has_shown_children(&index_path) && self.expanded.remove(&index_path) {
+            self.update_rows();
+        } else if index_path.len() > 1 {
+            let parent_index_path = &index_path[..index_path.len() - 1];
+            if let Some(parent_index) = self
+                .rows
+                .iter()
+                .position(|row| row.index_path == parent_index_path)
+            {
+                self.select(parent_index);
+            }
+        }
+    }
+
+    /// Toggles whether the items that did not change are shown.
+    pub(crate) fn toggle_unchanged(&mut self) {
+        self.options.show_unchanged = !self.options.show_unchanged;
+        self.update_rows();
+    }
+}
@@ -0,0 +1,113 @@
+use super::DiffViewState;
+use crate::cli::diff_command::{diff_render::DiffRenderOptions, diff_test_utils::make_test_diff};
+use rstest::rstest;
+use space_rs::{SizeDisplayFormat, SizeMode};
+
+fn make_diff_view_state() -> DiffViewState {
+    DiffViewState::new(
+        make_test_diff(),
+        DiffRenderOptions {
+            size_mode: SizeMode::Apparent,
+            size_display_format: SizeDisplayFormat::Metric,
+            show_unchanged: false,
+        },
+        String::from("Comparing old with new"),
+    )
+}
+
+fn visible_names(diff_view_state: &DiffViewState) -> Vec<&str> {
+    diff_view_state
+        .rows
+        .iter()
+        .map(|row| diff_view_state.item(&row.index_path).path_segment.as_str())
+        .collect()
+}
+
+fn selected_name(diff_view_state: &DiffViewState) -> &str {
+    &diff_view_state
+        .item(&diff_view_state.selected_row().unwrap().index_path)
+        .path_segment
+}
+
+#[rstest]
+fn new_expands_roots_and_hides_unchanged_items() {
+    // Act
+    let diff_view_state = make_diff_view_state();
+
+    // Assert
+    assert_eq!(
+        vec!["/data", "cache", "new.bin", "logs"],
+        visible_names(&diff_view_state)
+    );
+    assert_eq!(0, diff_view_state.selected_index);
+}
+
+#[rstest]
+fn expand_selected_shows_children_and_collapse_selected_hides_them() {
+    // Arrange
+    let mut diff_view_state = make_diff_view_state();
+    diff_view_state.select_last();
+
+    // Act
+    diff_view_state.expand_selected();
+    let expanded_names = visible_names(&diff_view_state).len();
+    diff_view_state.collapse_selected();
+
+    // Assert
+    assert_eq!(7, expanded_names);
+    assert_eq!(4, visible_names(&diff_view_state).len());
+    assert_eq!("logs", selected_name(&diff_view_state));
+}
+
+#[rstest]
+fn collapse_selected_given_collapsed_item_selects_parent() {
+    // Arrange
+    let mut diff_view_state = make_diff_view_state();
+    diff_view_state.select_next();
+
+    // Act
+    diff_view_state.collapse_selected();
+
+    // Assert
+    assert_eq!("/data", selected_name(&diff_view_state));
+}
+
+#[rstest]
+fn toggle_unchanged_shows_unchanged_items_and_keeps_selection() {
+    // Arrange
+    let mut diff_view_state = make_diff_view_state();
+    diff_view_state.select_last();
+
+    // Act
+    diff_view_state.toggle_unchanged();
+
+    // Assert
+    assert_eq!(
+        vec!["/data", "cache", "new.bin", "logs", "same.txt"],
+        visible_names(&diff_view_state)
+    );
+    assert_eq!("logs", selected_name(&diff_view_state));
+}
+
+#[rstest]
+fn select_next_page_scrolls_selected_row_into_view() {
+    // Arrange
+    let mut diff_view_state = make_diff_view_state();
+    diff_view_state.visible_height = 2;
+
+    // Act
+    diff_view_state.select_next_page();
+    diff_view_state.select_next();
+
+    // Assert
+    assert_eq!(3, diff_view_state.selected_index);
+    assert_eq!(2, diff_view_state.visible_offset);
+
+    // Act
+    diff_view_state.select_next();
+    diff_view_state.select_first();
+
+    // Assert
+    assert_eq!(0, diff_view_state.selected_index);
+    assert_eq!(0, diff_view_state.visible_offset);
+}
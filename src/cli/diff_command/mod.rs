@@ -0,0 +1,180 @@
+pub(crate) mod diff_render;
+pub(crate) mod diff_tree;
+pub(crate) mod diff_view_state;
+
+use super::{
+    cli_command::CliCommand,
+    environment::EnvServiceTrait,
+    snapshot::{self, SnapshotHeader},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use diff_render::DiffRenderOptions;
+use diff_tree::DiffItem;
+use space_rs::{SizeDisplayFormat, SizeMode};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc},
+};
+
+#[cfg(test)]
+#[path = "../diff_command_test.rs"]
+mod diff_command_test;
+
+#[cfg(test)]
+pub(crate) mod diff_test_utils;
+
+/// Compares two snapshots, saved with `--save-snapshot`, by aligning their trees by path.
+pub(crate) struct DiffCommand {
+    old_snapshot_path: PathBuf,
+    new_snapshot_path: PathBuf,
+    size_display_format: SizeDisplayFormat,
+    size_mode: SizeMode,
+    /// If `true` then the items that did not change are also shown.
+    show_unchanged: bool,
+    /// The maximum depth of the items to show, where the roots are at depth 0.
+    max_depth: Option<usize>,
+    #[cfg(not(test))]
+    non_interactive: bool,
+    /// Describes the compared snapshots, once they are loaded.
+    description: String,
+    items: Vec<DiffItem>,
+    #[cfg_attr(test, allow(dead_code))]
+    env_service: Box<dyn EnvServiceTrait>,
+    should_exit: Arc<AtomicBool>,
+}
+
+impl CliCommand for DiffCommand {
+    fn prepare(&mut self) -> anyhow::Result<&mut Self> {
+        let (old_header, old_result) = snapshot::read_snapshot_file(&self.old_snapshot_path)?;
+        let (new_header, new_result) = snapshot::read_snapshot_file(&self.new_snapshot_path)?;
+        self.description = format!(
+            "Comparing {} with {}",
+            describe_snapshot(&self.old_snapshot_path, &old_header),
+            describe_snapshot(&self.new_snapshot_path, &new_header)
+        );
+
+        self.items = diff_tree::diff_trees(&old_result.items, &new_result.items, self.size_mode);
+        if let Some(max_depth) = self.max_depth {
+            diff_tree::limit_depth(&mut self.items, max_depth);
+        }
+        Ok(self)
+    }
+
+    fn run<W: Write>(&mut self, writer: &mut W) -> anyhow::Result<()> {
+        writeln!(writer, "{}", self.description)?;
+
+        let options = DiffRenderOptions {
+            size_mode: self.size_mode,
+            size_display_format: self.size_display_format,
+            show_unchanged: self.show_unchanged,
+        };
+
+        #[cfg(not(test))]
+        if self.is_interactive() {
+            return self.run_interactive(writer, options);
+        }
+
+        let rendered_count =
+            diff_render::render_diff(&self.items, &options, writer, &self.should_exit)?;
+        if rendered_count == 0 {
+            writeln!(writer, "No items changed.")?;
+        }
+        writeln!(
+            writer,
+            "{}",
+            diff_render::describe_total(&self.items, &options)
+        )?;
+        writeln!(writer, "Done.")?;
+        Ok(())
+    }
+}
+
+impl DiffCommand {
+    #[allow(clippy::too_many_arguments, dead_code)]
+    pub fn new(
+        old_snapshot_path: PathBuf,
+        new_snapshot_path: PathBuf,
+        size_display_format: SizeDisplayFormat,
+        size_mode: SizeMode,
+        show_unchanged: bool,
+        max_depth: Option<usize>,
+        #[cfg(not(test))] non_interactive: bool,
+        env_service: Box<dyn EnvServiceTrait>,
+        should_exit: Arc<AtomicBool>,
+    ) -> Self {
+        DiffCommand {
+            old_snapshot_path,
+            new_snapshot_path,
+            size_display_format,
+            size_mode,
+            show_unchanged,
+            max_depth,
+            #[cfg(not(test))]
+            non_interactive,
+            description: String::default(),
+            items: vec![],
+            env_service,
+            should_exit,
+        }
+    }
+
+    #[cfg(not(test))]
+    fn is_interactive(&self) -> bool {
+        use crossterm::tty::IsTty;
+        use std::io;
+        if cfg!(debug_assertions) {
+            !self.non_interactive
+        } else {
+            !self.non_interactive && io::stdout().is_tty()
+        }
+    }
+
+    #[cfg(not(test))]
+    fn run_interactive<W: Write>(
+        &mut self,
+        writer: &mut W,
+        options: DiffRenderOptions,
+    ) -> anyhow::Result<()> {
+        use super::{crossterm_input_event_source::CrosstermInputEventSource, tui};
+        use diff_view_state::DiffViewState;
+
+        let skin = super::view_command::select_skin(self.env_service.as_ref());
+        let mut diff_view_state = DiffViewState::new(
+            std::mem::take(&mut self.items),
+            options,
+            self.description.clone(),
+        );
+        tui::diff::render_diff(
+            &mut diff_view_state,
+            writer,
+            &mut CrosstermInputEventSource::new(),
+            &skin,
+            self.should_exit.clone(),
+        )?;
+
+        writeln!(writer, "Done.")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Describes the snapshot at `path`, i.e. which roots were scanned, on which host and when, e.g.
+/// `old.snap (/home on box at 2026-01-02T03:04:05Z)`.
+fn describe_snapshot(path: &Path, header: &SnapshotHeader) -> String {
+    let time = i64::try_from(header.timestamp)
+        .ok()
+        .and_then(|timestamp| DateTime::<Utc>::from_timestamp(timestamp, 0))
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default();
+    let host = if header.host.is_empty() {
+        String::default()
+    } else {
+        format!(" on {}", header.host)
+    };
+    format!(
+        "{} ({}{host} at {time})",
+        path.display(),
+        header.roots.join(", ")
+    )
+}
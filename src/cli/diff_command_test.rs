@@ -0,0 +1,111 @@
+use crate::cli::{
+    cli_command::CliCommand,
+    diff_command::{
+        diff_test_utils::{make_new_tree, make_old_tree},
+        DiffCommand,
+    },
+    environment::MockEnvServiceTrait,
+    snapshot::{write_snapshot_file, SnapshotHeader},
+};
+use crate::test_utils::TestOut;
+use space_rs::{DirectoryItem, SizeDisplayFormat, SizeMode};
+use std::{
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc},
+};
+use uuid::Uuid;
+
+fn write_test_snapshot(items: Vec<DirectoryItem>) -> anyhow::Result<PathBuf> {
+    let path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    write_snapshot_file(&path, &SnapshotHeader::new(&items, &[]), items)?;
+    Ok(path)
+}
+
+fn make_diff_command(
+    old_snapshot_path: PathBuf,
+    new_snapshot_path: PathBuf,
+    max_depth: Option<usize>,
+) -> DiffCommand {
+    DiffCommand::new(
+        old_snapshot_path,
+        new_snapshot_path,
+        SizeDisplayFormat::Metric,
+        SizeMode::Apparent,
+        false,
+        max_depth,
+        Box::new(MockEnvServiceTrait::new()),
+        Arc::new(AtomicBool::new(false)),
+    )
+}
+
+#[test]
+fn run_writes_snapshots_changed_items_and_total() -> anyhow::Result<()> {
+    // Arrange
+    let mut output = TestOut::new();
+    let old_snapshot_path = write_test_snapshot(make_old_tree())?;
+    let new_snapshot_path = write_test_snapshot(make_new_tree())?;
+    let mut diff_command =
+        make_diff_command(old_snapshot_path.clone(), new_snapshot_path.clone(), None);
+
+    // Act
+    diff_command.prepare()?;
+    diff_command.run(&mut output)?;
+
+    // Assert
+    output.expect(&format!("Comparing {} (/data", old_snapshot_path.display()))?;
+    output.expect(&format!(") with {} (/data", new_snapshot_path.display()))?;
+    output.expect("-       -5 KB   removed       5 KB                 b.log")?;
+    output.expect(
+        "Total +12 KB (+21.1%), from 57 KB to 69 KB. 2 added, 1 removed and 4 changed items\nDone.",
+    )?;
+
+    std::fs::remove_file(&old_snapshot_path)?;
+    std::fs::remove_file(&new_snapshot_path)?;
+    Ok(())
+}
+
+#[test]
+fn run_given_max_depth_writes_items_up_to_max_depth() -> anyhow::Result<()> {
+    // Arrange
+    let mut output = TestOut::new();
+    let old_snapshot_path = write_test_snapshot(make_old_tree())?;
+    let new_snapshot_path = write_test_snapshot(make_new_tree())?;
+    let mut diff_command = make_diff_command(
+        old_snapshot_path.clone(),
+        new_snapshot_path.clone(),
+        Some(1),
+    );
+
+    // Act
+    diff_command.prepare()?;
+    diff_command.run(&mut output)?;
+
+    // Assert
+    output.expect("    logs\n")?;
+    assert!(!output.contains("a.log"));
+
+    std::fs::remove_file(&old_snapshot_path)?;
+    std::fs::remove_file(&new_snapshot_path)?;
+    Ok(())
+}
+
+#[test]
+fn prepare_given_missing_snapshot_fails() -> anyhow::Result<()> {
+    // Arrange
+    let old_snapshot_path = write_test_snapshot(make_old_tree())?;
+    let new_snapshot_path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    let mut diff_command = make_diff_command(old_snapshot_path.clone(), new_snapshot_path, None);
+
+    // Act
+    let result = diff_command.prepare();
+
+    // Assert
+    assert!(result
+        .err()
+        .unwrap()
+        .to_string()
+        .contains("Could not open the snapshot"));
+
+    std::fs::remove_file(&old_snapshot_path)?;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 pub mod cli_command;
+pub mod diff_command;
 pub mod environment;
 pub mod tui;
 pub mod view_command;
@@ -7,11 +8,12 @@ pub mod view_command;
 mod crossterm_input_event_source;
 
 mod input_event_source;
+mod ncdu;
+pub(crate) mod output_format;
 mod row_item;
-#[cfg_attr(test, allow(dead_code))]
-pub(crate) mod scan_worker;
 mod skin;
-mod view_state;
+mod snapshot;
+pub(crate) mod view_state;
 
 #[cfg(test)]
 mod tui_test_utils;
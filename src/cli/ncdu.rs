@@ -0,0 +1,490 @@
+//! Reads and writes scanned trees in the JSON export format of [ncdu](https://dev.yorhel.nl/ncdu/jsonfmt), which
+//! is the de facto interchange format of disk usage tools.
+
+#[cfg(test)]
+#[path = "./ncdu_test.rs"]
+mod ncdu_test;
+
+use anyhow::Context;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+use space_rs::{
+    DescendantCounts, DirectoryItem, DirectoryItemType, ItemMetadata, ScanError, ScanResult, Size,
+};
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// The major version of the format. Readers must reject other major versions.
+const NCDU_MAJOR_VERSION: u32 = 1;
+/// The minor version of the format. Readers must accept any minor version.
+const NCDU_MINOR_VERSION: u32 = 2;
+
+/// The reasons for excluding an item that ncdu reports for mount points, i.e. directories on another file system.
+const EXCLUDED_OTHER_FILE_SYSTEM: [&str; 2] = ["otherfs", "kernfs"];
+
+/// The information about a single item. The sizes of a directory are those of the directory itself, without its
+/// descendants.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct NcduInfo {
+    name: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    asize: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    dsize: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    excluded: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    notreg: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    read_error: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    gid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mode: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mtime: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct NcduHeader {
+    progname: &'static str,
+    progver: &'static str,
+    timestamp: u64,
+}
+
+/// Reads the JSON of an ncdu export a token at a time. The nested arrays of the directories are walked without
+/// recursion, so that deep trees don't exceed the recursion limit of `serde_json` or overflow the stack, and only the
+/// values within them, e.g. the information about each item, are deserialized.
+struct NcduReader<R: BufRead> {
+    reader: R,
+    /// The JSON of the value being read.
+    value: Vec<u8>,
+}
+
+impl<R: BufRead> NcduReader<R> {
+    fn new(reader: R) -> Self {
+        NcduReader {
+            reader,
+            value: vec![],
+        }
+    }
+
+    /// Returns the next byte that is not whitespace, without consuming it, or `None` at the end of the export.
+    fn peek(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                return Ok(None);
+            }
+            match buffer.iter().position(|byte| !byte.is_ascii_whitespace()) {
+                Some(position) => {
+                    let byte = buffer[position];
+                    self.reader.consume(position);
+                    return Ok(Some(byte));
+                }
+                None => {
+                    let length = buffer.len();
+                    self.reader.consume(length);
+                }
+            }
+        }
+    }
+
+    /// Consumes and returns the next byte that is not whitespace.
+    fn next_byte(&mut self) -> anyhow::Result<u8> {
+        let byte = self.peek()?.context("The ncdu export ended unexpectedly")?;
+        self.reader.consume(1);
+        Ok(byte)
+    }
+
+    fn expect(&mut self, expected: u8) -> anyhow::Result<()> {
+        let byte = self.next_byte()?;
+        if byte != expected {
+            anyhow::bail!(
+                "Expected '{}' but found '{}' in the ncdu export",
+                expected as char,
+                byte as char
+            );
+        }
+        Ok(())
+    }
+
+    /// Reads and deserializes the next value, i.e. up to its closing bracket or to the `,` or `]` that follows it.
+    fn read_value<T: DeserializeOwned>(&mut self) -> anyhow::Result<T> {
+        self.value.clear();
+        let mut nesting = 0usize;
+        let mut in_string = false;
+        let mut is_escaped = false;
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                break;
+            }
+            let mut length = 0;
+            let mut is_complete = false;
+            for &byte in buffer {
+                if in_string {
+                    match byte {
+                        _ if is_escaped => is_escaped = false,
+                        b'\\' => is_escaped = true,
+                        b'"' => in_string = false,
+                        _ => {}
+                    }
+                } else {
+                    match byte {
+                        b',' | b']' | b'}' if nesting == 0 => {
+                            is_complete = true;
+                            break;
+                        }
+                        b'"' => in_string = true,
+                        b'[' | b'{' => nesting += 1,
+                        b']' | b'}' => nesting -= 1,
+                        _ => {}
+                    }
+                }
+                length += 1;
+                if nesting == 0 && !in_string && matches!(byte, b']' | b'}') {
+                    is_complete = true;
+                    break;
+                }
+            }
+            self.value.extend_from_slice(&buffer[..length]);
+            self.reader.consume(length);
+            if is_complete {
+                break;
+            }
+        }
+        Ok(serde_json::from_slice(&self.value)?)
+    }
+}
+
+/// A directory whose children are being read.
+struct NcduDirectory {
+    /// The item of the directory, or `None` if it is left out, in which case so are its descendants.
+    item: Option<DirectoryItem>,
+    path: PathBuf,
+}
+
+impl NcduDirectory {
+    /// Returns `true` if the children are added to the directory, i.e. unless it is left out or is a mount point.
+    fn has_children(&self) -> bool {
+        self.item
+            .as_ref()
+            .is_some_and(|item| item.item_type == DirectoryItemType::Directory)
+    }
+
+    fn add_child(&mut self, child: Option<DirectoryItem>) {
+        if let (Some(item), Some(child)) = (&mut self.item, child) {
+            item.children.push(child);
+        }
+    }
+
+    fn finish(self) -> Option<DirectoryItem> {
+        let mut item = self.item?;
+        if item.item_type == DirectoryItemType::Directory {
+            item.children.sort();
+            update_stats_from_children(&mut item);
+        }
+        Some(item)
+    }
+}
+
+/// Reads the ncdu export at `path`, see [`read_ncdu`].
+pub(crate) fn read_ncdu_file(path: &Path) -> anyhow::Result<ScanResult> {
+    let file = fs::File::open(path)
+        .with_context(|| format!("Could not open the ncdu export {}", path.display()))?;
+    read_ncdu(file).with_context(|| format!("Could not read the ncdu export {}", path.display()))
+}
+
+/// Reads an ncdu export as if it were the result of a scan of its root. Excluded items are left out, as they are
+/// when scanning, except for directories on other file systems which are listed as mount points. Items that ncdu
+/// could not read are reported as errors. The sizes of each directory include the size that ncdu reports for the
+/// directory itself, so that the totals match those shown by ncdu.
+pub(crate) fn read_ncdu<R: Read>(reader: R) -> anyhow::Result<ScanResult> {
+    // The export is an array of the major and minor version, the metadata of the export and the root directory. Any
+    // minor version can be read, so it is ignored.
+    let mut reader = NcduReader::new(BufReader::new(reader));
+    reader.expect(b'[')?;
+    let major_version: u32 = reader.read_value()?;
+    if major_version != NCDU_MAJOR_VERSION {
+        anyhow::bail!(
+            "Unsupported ncdu export version {major_version}, expected {NCDU_MAJOR_VERSION}"
+        );
+    }
+    reader.expect(b',')?;
+    reader.read_value::<IgnoredAny>()?;
+    reader.expect(b',')?;
+    reader.read_value::<IgnoredAny>()?;
+    reader.expect(b',')?;
+
+    let mut errors = vec![];
+    let items = read_tree(&mut reader, &mut errors)?.into_iter().collect();
+    reader.expect(b']')?;
+    Ok(ScanResult { items, errors })
+}
+
+/// Reads the tree of the root. A directory is an array of its own information followed by its children, while any
+/// other item is an object.
+fn read_tree<R: BufRead>(
+    reader: &mut NcduReader<R>,
+    errors: &mut Vec<ScanError>,
+) -> anyhow::Result<Option<DirectoryItem>> {
+    let mut directories: Vec<NcduDirectory> = vec![];
+    loop {
+        let is_directory = reader.peek()? == Some(b'[');
+        if is_directory {
+            reader.expect(b'[')?;
+            if reader.peek()? == Some(b']') {
+                anyhow::bail!("An ncdu directory array must start with the directory information");
+            }
+        }
+        let info: NcduInfo = reader.read_value()?;
+        let parent = directories.last();
+        let path = match parent {
+            Some(parent) => parent.path.join(&info.name),
+            None => PathBuf::from(&info.name),
+        };
+        let item = if parent.is_some_and(|parent| !parent.has_children()) {
+            None
+        } else {
+            to_directory_item(info, is_directory, &path, errors)
+        };
+
+        let mut completed = if is_directory {
+            directories.push(NcduDirectory { item, path });
+            None
+        } else {
+            Some(item)
+        };
+        // Add the completed items to their parents, until the next sibling.
+        loop {
+            if let Some(item) = completed.take() {
+                match directories.last_mut() {
+                    Some(parent) => parent.add_child(item),
+                    None => return Ok(item),
+                }
+            }
+            match reader.next_byte()? {
+                b',' => break,
+                b']' => {
+                    completed = directories.pop().map(NcduDirectory::finish);
+                }
+                byte => anyhow::bail!(
+                    "Expected ',' or ']' but found '{}' in the ncdu export",
+                    byte as char
+                ),
+            }
+        }
+    }
+}
+
+/// Builds the item, without its children, for the information that ncdu reports about it.
+fn to_directory_item(
+    info: NcduInfo,
+    is_directory: bool,
+    path: &Path,
+    errors: &mut Vec<ScanError>,
+) -> Option<DirectoryItem> {
+    if info.read_error {
+        errors.push(ScanError::new(
+            path,
+            &io::Error::new(io::ErrorKind::Other, "ncdu could not read this item"),
+        ));
+    }
+
+    let item_type = match (&info.excluded, is_directory) {
+        (Some(excluded), _) if EXCLUDED_OTHER_FILE_SYSTEM.contains(&excluded.as_str()) => {
+            DirectoryItemType::MountPoint
+        }
+        (Some(_), _) => return None,
+        (None, true) => DirectoryItemType::Directory,
+        (None, false) if info.notreg => DirectoryItemType::Unknown,
+        (None, false) => DirectoryItemType::File,
+    };
+    let metadata = to_item_metadata(&info);
+    let mut item = DirectoryItem {
+        path_segment: info.name,
+        item_type,
+        size_in_bytes: Size::new(info.asize),
+        allocated_size_in_bytes: Size::new(info.dsize),
+        descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
+        children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata,
+    };
+    if item.item_type == DirectoryItemType::MountPoint {
+        // Mount points are not descended into, so they have no size.
+        item.size_in_bytes = Size::default();
+        item.allocated_size_in_bytes = Size::default();
+    }
+    Some(item)
+}
+
+/// Adds the sizes and counts of the children of a directory to those of the directory itself.
+fn update_stats_from_children(item: &mut DirectoryItem) {
+    let mut size_in_bytes = item.size_in_bytes.get_value();
+    let mut allocated_size_in_bytes = item.allocated_size_in_bytes.get_value();
+    for child in &item.children {
+        if child.item_type == DirectoryItemType::Directory {
+            item.descendant_count += child.descendant_count;
+            item.descendant_counts.add(&child.descendant_counts);
+        }
+        item.descendant_count += 1;
+        item.descendant_counts.add_item_type(&child.item_type);
+        size_in_bytes += child.size_in_bytes.get_value();
+        allocated_size_in_bytes += child.allocated_size_in_bytes.get_value();
+    }
+    item.size_in_bytes = Size::new(size_in_bytes);
+    item.allocated_size_in_bytes = Size::new(allocated_size_in_bytes);
+}
+
+fn to_item_metadata(info: &NcduInfo) -> Option<Box<ItemMetadata>> {
+    if info.mtime.is_none() && info.uid.is_none() && info.gid.is_none() && info.mode.is_none() {
+        return None;
+    }
+    Some(Box::new(ItemMetadata {
+        modified: info
+            .mtime
+            .map(|mtime| SystemTime::UNIX_EPOCH + Duration::from_secs(mtime)),
+        uid: info.uid,
+        gid: info.gid,
+        mode: info.mode,
+        ..Default::default()
+    }))
+}
+
+/// Writes a scanned tree as an ncdu export, e.g. to browse it with `ncdu -f`. The format only supports a single
+/// root. The full tree is written, and the items at the paths of the errors are marked as not read. Symbolic links
+/// and items of an unknown type are written as items that are not regular files, and mount points as excluded
+/// items on another file system.
+pub(crate) fn write_ncdu<W: Write>(
+    items: &[DirectoryItem],
+    errors: &[ScanError],
+    writer: &mut W,
+) -> anyhow::Result<()> {
+    let root = match items {
+        [root] => root,
+        _ => anyhow::bail!(
+            "The ncdu export format only supports a single target path, but {} were scanned",
+            items.len()
+        ),
+    };
+
+    let header = NcduHeader {
+        progname: "space",
+        progver: env!("CARGO_PKG_VERSION"),
+        timestamp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+    };
+    let error_paths: HashSet<_> = errors.iter().map(|error| error.path.as_path()).collect();
+
+    let mut writer = BufWriter::new(writer);
+    write!(writer, "[{NCDU_MAJOR_VERSION},{NCDU_MINOR_VERSION},")?;
+    serde_json::to_writer(&mut writer, &header)?;
+    writer.write_all(b",\n")?;
+    write_tree(root, &error_paths, &mut writer)?;
+    writer.write_all(b"]\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the tree of the root, in the format that [`read_tree`] reads. The directories are written with an explicit
+/// stack, so that the depth of the tree is not limited by the call stack.
+fn write_tree<W: Write>(
+    root: &DirectoryItem,
+    error_paths: &HashSet<&Path>,
+    writer: &mut W,
+) -> anyhow::Result<()> {
+    // The children that are left to write of each directory being written, along with its path.
+    let mut directories: Vec<(std::slice::Iter<DirectoryItem>, PathBuf)> = vec![];
+    let mut next = Some((root, PathBuf::from(&root.path_segment)));
+    loop {
+        if let Some((item, path)) = next.take() {
+            let info = to_ncdu_info(item, error_paths.contains(path.as_path()));
+            if item.item_type == DirectoryItemType::Directory {
+                writer.write_all(b"[")?;
+                serde_json::to_writer(&mut *writer, &info)?;
+                directories.push((item.children.iter(), path));
+            } else {
+                serde_json::to_writer(&mut *writer, &info)?;
+            }
+        }
+
+        let Some((children, path)) = directories.last_mut() else {
+            return Ok(());
+        };
+        match children.next() {
+            Some(child) => {
+                writer.write_all(b",\n")?;
+                next = Some((child, path.join(&child.path_segment)));
+            }
+            None => {
+                writer.write_all(b"]")?;
+                directories.pop();
+            }
+        }
+    }
+}
+
+fn to_ncdu_info(item: &DirectoryItem, read_error: bool) -> NcduInfo {
+    let mut info = NcduInfo {
+        name: item.path_segment.clone(),
+        asize: item.size_in_bytes.get_value(),
+        dsize: item.allocated_size_in_bytes.get_value(),
+        read_error,
+        ..Default::default()
+    };
+    if let Some(metadata) = &item.metadata {
+        info.uid = metadata.uid;
+        info.gid = metadata.gid;
+        info.mode = metadata.mode;
+        info.mtime = metadata
+            .modified
+            .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs());
+    }
+
+    match item.item_type {
+        DirectoryItemType::Directory => {
+            // The sizes of a directory are only those of the directory itself, which are not counted when scanning.
+            // A directory at the maximum depth has no children, so it keeps the sizes of all its descendants.
+            for child in &item.children {
+                info.asize = info.asize.saturating_sub(child.size_in_bytes.get_value());
+                info.dsize = info
+                    .dsize
+                    .saturating_sub(child.allocated_size_in_bytes.get_value());
+            }
+        }
+        DirectoryItemType::File | DirectoryItemType::HardLink => {}
+        DirectoryItemType::SymbolicLink | DirectoryItemType::Unknown => info.notreg = true,
+        DirectoryItemType::MountPoint => {
+            info.asize = 0;
+            info.dsize = 0;
+            info.excluded = Some(EXCLUDED_OTHER_FILE_SYSTEM[0].to_string());
+        }
+    }
+    info
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
@@ -0,0 +1,268 @@
+use super::{read_ncdu, write_ncdu};
+use crate::test_directory_utils::{create_test_directory_tree, delete_test_directory_tree};
+use rstest::rstest;
+use serde_json::Value;
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, ScanError, ScanOptions, Size};
+use std::{
+    io,
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc},
+    time::{Duration, SystemTime},
+};
+
+const NCDU_EXPORT: &str = r#"[1, 2, {"progname": "ncdu", "progver": "1.19", "timestamp": 1700000000},
+[{"name": "/srv", "asize": 4096, "dsize": 4096, "ino": 2},
+  {"name": "a.log", "asize": 1000, "dsize": 4096, "mtime": 1700000000, "uid": 1000, "gid": 100, "mode": 33188},
+  [{"name": "data", "asize": 4096, "dsize": 4096},
+    {"name": "b.bin", "asize": 5000, "dsize": 8192},
+    {"name": "link", "asize": 7, "dsize": 0, "notreg": true}
+  ],
+  {"name": "mnt", "excluded": "otherfs"},
+  {"name": "cache", "excluded": "pattern"},
+  [{"name": "denied", "asize": 4096, "dsize": 4096, "read_error": true}]
+]]"#;
+
+fn build(path: &std::path::Path) -> DirectoryItem {
+    DirectoryItem::from_root(path, &Arc::new(AtomicBool::new(false)))
+}
+
+fn find_child<'a>(item: &'a DirectoryItem, name: &str) -> &'a DirectoryItem {
+    item.children
+        .iter()
+        .find(|child| child.path_segment == name)
+        .unwrap()
+}
+
+fn make_item(
+    name: &str,
+    item_type: DirectoryItemType,
+    size: u64,
+    children: Vec<DirectoryItem>,
+) -> DirectoryItem {
+    DirectoryItem {
+        path_segment: name.to_string(),
+        item_type,
+        size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::new(size),
+        descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
+        children,
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
+    }
+}
+
+#[rstest]
+fn read_ncdu_includes_directory_sizes_and_skips_excluded_items() -> anyhow::Result<()> {
+    // Act
+    let result = read_ncdu(NCDU_EXPORT.as_bytes())?;
+
+    // Assert
+    assert_eq!(1, result.items.len());
+    let root = &result.items[0];
+    assert_eq!("/srv", root.path_segment);
+    assert_eq!(DirectoryItemType::Directory, root.item_type);
+    assert_eq!(
+        4096 + 1000 + 4096 + 5000 + 7 + 4096,
+        root.size_in_bytes.get_value()
+    );
+    assert_eq!(
+        4096 + 4096 + 4096 + 8192 + 4096,
+        root.allocated_size_in_bytes.get_value()
+    );
+    assert_eq!(6, root.descendant_count);
+    assert_eq!(2, root.descendant_counts.files);
+    assert_eq!(2, root.descendant_counts.directories);
+    assert_eq!(2, root.descendant_counts.other);
+    let names: Vec<_> = root
+        .children
+        .iter()
+        .map(|child| child.path_segment.as_str())
+        .collect();
+    assert_eq!(vec!["data", "denied", "a.log", "mnt"], names);
+    assert_eq!(
+        DirectoryItemType::Unknown,
+        find_child(find_child(root, "data"), "link").item_type
+    );
+    let mount_point = find_child(root, "mnt");
+    assert_eq!(DirectoryItemType::MountPoint, mount_point.item_type);
+    assert_eq!(0, mount_point.size_in_bytes.get_value());
+    Ok(())
+}
+
+#[rstest]
+fn read_ncdu_reads_extended_information_as_metadata() -> anyhow::Result<()> {
+    // Act
+    let result = read_ncdu(NCDU_EXPORT.as_bytes())?;
+
+    // Assert
+    let file = find_child(&result.items[0], "a.log");
+    let metadata = file.metadata.as_ref().unwrap();
+    assert_eq!(
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+        metadata.modified
+    );
+    assert_eq!(Some(1000), metadata.uid);
+    assert_eq!(Some(100), metadata.gid);
+    assert_eq!(Some("rw-r--r--".to_string()), metadata.format_permissions());
+    assert!(find_child(&result.items[0], "data").metadata.is_none());
+    Ok(())
+}
+
+#[rstest]
+fn read_ncdu_reports_items_that_could_not_be_read_as_errors() -> anyhow::Result<()> {
+    // Act
+    let result = read_ncdu(NCDU_EXPORT.as_bytes())?;
+
+    // Assert
+    let paths: Vec<_> = result.errors.iter().map(|error| &error.path).collect();
+    assert_eq!(vec![&PathBuf::from("/srv").join("denied")], paths);
+    Ok(())
+}
+
+#[rstest]
+#[case(r#"[2, 0, {}, [{"name": "/"}]]"#, "Unsupported ncdu export version 2")]
+#[case(r#"[1, 2, {}, []]"#, "must start with the directory information")]
+#[case(r#"{"name": "/"}"#, "Expected '[' but found '{'")]
+#[case(r#"[1, 2, {}, [{"name": "/"}, {"name": "a"}"#, "ended unexpectedly")]
+#[case(
+    r#"[1, 2, {}, [{"name": "/"} {"name": "a"}]]"#,
+    "Expected ',' or ']' but found '{'"
+)]
+fn read_ncdu_given_invalid_export_fails(#[case] export: &str, #[case] expected_error: &str) {
+    // Act
+    let result = read_ncdu(export.as_bytes());
+
+    // Assert
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains(expected_error), "{error}");
+}
+
+#[rstest]
+fn write_ncdu_writes_version_header_and_tree() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let items = vec![build(&temp_dir)];
+    let mut output = vec![];
+
+    // Act
+    write_ncdu(&items, &[], &mut output)?;
+
+    // Assert
+    let json: Value = serde_json::from_slice(&output)?;
+    assert_eq!(1, json[0]);
+    assert_eq!(2, json[1]);
+    assert_eq!("space", json[2]["progname"]);
+    assert!(json[2]["timestamp"].is_u64());
+    let root = &json[3];
+    assert_eq!(temp_dir.to_string_lossy().as_ref(), root[0]["name"]);
+    // The size of the directory itself is not counted when scanning.
+    assert!(root[0].get("asize").is_none());
+    assert_eq!(1 + items[0].children.len(), root.as_array().unwrap().len());
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+fn write_ncdu_then_read_ncdu_round_trips_scanned_tree() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let mut result = ScanOptions::default()
+        .collect_metadata(true)
+        .build()?
+        .scan(vec![temp_dir.clone()], &Arc::new(AtomicBool::new(false)));
+    let errors = vec![ScanError::new(
+        &temp_dir.join("1"),
+        &io::Error::from(io::ErrorKind::PermissionDenied),
+    )];
+    let mut output = vec![];
+
+    // Act
+    write_ncdu(&result.items, &errors, &mut output)?;
+    let read = read_ncdu(output.as_slice())?;
+
+    // Assert
+    let expected = result.items.remove(0);
+    let expected: Vec<_> = expected.iter_depth_first().collect();
+    let actual: Vec<_> = read.items[0].iter_depth_first().collect();
+    assert_eq!(expected.len(), actual.len());
+    for ((expected_path, expected, _), (actual_path, actual, _)) in
+        expected.iter().zip(actual.iter())
+    {
+        assert_eq!(expected_path, actual_path);
+        assert_eq!(expected.size_in_bytes, actual.size_in_bytes);
+        assert_eq!(
+            expected.allocated_size_in_bytes,
+            actual.allocated_size_in_bytes
+        );
+        assert_eq!(expected.descendant_count, actual.descendant_count);
+        let expected_metadata = expected.metadata.as_ref().unwrap();
+        let actual_metadata = actual.metadata.as_ref().unwrap();
+        assert_eq!(expected_metadata.mode, actual_metadata.mode);
+        assert_eq!(expected_metadata.uid, actual_metadata.uid);
+    }
+    let paths: Vec<_> = read.errors.iter().map(|error| &error.path).collect();
+    assert_eq!(vec![&temp_dir.join("1")], paths);
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+fn write_ncdu_then_read_ncdu_round_trips_deep_tree() -> anyhow::Result<()> {
+    // Arrange
+    let depth = 10_000;
+    let mut item = make_item("leaf", DirectoryItemType::File, 100, vec![]);
+    for level in (0..depth).rev() {
+        item = make_item(
+            &level.to_string(),
+            DirectoryItemType::Directory,
+            100,
+            vec![item],
+        );
+    }
+    let mut output = vec![];
+
+    // Act
+    write_ncdu(&[item], &[], &mut output)?;
+    let read = read_ncdu(output.as_slice())?;
+
+    // Assert
+    let mut item = &read.items[0];
+    let mut read_depth = 0;
+    while let [child] = item.children.as_slice() {
+        item = child;
+        read_depth += 1;
+    }
+    assert_eq!(depth, read_depth);
+    assert_eq!("leaf", item.path_segment);
+    assert_eq!(100, read.items[0].size_in_bytes.get_value());
+    assert_eq!(depth, read.items[0].descendant_count);
+    Ok(())
+}
+
+#[rstest]
+fn write_ncdu_given_multiple_roots_fails() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let items = vec![
+        build(&temp_dir.join("1")),
+        build(&temp_dir.join("1").join("1.5")),
+    ];
+
+    // Act
+    let result = write_ncdu(&items, &[], &mut vec![]);
+
+    // Assert
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("only supports a single target path, but 2 were scanned"));
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use clap::ValueEnum;
+
+/// The format of the output of a non-interactive run.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// A table with a size bar per item, like the TUI.
+    #[default]
+    Table,
+    /// A JSON document with the tree of items, e.g. to pipe into `jq`.
+    Json,
+    /// Comma-separated values with one row per item, e.g. to open in a spreadsheet.
+    Csv,
+    /// Tab-separated values with one row per item, e.g. to process with `awk` or `cut`.
+    Tsv,
+    /// `<size>\t<path>` lines like those of `du -h`, with each directory after its descendants, e.g. to replace `du`
+    /// in existing scripts.
+    Du,
+    /// A single self-contained HTML page with a zoomable treemap and a collapsible tree, e.g. to share a report.
+    Html,
+    /// A Markdown document with a summary of the roots, the total and the filters, and a nested list of items with
+    /// their sizes and percentages, e.g. to paste into an issue or a wiki.
+    #[value(alias = "md")]
+    Markdown,
+    /// A standalone SVG image with a treemap or icicle chart of the full tree, regardless of the regex filter, with
+    /// labels for the items that are at least the size threshold, e.g. to embed in a report.
+    Svg,
+    /// The JSON export format of ncdu with the full tree, regardless of the size threshold and regex filter, e.g. to
+    /// browse it with `ncdu -f`.
+    Ncdu,
+}
+
+impl OutputFormat {
+    /// Returns `true` if the output is meant to be read by other tools, in which case only the items are written,
+    /// without any progress or status messages.
+    pub(crate) fn is_machine_readable(&self) -> bool {
+        *self != OutputFormat::Table
+    }
+}
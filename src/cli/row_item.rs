@@ -1,9 +1,11 @@
-use space_rs::{DirectoryItem, DirectoryItemType, Size};
+use chrono::{DateTime, Local};
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, ItemMetadata, Size, SizeMode};
 use std::{
     cell::RefCell,
     fmt::Display,
     path::PathBuf,
     rc::{Rc, Weak},
+    time::SystemTime,
 };
 
 #[cfg(test)]
@@ -14,16 +16,38 @@ mod row_item_test;
 #[path = "./row_item_prefix_test.rs"]
 mod row_item_prefix_test;
 
+const DETAILS_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+const UNKNOWN_VALUE: &str = "-";
+
 #[derive(PartialEq)]
 pub(crate) enum RowItemType {
     Directory,
     File,
     SymbolicLink,
+    HardLink,
+    MountPoint,
     Unknown,
 }
 
+impl RowItemType {
+    /// Returns the name of the type as used in machine readable output, which matches the serialized
+    /// `DirectoryItemType`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RowItemType::Directory => "directory",
+            RowItemType::File => "file",
+            RowItemType::SymbolicLink => "symbolic_link",
+            RowItemType::HardLink => "hard_link",
+            RowItemType::MountPoint => "mount_point",
+            RowItemType::Unknown => "unknown",
+        }
+    }
+}
+
 pub(crate) struct RowItem {
     pub size: Size,
+    /// The size of the items within this item that match the ignore files, if ignored items are tagged.
+    pub ignored_size: Size,
     pub has_children: bool,
     pub expanded: bool,
     pub tree_prefix: String,
@@ -31,9 +55,14 @@ pub(crate) struct RowItem {
     pub incl_fraction: f32,
     pub peer_fraction: f32,
     pub path_segment: String,
+    pub link_target: Option<PathBuf>,
+    /// The timestamps, ownership and permissions of the item, if collected.
+    pub metadata: Option<Box<ItemMetadata>>,
     pub children: Vec<Rc<RefCell<RowItem>>>,
     pub parent: Option<Weak<RefCell<RowItem>>>,
     pub descendant_count: usize,
+    /// The number of descendants per type. The total is equal to `descendant_count`.
+    pub descendant_counts: DescendantCounts,
     pub row_index: usize,
     pub depth: usize,
     pub max_child_size: u64,
@@ -46,6 +75,7 @@ pub(crate) struct RowItem {
 impl RowItem {
     pub fn from_directory_item(
         dir_item: &DirectoryItem,
+        size_mode: SizeMode,
         total_size_in_bytes: u64,
         parent: Option<Weak<RefCell<RowItem>>>,
         parent_row_index: usize,
@@ -59,7 +89,8 @@ impl RowItem {
         };
 
         let current = Rc::new(RefCell::new(RowItem {
-            size: dir_item.size_in_bytes,
+            size: dir_item.get_size(size_mode),
+            ignored_size: dir_item.get_ignored_size(size_mode),
             has_children,
             expanded: false,
             tree_prefix: String::default(),
@@ -67,14 +98,21 @@ impl RowItem {
                 DirectoryItemType::Directory => RowItemType::Directory,
                 DirectoryItemType::File => RowItemType::File,
                 DirectoryItemType::SymbolicLink => RowItemType::SymbolicLink,
+                DirectoryItemType::HardLink => RowItemType::HardLink,
+                DirectoryItemType::MountPoint => RowItemType::MountPoint,
                 DirectoryItemType::Unknown => RowItemType::Unknown,
             },
-            incl_fraction: dir_item.get_fraction(total_size_in_bytes),
+            incl_fraction: dir_item
+                .get_size(size_mode)
+                .get_fraction(total_size_in_bytes),
             peer_fraction: 0.0,
             path_segment: dir_item.path_segment.clone(),
+            link_target: dir_item.link_target.clone(),
+            metadata: dir_item.metadata.clone(),
             children: vec![],
             parent,
             descendant_count: dir_item.descendant_count,
+            descendant_counts: dir_item.descendant_counts,
             depth: 0,
             row_index: current_row_index,
             max_child_size: 0,
@@ -91,6 +129,7 @@ impl RowItem {
                 let child = &dir_item.children[i];
                 children.push(Self::from_directory_item(
                     child,
+                    size_mode,
                     total_size_in_bytes,
                     Some(Rc::downgrade(&current)),
                     current_row_index,
@@ -213,6 +252,82 @@ impl RowItem {
         add_path(self, &mut path);
         path
     }
+
+    /// Returns the suffix that shows the share of the size of this item that matches the ignore files, if any.
+    pub fn get_ignored_share_suffix(&self) -> String {
+        let ignored_size = self.ignored_size.get_value();
+        if ignored_size == 0 {
+            return String::default();
+        }
+        let ignored_percentage = ignored_size as f64 * 100f64 / self.size.get_value().max(1) as f64;
+        format!(" ({:.0}% ignored)", ignored_percentage.floor())
+    }
+
+    /// Returns the counts of this item and its descendants, e.g. to add them to the counts of an ancestor.
+    pub fn get_counts_including_self(&self) -> DescendantCounts {
+        let mut counts = self.descendant_counts;
+        match self.item_type {
+            RowItemType::File => counts.files += 1,
+            RowItemType::Directory => counts.directories += 1,
+            RowItemType::SymbolicLink => counts.symlinks += 1,
+            RowItemType::HardLink | RowItemType::MountPoint | RowItemType::Unknown => {
+                counts.other += 1
+            }
+        }
+        counts
+    }
+
+    /// Returns a compact summary of the metadata of this item, i.e. the modification time, owner and permissions,
+    /// or `None` if the metadata was not collected.
+    pub fn get_metadata_summary(&self) -> Option<String> {
+        self.metadata.as_ref().map(|metadata| {
+            format!(
+                "{:16}  {:>11}  {}",
+                format_time(metadata.modified, "%Y-%m-%d %H:%M"),
+                format_owner(metadata),
+                metadata.format_permissions().unwrap_or_default()
+            )
+        })
+    }
+
+    /// Returns the labelled metadata of this item, as shown in the details view, or `None` if the metadata was not
+    /// collected.
+    pub fn get_metadata_details(&self) -> Option<Vec<(&'static str, String)>> {
+        self.metadata.as_ref().map(|metadata| {
+            vec![
+                (
+                    "Modified",
+                    format_time(metadata.modified, DETAILS_TIME_FORMAT),
+                ),
+                (
+                    "Accessed",
+                    format_time(metadata.accessed, DETAILS_TIME_FORMAT),
+                ),
+                (
+                    "Changed",
+                    format_time(metadata.changed, DETAILS_TIME_FORMAT),
+                ),
+                ("Owner (uid:gid)", format_owner(metadata)),
+                (
+                    "Permissions",
+                    match (metadata.format_permissions(), metadata.mode) {
+                        (Some(permissions), Some(mode)) => {
+                            format!("{permissions} ({:04o})", mode & 0o7777)
+                        }
+                        _ => UNKNOWN_VALUE.to_string(),
+                    },
+                ),
+            ]
+        })
+    }
+
+    /// Returns the suffix that marks an item that was reached by following a link, showing the link target.
+    pub fn get_link_target_suffix(&self) -> String {
+        match &self.link_target {
+            Some(link_target) => format!(" → {}", link_target.display()),
+            None => String::default(),
+        }
+    }
 }
 
 impl Display for RowItem {
@@ -227,6 +342,21 @@ impl Display for RowItem {
     }
 }
 
+/// Formats `time` in the local time zone, or returns a placeholder if it is not known.
+fn format_time(time: Option<SystemTime>, format: &str) -> String {
+    match time {
+        Some(time) => DateTime::<Local>::from(time).format(format).to_string(),
+        None => UNKNOWN_VALUE.to_string(),
+    }
+}
+
+fn format_owner(metadata: &ItemMetadata) -> String {
+    match (metadata.uid, metadata.gid) {
+        (Some(uid), Some(gid)) => format!("{uid}:{gid}"),
+        _ => UNKNOWN_VALUE.to_string(),
+    }
+}
+
 fn add_path(row_item: &RowItem, path: &mut PathBuf) {
     if let Some(parent) = &row_item.parent {
         if let Some(parent) = parent.upgrade() {
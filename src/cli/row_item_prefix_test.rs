@@ -1,10 +1,11 @@
 use crate::cli::row_item::{RowItem, RowItemType};
-use space_rs::Size;
+use space_rs::{DescendantCounts, Size};
 use std::{cell::RefCell, rc::Rc};
 
 fn make_child_rc(name: &str) -> Rc<RefCell<RowItem>> {
     Rc::new(RefCell::new(RowItem {
         size: Size::new(0),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: false,
         tree_prefix: String::default(),
@@ -12,9 +13,12 @@ fn make_child_rc(name: &str) -> Rc<RefCell<RowItem>> {
         incl_fraction: 0f32,
         peer_fraction: 0.0,
         path_segment: name.to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -28,6 +32,7 @@ fn make_child_rc(name: &str) -> Rc<RefCell<RowItem>> {
 fn make_parent_with_children(children: Vec<Rc<RefCell<RowItem>>>) -> RowItem {
     RowItem {
         size: Size::new(0),
+        ignored_size: Size::default(),
         has_children: !children.is_empty(),
         expanded: true,
         tree_prefix: "─┬".to_string(),
@@ -35,9 +40,12 @@ fn make_parent_with_children(children: Vec<Rc<RefCell<RowItem>>>) -> RowItem {
         incl_fraction: 0f32,
         peer_fraction: 0.0,
         path_segment: "root".to_string(),
+        link_target: None,
+        metadata: None,
         children,
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
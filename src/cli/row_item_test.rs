@@ -1,5 +1,5 @@
 use crate::cli::row_item::{RowItem, RowItemType};
-use space_rs::Size;
+use space_rs::{DescendantCounts, Size};
 use std::{
     cell::RefCell,
     path::{self, PathBuf},
@@ -9,6 +9,7 @@ use std::{
 fn make_leaf_row_item(size: u64) -> RowItem {
     RowItem {
         size: Size::new(size),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: false,
         tree_prefix: String::default(),
@@ -16,9 +17,12 @@ fn make_leaf_row_item(size: u64) -> RowItem {
         incl_fraction: 0f32,
         peer_fraction: 0.0,
         path_segment: "item".to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -34,6 +38,7 @@ fn display_outputs_path_and_number_of_children() {
     // Arrange
     let item = RowItem {
         size: Size::default(),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: false,
         tree_prefix: String::default(),
@@ -41,9 +46,12 @@ fn display_outputs_path_and_number_of_children() {
         incl_fraction: 0.1f32,
         peer_fraction: 0.0,
         path_segment: "/some/path".to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -72,6 +80,7 @@ fn get_path_returns_correct_path() {
     // Arrange
     let item1 = Rc::new(RefCell::new(RowItem {
         size: Size::new(1024),
+        ignored_size: Size::default(),
         has_children: true,
         expanded: true,
         tree_prefix: String::default(),
@@ -79,9 +88,12 @@ fn get_path_returns_correct_path() {
         incl_fraction: 0.1f32,
         peer_fraction: 0.0,
         path_segment: format!("some{}path", path::MAIN_SEPARATOR_STR),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 2,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -92,6 +104,7 @@ fn get_path_returns_correct_path() {
     }));
     let item2 = Rc::new(RefCell::new(RowItem {
         size: Size::new(1024),
+        ignored_size: Size::default(),
         has_children: true,
         expanded: true,
         tree_prefix: String::default(),
@@ -99,9 +112,12 @@ fn get_path_returns_correct_path() {
         incl_fraction: 0.1f32,
         peer_fraction: 0.0,
         path_segment: "to".to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: Some(Rc::downgrade(&item1)),
         descendant_count: 1,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -112,6 +128,7 @@ fn get_path_returns_correct_path() {
     }));
     let item3 = Rc::new(RefCell::new(RowItem {
         size: Size::new(1024),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: false,
         tree_prefix: String::default(),
@@ -119,9 +136,12 @@ fn get_path_returns_correct_path() {
         incl_fraction: 0.1f32,
         peer_fraction: 0.0,
         path_segment: "file".to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: Some(Rc::downgrade(&item2)),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -207,6 +227,7 @@ fn update_fraction_recursively_updates_children() {
     let child = Rc::new(RefCell::new(make_leaf_row_item(500)));
     let mut parent = RowItem {
         size: Size::new(1000),
+        ignored_size: Size::default(),
         has_children: true,
         expanded: true,
         tree_prefix: String::default(),
@@ -214,9 +235,12 @@ fn update_fraction_recursively_updates_children() {
         incl_fraction: 0f32,
         peer_fraction: 0.0,
         path_segment: "parent".to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![child.clone()],
         parent: None,
         descendant_count: 1,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -239,3 +263,89 @@ fn update_fraction_recursively_updates_children() {
         child.borrow().incl_fraction
     );
 }
+
+#[rstest::rstest]
+#[case(RowItemType::File, DescendantCounts { files: 3, directories: 1, ..Default::default() })]
+#[case(RowItemType::Directory, DescendantCounts { files: 2, directories: 2, ..Default::default() })]
+#[case(RowItemType::SymbolicLink, DescendantCounts { files: 2, directories: 1, symlinks: 1, ..Default::default() })]
+#[case(RowItemType::MountPoint, DescendantCounts { files: 2, directories: 1, other: 1, ..Default::default() })]
+fn get_counts_including_self_counts_item_by_type(
+    #[case] item_type: RowItemType,
+    #[case] expected: DescendantCounts,
+) {
+    // Arrange
+    let mut item = make_leaf_row_item(1);
+    item.item_type = item_type;
+    item.descendant_counts = DescendantCounts {
+        files: 2,
+        directories: 1,
+        ..Default::default()
+    };
+
+    // Act
+    let counts = item.get_counts_including_self();
+
+    // Assert
+    assert_eq!(expected, counts);
+}
+
+#[test]
+fn get_metadata_summary_given_no_metadata_returns_none() {
+    let item = make_leaf_row_item(1);
+    assert_eq!(None, item.get_metadata_summary());
+    assert_eq!(None, item.get_metadata_details());
+}
+
+#[test]
+fn get_metadata_summary_returns_owner_and_permissions() {
+    // Arrange
+    let mut item = make_leaf_row_item(1);
+    item.metadata = Some(Box::new(space_rs::ItemMetadata {
+        uid: Some(1000),
+        gid: Some(100),
+        mode: Some(0o100640),
+        ..Default::default()
+    }));
+
+    // Act
+    let summary = item.get_metadata_summary();
+
+    // Assert
+    assert_eq!(
+        Some(format!("{:16}  {:>11}  rw-r-----", "-", "1000:100")),
+        summary
+    );
+}
+
+#[test]
+fn get_metadata_details_returns_all_labelled_values() {
+    // Arrange
+    let mut item = make_leaf_row_item(1);
+    item.metadata = Some(Box::new(space_rs::ItemMetadata {
+        modified: Some(std::time::SystemTime::now()),
+        uid: Some(0),
+        gid: Some(0),
+        mode: Some(0o040755),
+        ..Default::default()
+    }));
+
+    // Act
+    let details = item.get_metadata_details().unwrap();
+
+    // Assert
+    let labels: Vec<_> = details.iter().map(|(label, _)| *label).collect();
+    assert_eq!(
+        vec![
+            "Modified",
+            "Accessed",
+            "Changed",
+            "Owner (uid:gid)",
+            "Permissions"
+        ],
+        labels
+    );
+    assert_ne!("-", details[0].1);
+    assert_eq!("-", details[1].1);
+    assert_eq!("0:0", details[3].1);
+    assert_eq!("rwxr-xr-x (0755)", details[4].1);
+}
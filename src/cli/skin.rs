@@ -1,4 +1,5 @@
 use ratatui::style::{Color, Modifier, Style};
+use space_rs::{SvgColor, SvgPalette};
 
 #[cfg(test)]
 #[path = "./skin_test.rs"]
@@ -22,8 +23,12 @@ pub(crate) struct Skin {
     pub(crate) item_type_directory_symbol: char,
     pub(crate) item_type_file_symbol: char,
     pub(crate) item_type_symbolic_link_symbol: char,
+    pub(crate) item_type_hard_link_symbol: char,
+    pub(crate) item_type_mount_point_symbol: char,
     pub(crate) item_type_unknown_symbol: char,
     pub(crate) item_type_access_denied_symbol: char,
+    pub(crate) diff_growth_fg_color: Color,
+    pub(crate) diff_shrinkage_fg_color: Color,
 }
 
 impl Default for Skin {
@@ -46,8 +51,12 @@ impl Default for Skin {
             item_type_directory_symbol: '📁',
             item_type_file_symbol: '📄',
             item_type_symbolic_link_symbol: '🔗',
+            item_type_hard_link_symbol: '📎',
+            item_type_mount_point_symbol: '💽',
             item_type_unknown_symbol: '❓',
             item_type_access_denied_symbol: '🚫',
+            diff_growth_fg_color: Color::Rgb(255, 112, 112),
+            diff_shrinkage_fg_color: Color::Rgb(112, 208, 112),
         }
     }
 }
@@ -63,4 +72,44 @@ impl Skin {
         }
         style
     }
+
+    /// Returns the colors of the SVG charts, taken from the title and size bar colors.
+    pub(crate) fn svg_palette(&self) -> SvgPalette {
+        let defaults = SvgPalette::default();
+        let background = to_svg_color(self.title_bg_color, defaults.background);
+        SvgPalette {
+            background,
+            text: to_svg_color(self.title_fg_color, defaults.text),
+            border: background,
+            fills: vec![
+                to_svg_color(self.size_bar_fg_color_even, defaults.fills[0]),
+                to_svg_color(self.size_bar_fg_color_odd, defaults.fills[1]),
+            ],
+        }
+    }
+}
+
+/// Converts a terminal color to an SVG color, using the xterm values of the named colors. Colors that depend on the
+/// terminal, i.e. the reset and indexed colors, are replaced by the fallback.
+fn to_svg_color(color: Color, fallback: SvgColor) -> SvgColor {
+    match color {
+        Color::Rgb(red, green, blue) => SvgColor::new(red, green, blue),
+        Color::Black => SvgColor::new(0, 0, 0),
+        Color::Red => SvgColor::new(205, 0, 0),
+        Color::Green => SvgColor::new(0, 205, 0),
+        Color::Yellow => SvgColor::new(205, 205, 0),
+        Color::Blue => SvgColor::new(0, 0, 238),
+        Color::Magenta => SvgColor::new(205, 0, 205),
+        Color::Cyan => SvgColor::new(0, 205, 205),
+        Color::Gray => SvgColor::new(229, 229, 229),
+        Color::DarkGray => SvgColor::new(127, 127, 127),
+        Color::LightRed => SvgColor::new(255, 0, 0),
+        Color::LightGreen => SvgColor::new(0, 255, 0),
+        Color::LightYellow => SvgColor::new(255, 255, 0),
+        Color::LightBlue => SvgColor::new(92, 92, 255),
+        Color::LightMagenta => SvgColor::new(255, 0, 255),
+        Color::LightCyan => SvgColor::new(0, 255, 255),
+        Color::White => SvgColor::new(255, 255, 255),
+        Color::Reset | Color::Indexed(_) => fallback,
+    }
 }
@@ -1,5 +1,6 @@
 use super::Skin;
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
+use space_rs::{SvgColor, SvgPalette};
 
 #[test]
 fn value_style_with_no_fg_does_not_set_fg() {
@@ -64,3 +65,43 @@ fn value_style_with_reverse_applies_reverse() {
         style
     );
 }
+
+#[test]
+fn svg_palette_uses_title_and_size_bar_colors() {
+    // Arrange
+    let skin = Skin::default();
+
+    // Act
+    let palette = skin.svg_palette();
+
+    // Assert
+    assert_eq!(
+        SvgPalette {
+            background: SvgColor::new(64, 64, 64),
+            text: SvgColor::new(255, 255, 255),
+            border: SvgColor::new(64, 64, 64),
+            fills: vec![SvgColor::new(88, 144, 255), SvgColor::new(56, 100, 192)],
+        },
+        palette
+    );
+}
+
+#[test]
+fn svg_palette_converts_named_colors_and_replaces_terminal_colors() {
+    // Arrange
+    let skin = Skin {
+        title_bg_color: Color::Blue,
+        title_fg_color: Color::Reset,
+        size_bar_fg_color_even: Color::Indexed(42),
+        ..Default::default()
+    };
+
+    // Act
+    let palette = skin.svg_palette();
+
+    // Assert
+    let defaults = SvgPalette::default();
+    assert_eq!(SvgColor::new(0, 0, 238), palette.background);
+    assert_eq!(defaults.text, palette.text);
+    assert_eq!(defaults.fills[0], palette.fills[0]);
+}
@@ -0,0 +1,164 @@
+//! Reads and writes snapshot files, i.e. completed scans that are persisted so that they can be viewed later, or on
+//! another machine, without scanning again.
+//!
+//! A snapshot file starts with [`SNAPSHOT_MAGIC`] and the version of the file format, followed by a gzip stream of
+//! two CBOR values: the [`SnapshotHeader`] and the [`ScanSnapshot`] with the scanned trees.
+
+#[cfg(test)]
+#[path = "./snapshot_test.rs"]
+mod snapshot_test;
+
+use anyhow::Context;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use space_rs::{DirectoryItem, ScanError, ScanResult, ScanSnapshot};
+use std::{
+    fs,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// The bytes that every snapshot file starts with.
+const SNAPSHOT_MAGIC: &[u8; 8] = b"SPACESNP";
+/// The version of the file format, which is incremented whenever the layout of the file changes. Changes to the
+/// scanned trees are covered by the version of the [`ScanSnapshot`] instead.
+const SNAPSHOT_FILE_VERSION: u32 = 1;
+/// The maximum nesting of CBOR values, which is two per level of the tree, i.e. an item and its children.
+const MAX_NESTING: usize = 8192;
+
+/// Describes where and when the scan was done.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct SnapshotHeader {
+    /// The paths of the root items that were scanned.
+    pub(crate) roots: Vec<String>,
+    /// When the snapshot was saved, in seconds since the Unix epoch.
+    pub(crate) timestamp: u64,
+    /// The name of the host that was scanned, or an empty string if it is unknown.
+    pub(crate) host: String,
+    /// The errors that occurred while scanning.
+    pub(crate) errors: Vec<SnapshotError>,
+}
+
+/// An error that occurred while scanning. Only the path and the description are kept.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct SnapshotError {
+    path: String,
+    message: String,
+}
+
+impl SnapshotHeader {
+    /// Creates a header for a scan of the given items that is saved now, on this host.
+    pub(crate) fn new(items: &[DirectoryItem], errors: &[ScanError]) -> Self {
+        SnapshotHeader {
+            roots: items.iter().map(|item| item.path_segment.clone()).collect(),
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+            host: get_host_name(),
+            errors: errors
+                .iter()
+                .map(|error| SnapshotError {
+                    path: error.path.to_string_lossy().into_owned(),
+                    message: error.message.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Writes a snapshot of the given items to `path`, see [`write_snapshot`].
+pub(crate) fn write_snapshot_file(
+    path: &Path,
+    header: &SnapshotHeader,
+    items: Vec<DirectoryItem>,
+) -> anyhow::Result<()> {
+    let file = fs::File::create(path)
+        .with_context(|| format!("Could not create the snapshot {}", path.display()))?;
+    write_snapshot(header, items, BufWriter::new(file))
+        .with_context(|| format!("Could not write the snapshot {}", path.display()))
+}
+
+/// Writes the header and a snapshot of the given items in the snapshot file format.
+pub(crate) fn write_snapshot<W: Write>(
+    header: &SnapshotHeader,
+    items: Vec<DirectoryItem>,
+    mut writer: W,
+) -> anyhow::Result<()> {
+    writer.write_all(SNAPSHOT_MAGIC)?;
+    writer.write_all(&SNAPSHOT_FILE_VERSION.to_le_bytes())?;
+
+    let mut encoder = GzEncoder::new(writer, Compression::default());
+    ciborium::into_writer(header, &mut encoder)?;
+    ciborium::into_writer(&ScanSnapshot::new(items), &mut encoder)?;
+    encoder.finish()?.flush()?;
+    Ok(())
+}
+
+/// Reads the snapshot file at `path`, see [`read_snapshot`].
+pub(crate) fn read_snapshot_file(path: &Path) -> anyhow::Result<(SnapshotHeader, ScanResult)> {
+    let file = fs::File::open(path)
+        .with_context(|| format!("Could not open the snapshot {}", path.display()))?;
+    read_snapshot(BufReader::new(file))
+        .with_context(|| format!("Could not read the snapshot {}", path.display()))
+}
+
+/// Reads a snapshot as if it were the result of a scan, along with its header. The errors that occurred while
+/// scanning are reported as errors of the result.
+pub(crate) fn read_snapshot<R: Read>(
+    mut reader: R,
+) -> anyhow::Result<(SnapshotHeader, ScanResult)> {
+    let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+    let mut version = [0u8; 4];
+    let is_snapshot = reader
+        .read_exact(&mut magic)
+        .and_then(|_| reader.read_exact(&mut version))
+        .is_ok()
+        && &magic == SNAPSHOT_MAGIC;
+    if !is_snapshot {
+        anyhow::bail!("Not a snapshot file");
+    }
+    let version = u32::from_le_bytes(version);
+    if version != SNAPSHOT_FILE_VERSION {
+        anyhow::bail!(
+            "Unsupported snapshot file version {version}, expected {SNAPSHOT_FILE_VERSION}"
+        );
+    }
+
+    let mut decoder = GzDecoder::new(reader);
+    let header: SnapshotHeader =
+        ciborium::de::from_reader_with_recursion_limit(&mut decoder, MAX_NESTING)
+            .context("The snapshot is corrupt or truncated")?;
+    let snapshot: ScanSnapshot =
+        ciborium::de::from_reader_with_recursion_limit(&mut decoder, MAX_NESTING)
+            .context("The snapshot is corrupt or truncated")?;
+
+    let errors = header
+        .errors
+        .iter()
+        .map(|error| ScanError {
+            path: PathBuf::from(&error.path),
+            kind: io::ErrorKind::Other,
+            message: error.message.clone(),
+        })
+        .collect();
+    Ok((
+        header,
+        ScanResult {
+            items: snapshot.items,
+            errors,
+        },
+    ))
+}
+
+/// Returns the name of this host, from the environment or else `/etc/hostname`, or an empty string if it is
+/// unknown.
+fn get_host_name() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .unwrap_or_default()
+}
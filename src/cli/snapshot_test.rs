@@ -0,0 +1,92 @@
+use super::{read_snapshot, write_snapshot, SnapshotHeader, SNAPSHOT_FILE_VERSION, SNAPSHOT_MAGIC};
+use crate::test_directory_utils::{create_test_directory_tree, delete_test_directory_tree};
+use rstest::rstest;
+use space_rs::{ScanError, ScanOptions};
+use std::{
+    io,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+#[rstest]
+fn write_snapshot_then_read_snapshot_round_trips_scanned_tree() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let scan = || {
+        ScanOptions::default()
+            .collect_metadata(true)
+            .build()
+            .unwrap()
+            .scan(vec![temp_dir.clone()], &Arc::new(AtomicBool::new(false)))
+    };
+    let errors = vec![ScanError::new(
+        &temp_dir.join("1"),
+        &io::Error::from(io::ErrorKind::PermissionDenied),
+    )];
+    let header = SnapshotHeader::new(&scan().items, &errors);
+    let mut output = vec![];
+
+    // Act
+    write_snapshot(&header, scan().items, &mut output)?;
+    let (read_header, read) = read_snapshot(output.as_slice())?;
+
+    // Assert
+    assert_eq!(header, read_header);
+    assert_eq!(vec![temp_dir.display().to_string()], read_header.roots);
+    let expected = scan().items.remove(0);
+    let expected: Vec<_> = expected.iter_depth_first().collect();
+    let actual: Vec<_> = read.items[0].iter_depth_first().collect();
+    assert_eq!(expected.len(), actual.len());
+    for ((expected_path, expected, _), (actual_path, actual, _)) in
+        expected.iter().zip(actual.iter())
+    {
+        assert_eq!(expected_path, actual_path);
+        assert_eq!(expected.item_type, actual.item_type);
+        assert_eq!(expected.size_in_bytes, actual.size_in_bytes);
+        assert_eq!(
+            expected.allocated_size_in_bytes,
+            actual.allocated_size_in_bytes
+        );
+        assert_eq!(expected.descendant_count, actual.descendant_count);
+        assert_eq!(expected.metadata, actual.metadata);
+    }
+    assert_eq!(1, read.errors.len());
+    assert_eq!(temp_dir.join("1"), read.errors[0].path);
+    assert_eq!(errors[0].message, read.errors[0].message);
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+fn write_snapshot_writes_magic_and_version_before_compressed_content() -> anyhow::Result<()> {
+    // Arrange
+    let header = SnapshotHeader::new(&[], &[]);
+    let mut output = vec![];
+
+    // Act
+    write_snapshot(&header, vec![], &mut output)?;
+
+    // Assert
+    assert_eq!(SNAPSHOT_MAGIC, &output[..8]);
+    assert_eq!(SNAPSHOT_FILE_VERSION.to_le_bytes(), output[8..12]);
+    // The gzip magic number.
+    assert_eq!([0x1f, 0x8b], output[12..14]);
+    Ok(())
+}
+
+#[rstest]
+#[case(b"", "Not a snapshot file")]
+#[case(b"[1, 2, {}, []]", "Not a snapshot file")]
+#[case(b"SPACESNP\x02\0\0\0", "Unsupported snapshot file version 2")]
+#[case(b"SPACESNP\x01\0\0\0not gzip", "corrupt or truncated")]
+fn read_snapshot_given_invalid_snapshot_fails(
+    #[case] snapshot: &[u8],
+    #[case] expected_error: &str,
+) {
+    // Act
+    let result = read_snapshot(snapshot);
+
+    // Assert
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains(expected_error), "{error}");
+}
@@ -34,8 +34,8 @@ fn render_with_accept_license_input_updates_view_state_and_writes_config_file()
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -48,3 +48,40 @@ fn render_with_accept_license_input_updates_view_state_and_writes_config_file()
 
     Ok(())
 }
+
+#[test]
+#[ignore]
+fn render_with_details_key_shows_details_of_selected_item() -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    let mut output = TestOut::new();
+    let mut input_event_source = TestInputEventSource::new(vec![
+        Event::Key(KeyEvent::new(
+            KeyCode::Char(DETAILS_KEY),
+            KeyModifiers::NONE,
+        )),
+        Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+        Event::Key(KeyEvent::new(KeyCode::Char(QUIT_KEY_1), KeyModifiers::NONE)),
+    ]);
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    // Act
+    render(
+        &mut view_state,
+        &mut output,
+        &mut input_event_source,
+        &Skin::default(),
+        should_exit,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
+    )?;
+
+    // Assert
+    assert!(!view_state.show_details);
+    output.expect("Descendants")?;
+    output.expect("only collected when run with --metadata")?;
+
+    delete_test_directory_tree(&temp_dir_path);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use super::rendering::expand_area;
 use super::{ACCEPT_LICENSE_TERMS_KEY, CONFIRM_DELETE_KEY};
 use crate::cli::{
+    row_item::{RowItem, RowItemType},
     skin::Skin,
     view_state::{table_rows::SPINNER_FRAMES, DeletionState, ViewState},
 };
@@ -12,6 +13,7 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap},
     Frame,
 };
+use space_rs::{Size, SizeMode};
 use std::cmp::{max, min};
 
 macro_rules! get_dialog_area {
@@ -87,8 +89,10 @@ pub(in crate::cli) fn render_delete_dialog<B: Backend>(
 
             let value_style = skin.value_style();
             let selected_item_ref = selected_item.borrow();
-            let is_dir =
-                selected_item_ref.item_type == crate::cli::row_item::RowItemType::Directory;
+            // Deleting a followed link only removes the link, not the contents of its target.
+            let is_dir = selected_item_ref.item_type
+                == crate::cli::row_item::RowItemType::Directory
+                && selected_item_ref.link_target.is_none();
 
             let lines = build_delete_dialog_lines(
                 &selected_item_ref,
@@ -113,7 +117,13 @@ fn build_delete_dialog_lines<'a>(
     vec![
         Line::from(vec![
             Span::raw("Delete "),
-            Span::raw(if is_dir { "directory " } else { "file " }),
+            Span::raw(if is_dir {
+                "directory "
+            } else if selected_item_ref.link_target.is_some() {
+                "link "
+            } else {
+                "file "
+            }),
             Span::styled(selected_item_ref.path_segment.clone(), *value_style),
             Span::styled(
                 if is_dir {
@@ -129,9 +139,7 @@ fn build_delete_dialog_lines<'a>(
         Line::from(vec![
             Span::raw("This will free up at least "),
             Span::styled(
-                selected_item_ref
-                    .size
-                    .to_string(view_state.size_display_format),
+                format_size(&selected_item_ref.size, view_state),
                 *value_style,
             ),
         ]),
@@ -206,6 +214,79 @@ r#"By accepting, you agree to be bound by the terms of the MIT License. You can
     render_centered_dialog(f, lines, Alignment::Left);
 }
 
+pub(in crate::cli) fn render_details_dialog<B: Backend>(
+    f: &mut Frame<B>,
+    view_state: &ViewState,
+    skin: &Skin,
+) {
+    let Some(selected_item) = view_state.get_selected_item() else {
+        return;
+    };
+    let selected_item_ref = selected_item.borrow();
+    let lines = build_details_dialog_lines(&selected_item_ref, view_state, &skin.value_style());
+    render_centered_dialog(f, lines, Alignment::Left);
+}
+
+fn build_details_dialog_lines<'a>(
+    selected_item_ref: &std::cell::Ref<'a, RowItem>,
+    view_state: &ViewState,
+    value_style: &Style,
+) -> Vec<Line<'a>> {
+    let mut details = vec![
+        ("Path", selected_item_ref.get_path().display().to_string()),
+        (
+            "Type",
+            match selected_item_ref.item_type {
+                RowItemType::Directory => "Directory",
+                RowItemType::File => "File",
+                RowItemType::SymbolicLink => "Symbolic link",
+                RowItemType::HardLink => "Hard link",
+                RowItemType::MountPoint => "Mount point",
+                RowItemType::Unknown => "Unknown",
+            }
+            .to_string(),
+        ),
+        ("Size", format_size(&selected_item_ref.size, view_state)),
+    ];
+    if selected_item_ref.item_type == RowItemType::Directory {
+        details.push((
+            "Descendants",
+            selected_item_ref.descendant_count.to_string(),
+        ));
+    }
+    if let Some(link_target) = &selected_item_ref.link_target {
+        details.push(("Link target", link_target.display().to_string()));
+    }
+
+    let metadata_details = selected_item_ref.get_metadata_details();
+    let has_metadata = metadata_details.is_some();
+    details.extend(metadata_details.unwrap_or_default());
+
+    let label_width = details
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    let mut lines: Vec<Line> = details
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::raw(format!("{label:label_width$}  ")),
+                Span::styled(value, *value_style),
+            ])
+        })
+        .collect();
+    if !has_metadata {
+        lines.push(Line::default());
+        lines.push(Line::from(
+            "Timestamps, ownership and permissions are only collected when run with --metadata.",
+        ));
+    }
+    lines.push(Line::default());
+    lines.push(Line::from("Press any key to close."));
+    lines
+}
+
 fn render_centered_dialog<B: Backend>(
     f: &mut Frame<B>,
     lines: Vec<Line<'_>>,
@@ -268,3 +349,11 @@ fn centered_rect(width_percent: u16, ideal_width: u16, height: u16, area: Rect)
         )
         .split(layout[1])[1]
 }
+
+/// Formats a size as measured by the size mode of the view, including the unit.
+fn format_size(size: &Size, view_state: &ViewState) -> String {
+    match view_state.size_mode {
+        SizeMode::Count => format!("{} entries", size.get_value()),
+        SizeMode::Apparent | SizeMode::Allocated => size.to_string(view_state.size_display_format),
+    }
+}
@@ -0,0 +1,339 @@
+//! Renders the diff of two snapshots in a TUI, with the same look and navigation keys as the main TUI.
+
+#[cfg(test)]
+#[path = "diff_test.rs"]
+mod diff_test;
+
+use super::{
+    enter_terminal_command, exit_terminal_command, COLLAPSE_KEY_SYMBOL, EXPAND_KEY_SYMBOL,
+    POLL_TIMEOUT, QUIT_KEY_1, QUIT_KEY_2_SYMBOL, SELECT_NEXT_KEY_SYMBOL, SELECT_PREV_KEY_SYMBOL,
+    VERSION,
+};
+use crate::cli::{
+    diff_command::{
+        diff_render::{describe_total, CHANGE_COLUMN_WIDTH, DELTA_COLUMN_WIDTH, SIZE_COLUMN_WIDTH},
+        diff_tree::{format_change, format_delta, format_size},
+        diff_view_state::DiffViewState,
+    },
+    input_event_source::InputEventSource,
+    skin::Skin,
+};
+#[cfg(not(test))]
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use crossterm::{
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers,
+    },
+    execute,
+};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Terminal,
+};
+use std::{
+    cmp::Ordering,
+    io::Write,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+pub(crate) const TOGGLE_UNCHANGED_KEY: char = 'u';
+
+/// The width of the column with the added and removed markers.
+const MARKER_COLUMN_WIDTH: u16 = 1;
+
+pub(crate) fn render_diff<W: Write, I: InputEventSource>(
+    diff_view_state: &mut DiffViewState,
+    writer: &mut W,
+    input_event_source: &mut I,
+    skin: &Skin,
+    should_exit: Arc<AtomicBool>,
+) -> anyhow::Result<()> {
+    #[cfg(not(test))]
+    enable_raw_mode()?;
+
+    execute!(writer, enter_terminal_command(), EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(writer);
+
+    #[cfg(not(test))]
+    let mut terminal = Terminal::new(backend)?;
+    #[cfg(test)]
+    let mut terminal = {
+        let area = Rect::new(0, 0, 120, 24);
+        Terminal::with_options(
+            backend,
+            ratatui::TerminalOptions {
+                viewport: ratatui::Viewport::Fixed(area),
+            },
+        )?
+    };
+
+    let result = render_loop(
+        &mut terminal,
+        diff_view_state,
+        input_event_source,
+        skin,
+        should_exit,
+    );
+
+    #[cfg(not(test))]
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        exit_terminal_command(),
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    result
+}
+
+fn render_loop<B: Backend, I: InputEventSource>(
+    terminal: &mut Terminal<B>,
+    diff_view_state: &mut DiffViewState,
+    input_event_source: &mut I,
+    skin: &Skin,
+    should_exit: Arc<AtomicBool>,
+) -> anyhow::Result<()> {
+    loop {
+        terminal.draw(|f| create_frame(f, diff_view_state, skin))?;
+
+        if should_exit.load(std::sync::atomic::Ordering::Relaxed) {
+            anyhow::bail!("Cancelled.");
+        }
+
+        if let Some(Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            modifiers,
+            ..
+        })) = input_event_source.poll_event(POLL_TIMEOUT)?
+        {
+            if code == KeyCode::Char('c') && modifiers == KeyModifiers::CONTROL {
+                anyhow::bail!("Cancelled.");
+            }
+
+            if handle_key_input(diff_view_state, code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Returns true if the loop should exit.
+pub(crate) fn handle_key_input(diff_view_state: &mut DiffViewState, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char(QUIT_KEY_1) | KeyCode::Esc => return true,
+        KeyCode::Up => diff_view_state.select_prev(),
+        KeyCode::Down => diff_view_state.select_next(),
+        KeyCode::PageUp => diff_view_state.select_prev_page(),
+        KeyCode::PageDown => diff_view_state.select_next_page(),
+        KeyCode::Home => diff_view_state.select_first(),
+        KeyCode::End => diff_view_state.select_last(),
+        KeyCode::Right | KeyCode::Enter => diff_view_state.expand_selected(),
+        KeyCode::Left => diff_view_state.collapse_selected(),
+        KeyCode::Char(TOGGLE_UNCHANGED_KEY) => diff_view_state.toggle_unchanged(),
+        _ => {}
+    }
+    false
+}
+
+fn create_frame<B: Backend>(f: &mut Frame<B>, diff_view_state: &mut DiffViewState, skin: &Skin) {
+    let vertical_rects = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(1),
+                Constraint::Min(2),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(f.size());
+
+    render_title_bar(f, diff_view_state, &vertical_rects[0], skin);
+    render_table(f, diff_view_state, &vertical_rects[1], skin);
+    render_status_bar(f, diff_view_state, &vertical_rects[2], skin);
+}
+
+fn render_title_bar<B: Backend>(
+    f: &mut Frame<B>,
+    diff_view_state: &DiffViewState,
+    area: &Rect,
+    skin: &Skin,
+) {
+    let title_style = Style::default()
+        .fg(skin.title_fg_color)
+        .bg(skin.title_bg_color);
+    let version_style = Style::default()
+        .fg(skin.version_fg_color)
+        .bg(skin.title_bg_color);
+    let key_style = Style::default()
+        .fg(skin.key_help_key_fg_color)
+        .bg(skin.title_bg_color);
+
+    let key_help = [
+        (format!("{QUIT_KEY_1}/{QUIT_KEY_2_SYMBOL}"), "quit"),
+        (
+            format!("{SELECT_PREV_KEY_SYMBOL}{SELECT_NEXT_KEY_SYMBOL}"),
+            "select",
+        ),
+        (
+            format!("{COLLAPSE_KEY_SYMBOL}{EXPAND_KEY_SYMBOL}"),
+            "collapse/expand",
+        ),
+        (
+            TOGGLE_UNCHANGED_KEY.to_string(),
+            if diff_view_state.options.show_unchanged {
+                "hide unchanged"
+            } else {
+                "show unchanged"
+            },
+        ),
+    ];
+    let mut spans = vec![
+        Span::styled("Space diff ", title_style),
+        Span::styled(format!("v{VERSION}"), version_style),
+    ];
+    for (key, description) in key_help {
+        spans.push(Span::styled("  ", title_style));
+        spans.push(Span::styled(key, key_style));
+        spans.push(Span::styled(format!(" {description}"), title_style));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)).style(title_style), *area);
+}
+
+fn render_table<B: Backend>(
+    f: &mut Frame<B>,
+    diff_view_state: &mut DiffViewState,
+    area: &Rect,
+    skin: &Skin,
+) {
+    let table_header_style = Style::default()
+        .bg(skin.table_header_bg_color)
+        .fg(skin.table_header_fg_color);
+    let selected_style = Style::default().add_modifier(Modifier::REVERSED);
+
+    // Subtract one for the column headers.
+    diff_view_state.visible_height = area.height.saturating_sub(1).max(1) as usize;
+    diff_view_state.scroll_to_selected();
+
+    let header = Row::new(
+        [
+            (String::default(), MARKER_COLUMN_WIDTH as usize),
+            (String::from("Delta"), DELTA_COLUMN_WIDTH),
+            (String::from("Change"), CHANGE_COLUMN_WIDTH),
+            (String::from("Old"), SIZE_COLUMN_WIDTH),
+            (String::from("New"), SIZE_COLUMN_WIDTH),
+        ]
+        .into_iter()
+        .map(|(header, width)| Cell::from(format!("{header:>width$}")))
+        .chain([Cell::from("Path")]),
+    )
+    .style(table_header_style)
+    .height(1);
+
+    let options = &diff_view_state.options;
+    let rows: Vec<Row> = diff_view_state
+        .rows
+        .iter()
+        .skip(diff_view_state.visible_offset)
+        .take(diff_view_state.visible_height)
+        .map(|row| {
+            let item = diff_view_state.item(&row.index_path);
+            let change_style = match item.delta().cmp(&0) {
+                Ordering::Greater => Style::default().fg(skin.diff_growth_fg_color),
+                Ordering::Less => Style::default().fg(skin.diff_shrinkage_fg_color),
+                Ordering::Equal => Style::default(),
+            };
+            let expand_indicator = if !diff_view_state.has_shown_children(&row.index_path) {
+                "  "
+            } else if diff_view_state.is_expanded(&row.index_path) {
+                "▾ "
+            } else {
+                "▸ "
+            };
+            Row::new([
+                Cell::from(item.status().marker().to_string()).style(change_style),
+                Cell::from(format!(
+                    "{:>DELTA_COLUMN_WIDTH$}",
+                    format_delta(item.delta(), options.size_mode, options.size_display_format)
+                ))
+                .style(change_style),
+                Cell::from(format!("{:>CHANGE_COLUMN_WIDTH$}", format_change(item)))
+                    .style(change_style),
+                Cell::from(format!(
+                    "{:>SIZE_COLUMN_WIDTH$}",
+                    format_size(
+                        item.old_size,
+                        options.size_mode,
+                        options.size_display_format
+                    )
+                )),
+                Cell::from(format!(
+                    "{:>SIZE_COLUMN_WIDTH$}",
+                    format_size(
+                        item.new_size,
+                        options.size_mode,
+                        options.size_display_format
+                    )
+                )),
+                Cell::from(format!(
+                    "{:indent$}{expand_indicator}{}",
+                    "",
+                    item.path_segment,
+                    indent = row.depth * 2
+                )),
+            ])
+        })
+        .collect();
+
+    let fixed_widths = [
+        MARKER_COLUMN_WIDTH,
+        DELTA_COLUMN_WIDTH as u16,
+        CHANGE_COLUMN_WIDTH as u16,
+        SIZE_COLUMN_WIDTH as u16,
+        SIZE_COLUMN_WIDTH as u16,
+    ];
+    // The path column gets the remaining width, after the column separators.
+    let path_width = area
+        .width
+        .saturating_sub(fixed_widths.iter().sum::<u16>() + fixed_widths.len() as u16);
+    let constraints: Vec<_> = fixed_widths
+        .into_iter()
+        .chain([path_width])
+        .map(Constraint::Length)
+        .collect();
+    let table = Table::new(rows)
+        .header(header)
+        .block(Block::default().borders(Borders::NONE))
+        .highlight_style(selected_style)
+        .highlight_symbol("")
+        .widths(&constraints);
+    let selected = (!diff_view_state.rows.is_empty())
+        .then(|| diff_view_state.selected_index - diff_view_state.visible_offset);
+    f.render_stateful_widget(
+        table,
+        *area,
+        &mut TableState::default().with_selected(selected),
+    );
+}
+
+fn render_status_bar<B: Backend>(
+    f: &mut Frame<B>,
+    diff_view_state: &DiffViewState,
+    area: &Rect,
+    skin: &Skin,
+) {
+    let title_style = Style::default()
+        .fg(skin.title_fg_color)
+        .bg(skin.title_bg_color);
+    let status = format!(
+        "{} | {}",
+        describe_total(&diff_view_state.items, &diff_view_state.options),
+        diff_view_state.title
+    );
+    f.render_widget(Paragraph::new(status).style(title_style), *area);
+}
@@ -0,0 +1,162 @@
+use super::{create_frame, handle_key_input, render_diff, TOGGLE_UNCHANGED_KEY};
+use crate::cli::{
+    diff_command::{
+        diff_render::DiffRenderOptions, diff_test_utils::make_test_diff,
+        diff_view_state::DiffViewState,
+    },
+    skin::Skin,
+    tui::QUIT_KEY_1,
+    tui_test_utils::TestInputEventSource,
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use rstest::rstest;
+use space_rs::{SizeDisplayFormat, SizeMode};
+use std::sync::{atomic::AtomicBool, Arc};
+
+fn make_diff_view_state() -> DiffViewState {
+    DiffViewState::new(
+        make_test_diff(),
+        DiffRenderOptions {
+            size_mode: SizeMode::Apparent,
+            size_display_format: SizeDisplayFormat::Metric,
+            show_unchanged: false,
+        },
+        String::from("Comparing old with new"),
+    )
+}
+
+fn render_lines(diff_view_state: &mut DiffViewState, height: u16) -> anyhow::Result<Vec<String>> {
+    let width = 100;
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|f| create_frame(f, diff_view_state, &Skin::default()))?;
+    let buffer = terminal.backend().buffer();
+    Ok((0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| buffer.get(x, y).symbol.as_str())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect())
+}
+
+#[rstest]
+#[case(KeyCode::Char(QUIT_KEY_1))]
+#[case(KeyCode::Esc)]
+fn handle_key_input_given_quit_key_returns_true(#[case] code: KeyCode) {
+    // Arrange
+    let mut diff_view_state = make_diff_view_state();
+
+    // Act
+    let should_exit = handle_key_input(&mut diff_view_state, code);
+
+    // Assert
+    assert!(should_exit);
+}
+
+#[rstest]
+fn handle_key_input_navigates_expands_and_toggles_unchanged() {
+    // Arrange
+    let mut diff_view_state = make_diff_view_state();
+
+    // Act
+    for code in [
+        KeyCode::End,
+        KeyCode::Right,
+        KeyCode::Down,
+        KeyCode::Char(TOGGLE_UNCHANGED_KEY),
+    ] {
+        assert!(!handle_key_input(&mut diff_view_state, code));
+    }
+
+    // Assert
+    assert_eq!(4, diff_view_state.selected_index);
+    assert!(diff_view_state.options.show_unchanged);
+    assert_eq!(8, diff_view_state.rows.len());
+}
+
+#[rstest]
+fn create_frame_renders_title_rows_and_total() -> anyhow::Result<()> {
+    // Arrange
+    let mut diff_view_state = make_diff_view_state();
+
+    // Act
+    let lines = render_lines(&mut diff_view_state, 8)?;
+
+    // Assert
+    assert!(lines[0].starts_with("Space diff v"), "{lines:?}");
+    assert!(lines[0].contains("u show unchanged"), "{lines:?}");
+    assert_eq!(
+        "        Delta    Change        Old        New Path",
+        lines[1]
+    );
+    assert_eq!(
+        "       +12 KB    +21.1%      57 KB      69 KB ▾ /data",
+        lines[2]
+    );
+    assert_eq!(
+        "+      +25 KB     added                 25 KB     new.bin",
+        lines[4]
+    );
+    assert_eq!(
+        "       +17 KB   +113.3%      15 KB      32 KB   ▸ logs",
+        lines[5]
+    );
+    assert_eq!(
+        "Total +12 KB (+21.1%), from 57 KB to 69 KB. 2 added, 1 removed and 4 changed items | Comparing old w",
+        lines[7]
+    );
+    Ok(())
+}
+
+#[rstest]
+fn render_diff_given_quit_key_returns_ok() -> anyhow::Result<()> {
+    // Arrange
+    let mut diff_view_state = make_diff_view_state();
+    let mut output = vec![];
+    let mut input_event_source = TestInputEventSource::new(vec![
+        Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+        Event::Key(KeyEvent::new(KeyCode::Char(QUIT_KEY_1), KeyModifiers::NONE)),
+    ]);
+
+    // Act
+    render_diff(
+        &mut diff_view_state,
+        &mut output,
+        &mut input_event_source,
+        &Skin::default(),
+        Arc::new(AtomicBool::new(false)),
+    )?;
+
+    // Assert
+    assert_eq!(1, diff_view_state.selected_index);
+    assert!(String::from_utf8_lossy(&output).contains("/data"));
+    Ok(())
+}
+
+#[rstest]
+fn render_diff_given_ctrl_c_returns_cancelled_error() {
+    // Arrange
+    let mut diff_view_state = make_diff_view_state();
+    let mut input_event_source = TestInputEventSource::new(vec![Event::Key(KeyEvent::new(
+        KeyCode::Char('c'),
+        KeyModifiers::CONTROL,
+    ))]);
+
+    // Act
+    let result = render_diff(
+        &mut diff_view_state,
+        &mut vec![],
+        &mut input_event_source,
+        &Skin::default(),
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    // Assert
+    assert_eq!(
+        "Cancelled.",
+        result.err().map(|e| e.to_string()).unwrap_or_default()
+    );
+}
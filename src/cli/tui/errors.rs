@@ -0,0 +1,72 @@
+use super::rendering::contract_area;
+use crate::cli::{skin::Skin, view_state::ViewState};
+use ratatui::{
+    layout::Constraint,
+    prelude::*,
+    style::Style,
+    widgets::{Block, Borders, Cell, Clear, Row, Table},
+    Frame,
+};
+
+const KIND_COLUMN_WIDTH: u16 = 20;
+
+pub(in crate::cli) fn render_scan_errors<B: Backend>(
+    f: &mut Frame<B>,
+    view_state: &ViewState,
+    skin: &Skin,
+) {
+    let error_count = view_state.scan_errors.len();
+    let block = Block::default()
+        .title(format!(
+            "Scan errors ({error_count}) - sizes that include these items are undercounted"
+        ))
+        .borders(Borders::ALL);
+    let mut area = f.size();
+    f.render_widget(Clear, area); // Clear out the background
+    f.render_widget(block, area);
+
+    contract_area(&mut area, 2, 2);
+
+    let header_style = Style::default()
+        .bg(skin.table_header_bg_color)
+        .fg(skin.table_header_fg_color);
+    let header = Row::new(["Path", "Kind", "Message"].into_iter().map(Cell::from))
+        .style(header_style)
+        .height(1);
+
+    // Leave room for the header and, if required, a line indicating how many errors are not shown.
+    let max_rows = (area.height as usize).saturating_sub(1);
+    let shown_count = if error_count > max_rows {
+        max_rows.saturating_sub(1)
+    } else {
+        error_count
+    };
+    let mut rows: Vec<Row> = view_state.scan_errors[..shown_count]
+        .iter()
+        .map(|error| {
+            Row::new(vec![
+                Cell::from(error.path.display().to_string()),
+                Cell::from(format!("{:?}", error.kind)),
+                Cell::from(error.message.clone()),
+            ])
+        })
+        .collect();
+    if shown_count < error_count {
+        rows.push(Row::new(vec![Cell::from(format!(
+            "... and {} more",
+            error_count - shown_count
+        ))]));
+    }
+
+    let path_column_width = area.width.saturating_sub(KIND_COLUMN_WIDTH) / 2;
+    let widths = [
+        Constraint::Length(path_column_width),
+        Constraint::Length(KIND_COLUMN_WIDTH),
+        Constraint::Length(
+            area.width
+                .saturating_sub(path_column_width + KIND_COLUMN_WIDTH),
+        ),
+    ];
+    let table = Table::new(rows).header(header).widths(&widths);
+    f.render_widget(table, area);
+}
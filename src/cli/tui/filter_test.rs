@@ -29,8 +29,8 @@ fn render_with_help_key_input_outputs_help() -> anyhow::Result<()> {
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -85,8 +85,8 @@ fn render_with_view_size_threshold_input_shows_percentage_on_top_line(
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -127,8 +127,8 @@ fn render_given_collapse_input_for_expanded_directory_item_collapses_it() -> any
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -163,8 +163,8 @@ fn render_given_expand_input_for_collapsed_directory_item_expands_it() -> anyhow
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -201,8 +201,8 @@ fn render_given_collapse_children_input_for_directory_item_collapses_all_childre
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -242,8 +242,8 @@ fn render_given_expand_children_input_for_collapsed_directory_item_expands_all_c
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -281,8 +281,8 @@ fn render_given_expand_children_input_for_expanded_directory_item_expands_all_ch
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
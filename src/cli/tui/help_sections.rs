@@ -1,14 +1,15 @@
 use crate::cli::tui::rendering::remove_area_top;
 use crate::cli::tui::{
-    COLLAPSE_KEY_SYMBOL, COLLAPSE_SELECTED_CHILDREN_KEY, CONFIRM_DELETE_KEY, DELETE_KEY,
-    EXPAND_KEY_SYMBOL, EXPAND_SELECTED_CHILDREN_KEY, FILTER_KEY, QUIT_KEY_1, QUIT_KEY_2_SYMBOL,
-    RESCAN_KEY, SELECT_FIRST_KEY_SYMBOL, SELECT_LAST_KEY_SYMBOL, SELECT_NEXT_KEY_SYMBOL,
-    SELECT_NEXT_PAGE_KEY_SYMBOL, SELECT_PREV_KEY_SYMBOL, SELECT_PREV_PAGE_KEY_SYMBOL,
-    VIEW_SIZE_THRESHOLD_0_PERCENT_KEY, VIEW_SIZE_THRESHOLD_10_PERCENT_KEY,
-    VIEW_SIZE_THRESHOLD_20_PERCENT_KEY, VIEW_SIZE_THRESHOLD_30_PERCENT_KEY,
-    VIEW_SIZE_THRESHOLD_40_PERCENT_KEY, VIEW_SIZE_THRESHOLD_50_PERCENT_KEY,
-    VIEW_SIZE_THRESHOLD_60_PERCENT_KEY, VIEW_SIZE_THRESHOLD_70_PERCENT_KEY,
-    VIEW_SIZE_THRESHOLD_80_PERCENT_KEY, VIEW_SIZE_THRESHOLD_90_PERCENT_KEY,
+    COLLAPSE_KEY_SYMBOL, COLLAPSE_SELECTED_CHILDREN_KEY, CONFIRM_DELETE_KEY, COUNTS_KEY,
+    DELETE_KEY, DETAILS_KEY, EXPAND_KEY_SYMBOL, EXPAND_SELECTED_CHILDREN_KEY, FILTER_KEY,
+    QUIT_KEY_1, QUIT_KEY_2_SYMBOL, RESCAN_KEY, SCAN_ERRORS_KEY, SELECT_FIRST_KEY_SYMBOL,
+    SELECT_LAST_KEY_SYMBOL, SELECT_NEXT_KEY_SYMBOL, SELECT_NEXT_PAGE_KEY_SYMBOL,
+    SELECT_PREV_KEY_SYMBOL, SELECT_PREV_PAGE_KEY_SYMBOL, VIEW_SIZE_THRESHOLD_0_PERCENT_KEY,
+    VIEW_SIZE_THRESHOLD_10_PERCENT_KEY, VIEW_SIZE_THRESHOLD_20_PERCENT_KEY,
+    VIEW_SIZE_THRESHOLD_30_PERCENT_KEY, VIEW_SIZE_THRESHOLD_40_PERCENT_KEY,
+    VIEW_SIZE_THRESHOLD_50_PERCENT_KEY, VIEW_SIZE_THRESHOLD_60_PERCENT_KEY,
+    VIEW_SIZE_THRESHOLD_70_PERCENT_KEY, VIEW_SIZE_THRESHOLD_80_PERCENT_KEY,
+    VIEW_SIZE_THRESHOLD_90_PERCENT_KEY,
 };
 use ratatui::{
     layout::Constraint,
@@ -53,6 +54,21 @@ pub(super) fn render_general_section<B: Backend>(
                 Cell::from(format!("{:^key_column_size$}", "F5")).style(key_style),
                 Cell::from("Rescan selected directory"),
             ]),
+            Row::new(vec![
+                Cell::from(format!("{SCAN_ERRORS_KEY:^key_column_size$}")).style(key_style),
+                Cell::from(""),
+                Cell::from("Show scan errors, i.e. items that could not be read"),
+            ]),
+            Row::new(vec![
+                Cell::from(format!("{DETAILS_KEY:^key_column_size$}")).style(key_style),
+                Cell::from(""),
+                Cell::from("Show details of selected item, e.g. timestamps and owner"),
+            ]),
+            Row::new(vec![
+                Cell::from(format!("{COUNTS_KEY:^key_column_size$}")).style(key_style),
+                Cell::from(""),
+                Cell::from("Toggle file, directory, symlink and other count columns"),
+            ]),
         ],
         section_header_style,
         column_constraints,
@@ -12,13 +12,13 @@ mod key_handlers_filter_test;
 
 use super::{
     ACCEPT_LICENSE_TERMS_KEY, COLLAPSE_SELECTED_CHILDREN_KEY, COLLAPSE_SELECTED_CHILDREN_KEY_ALT,
-    CONFIRM_DELETE_KEY, DELETE_KEY, EXPAND_SELECTED_CHILDREN_KEY, EXPAND_SELECTED_CHILDREN_KEY_ALT,
-    FILTER_KEY, HELP_KEY, QUIT_KEY_1, RESCAN_KEY, VIEW_SIZE_THRESHOLD_0_PERCENT_KEY,
-    VIEW_SIZE_THRESHOLD_10_PERCENT_KEY, VIEW_SIZE_THRESHOLD_20_PERCENT_KEY,
-    VIEW_SIZE_THRESHOLD_30_PERCENT_KEY, VIEW_SIZE_THRESHOLD_40_PERCENT_KEY,
-    VIEW_SIZE_THRESHOLD_50_PERCENT_KEY, VIEW_SIZE_THRESHOLD_60_PERCENT_KEY,
-    VIEW_SIZE_THRESHOLD_70_PERCENT_KEY, VIEW_SIZE_THRESHOLD_80_PERCENT_KEY,
-    VIEW_SIZE_THRESHOLD_90_PERCENT_KEY,
+    CONFIRM_DELETE_KEY, COUNTS_KEY, DELETE_KEY, DETAILS_KEY, EXPAND_SELECTED_CHILDREN_KEY,
+    EXPAND_SELECTED_CHILDREN_KEY_ALT, FILTER_KEY, HELP_KEY, QUIT_KEY_1, RESCAN_KEY,
+    SCAN_ERRORS_KEY, VIEW_SIZE_THRESHOLD_0_PERCENT_KEY, VIEW_SIZE_THRESHOLD_10_PERCENT_KEY,
+    VIEW_SIZE_THRESHOLD_20_PERCENT_KEY, VIEW_SIZE_THRESHOLD_30_PERCENT_KEY,
+    VIEW_SIZE_THRESHOLD_40_PERCENT_KEY, VIEW_SIZE_THRESHOLD_50_PERCENT_KEY,
+    VIEW_SIZE_THRESHOLD_60_PERCENT_KEY, VIEW_SIZE_THRESHOLD_70_PERCENT_KEY,
+    VIEW_SIZE_THRESHOLD_80_PERCENT_KEY, VIEW_SIZE_THRESHOLD_90_PERCENT_KEY,
 };
 use crate::cli::view_state::{DeletionState, ViewState};
 use crossterm::event::KeyCode;
@@ -30,6 +30,10 @@ pub(crate) fn handle_key_input(view_state: &mut ViewState, code: KeyCode) -> boo
         handle_filter_input_key(view_state, code);
     } else if view_state.show_help {
         view_state.show_help = false;
+    } else if view_state.show_scan_errors {
+        view_state.show_scan_errors = false;
+    } else if view_state.show_details {
+        view_state.show_details = false;
     } else if view_state.show_delete_dialog {
         handle_delete_dialog_key(view_state, code);
     } else {
@@ -75,15 +79,39 @@ pub(crate) fn handle_normal_key(view_state: &mut ViewState, code: KeyCode) -> bo
     match code {
         KeyCode::Char(HELP_KEY) => view_state.show_help = true,
         KeyCode::Char(DELETE_KEY) => {
-            if view_state.is_selected_items_parent_scanning() {
+            if view_state.is_read_only {
+                view_state.status_message =
+                    Some("Cannot delete items that were loaded from a file".to_string());
+            } else if view_state.is_selected_items_parent_scanning() {
                 view_state.status_message =
                     Some("Cannot delete while parent is scanning".to_string());
             } else {
                 view_state.show_delete_dialog = true;
             }
         }
+        KeyCode::Char(SCAN_ERRORS_KEY) => {
+            if view_state.scan_errors.is_empty() {
+                view_state.status_message = Some("No scan errors".to_string());
+            } else {
+                view_state.show_scan_errors = true;
+            }
+        }
+        KeyCode::Char(DETAILS_KEY) => {
+            if view_state.get_selected_item().is_some() {
+                view_state.show_details = true;
+            }
+        }
+        KeyCode::Char(COUNTS_KEY) => {
+            view_state.show_counts = !view_state.show_counts;
+            view_state.visible_rows_dirty = true;
+        }
         KeyCode::Char(RESCAN_KEY) | KeyCode::F(5) => {
-            view_state.prepare_rescan();
+            if view_state.is_read_only {
+                view_state.status_message =
+                    Some("Cannot rescan items that were loaded from a file".to_string());
+            } else {
+                view_state.prepare_rescan();
+            }
         }
         KeyCode::Char(QUIT_KEY_1) | KeyCode::Esc => return true,
         KeyCode::Left => view_state.collapse_selected_item(),
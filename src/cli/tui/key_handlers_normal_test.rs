@@ -4,13 +4,14 @@ use crate::cli::tui::*;
 use crate::cli::view_state::ViewState;
 use crossterm::event::KeyCode;
 use rstest::rstest;
-use space_rs::Size;
-use std::{cell::RefCell, rc::Rc};
+use space_rs::{DescendantCounts, ScanError, Size};
+use std::{cell::RefCell, io, path::Path, rc::Rc};
 
 /// Creates a minimal RowItem wrapped in Rc<RefCell>.
 fn make_row_item(name: &str) -> Rc<RefCell<RowItem>> {
     Rc::new(RefCell::new(RowItem {
         size: Size::new(0),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: false,
         tree_prefix: String::default(),
@@ -18,9 +19,12 @@ fn make_row_item(name: &str) -> Rc<RefCell<RowItem>> {
         incl_fraction: 0.0,
         peer_fraction: 0.0,
         path_segment: name.to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -74,6 +78,78 @@ fn handle_normal_key_help_sets_show_help_flag() {
     assert!(view_state.show_help);
 }
 
+#[test]
+fn handle_normal_key_scan_errors_sets_show_scan_errors_flag() {
+    let mut view_state = ViewState {
+        scan_errors: vec![ScanError::new(
+            Path::new("/a"),
+            &io::Error::from(io::ErrorKind::PermissionDenied),
+        )],
+        ..Default::default()
+    };
+    handle_normal_key(&mut view_state, KeyCode::Char(SCAN_ERRORS_KEY));
+    assert!(view_state.show_scan_errors);
+}
+
+#[test]
+fn handle_normal_key_scan_errors_without_errors_sets_status_message() {
+    let mut view_state = ViewState::default();
+    handle_normal_key(&mut view_state, KeyCode::Char(SCAN_ERRORS_KEY));
+    assert!(!view_state.show_scan_errors);
+    assert_eq!(
+        Some("No scan errors".to_string()),
+        view_state.status_message
+    );
+}
+
+#[rstest]
+#[case(DELETE_KEY, "Cannot delete items that were loaded from a file")]
+#[case(RESCAN_KEY, "Cannot rescan items that were loaded from a file")]
+fn handle_normal_key_given_read_only_items_sets_status_message(
+    #[case] key: char,
+    #[case] expected_message: &str,
+) {
+    let mut view_state = make_scanning_view_state_with_selected_child(false);
+    view_state.is_scanning = false;
+    view_state.is_read_only = true;
+    handle_normal_key(&mut view_state, KeyCode::Char(key));
+    assert!(!view_state.show_delete_dialog);
+    assert!(view_state.rescan_request.is_none());
+    assert_eq!(
+        Some(expected_message.to_string()),
+        view_state.status_message
+    );
+}
+
+#[test]
+fn handle_normal_key_details_with_selected_item_sets_show_details_flag() {
+    let mut view_state = make_scanning_view_state_with_selected_child(false);
+    handle_normal_key(&mut view_state, KeyCode::Char(DETAILS_KEY));
+    assert!(view_state.show_details);
+}
+
+#[test]
+fn handle_normal_key_details_without_items_does_not_set_show_details_flag() {
+    let mut view_state = ViewState::default();
+    handle_normal_key(&mut view_state, KeyCode::Char(DETAILS_KEY));
+    assert!(!view_state.show_details);
+}
+
+#[test]
+fn handle_normal_key_counts_toggles_show_counts_flag() {
+    let mut view_state = ViewState {
+        visible_rows_dirty: false,
+        ..Default::default()
+    };
+
+    handle_normal_key(&mut view_state, KeyCode::Char(COUNTS_KEY));
+    assert!(view_state.show_counts);
+    assert!(view_state.visible_rows_dirty);
+
+    handle_normal_key(&mut view_state, KeyCode::Char(COUNTS_KEY));
+    assert!(!view_state.show_counts);
+}
+
 #[test]
 fn handle_normal_key_delete_sets_show_delete_dialog_flag() {
     let mut view_state = ViewState::default();
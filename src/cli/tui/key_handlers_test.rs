@@ -18,6 +18,32 @@ fn handle_key_input_when_show_help_dismisses_help_and_returns_false() {
     assert!(!should_exit);
 }
 
+#[test]
+fn handle_key_input_when_show_scan_errors_dismisses_errors_and_returns_false() {
+    let mut view_state = ViewState {
+        show_scan_errors: true,
+        ..Default::default()
+    };
+
+    let should_exit = handle_key_input(&mut view_state, KeyCode::Char(QUIT_KEY_1));
+
+    assert!(!view_state.show_scan_errors);
+    assert!(!should_exit);
+}
+
+#[test]
+fn handle_key_input_when_show_details_dismisses_details_and_returns_false() {
+    let mut view_state = ViewState {
+        show_details: true,
+        ..Default::default()
+    };
+
+    let should_exit = handle_key_input(&mut view_state, KeyCode::Char(QUIT_KEY_1));
+
+    assert!(!view_state.show_details);
+    assert!(!should_exit);
+}
+
 #[test]
 fn handle_key_input_when_show_delete_dialog_without_license_terms_and_other_key_closes_dialog() {
     let mut view_state = ViewState {
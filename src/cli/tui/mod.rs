@@ -1,10 +1,12 @@
 mod dialogs;
+pub(crate) mod diff;
+mod errors;
 mod help;
 pub(crate) mod key_handlers;
 pub(crate) mod rendering;
 mod scan_drain;
 
-use super::{input_event_source::InputEventSource, scan_worker, skin::Skin, view_state::ViewState};
+use super::{input_event_source::InputEventSource, skin::Skin, view_state::ViewState};
 #[cfg(not(test))]
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 #[cfg(not(test))]
@@ -17,6 +19,7 @@ use crossterm::{
     execute,
 };
 use ratatui::{prelude::*, Terminal};
+use space_rs::{ProgressiveScan, ScanEventReceiver, ScanEventSender};
 use std::{
     io::Write,
     sync::{atomic::AtomicBool, Arc},
@@ -76,6 +79,9 @@ pub(crate) const CONFIRM_DELETE_KEY: char = 'y';
 pub(crate) const RESCAN_KEY: char = 'r';
 pub(crate) const ACCEPT_LICENSE_TERMS_KEY: char = 'a';
 pub(crate) const FILTER_KEY: char = '/';
+pub(crate) const SCAN_ERRORS_KEY: char = 'e';
+pub(crate) const DETAILS_KEY: char = 'i';
+pub(crate) const COUNTS_KEY: char = 'c';
 
 pub(crate) const QUIT_KEY_2_SYMBOL: &str = "Esc";
 pub(crate) const SELECT_PREV_KEY_SYMBOL: char = '↑';
@@ -95,8 +101,8 @@ pub(crate) fn render<W: Write, I: InputEventSource>(
     input_event_source: &mut I,
     skin: &Skin,
     should_exit: Arc<AtomicBool>,
-    scan_sender: scan_worker::ScanSender,
-    scan_receiver: scan_worker::ScanReceiver,
+    scan_sender: ScanEventSender,
+    scan_receiver: ScanEventReceiver,
 ) -> anyhow::Result<()> {
     #[cfg(not(test))]
     enable_raw_mode()?;
@@ -172,8 +178,8 @@ fn render_loop<B: Backend, I: InputEventSource>(
     input_event_source: &mut I,
     skin: &Skin,
     should_exit: Arc<AtomicBool>,
-    scan_sender: scan_worker::ScanSender,
-    scan_receiver: scan_worker::ScanReceiver,
+    scan_sender: ScanEventSender,
+    scan_receiver: ScanEventReceiver,
 ) -> anyhow::Result<()> {
     // Track how many scan/rescan threads are outstanding.  Each sends
     // Complete when done; final cleanup runs when the count reaches 0.
@@ -215,9 +221,10 @@ fn render_loop<B: Backend, I: InputEventSource>(
         // Process any rescan requests from key handlers or deletion recovery.
         if let Some((path, ancestor_segments)) = view_state.rescan_request.take() {
             active_scan_count += 1;
-            scan_worker::spawn_rescan(
+            ProgressiveScan::start_rescan(
                 ancestor_segments,
                 path,
+                view_state.scanner.clone(),
                 should_exit.clone(),
                 scan_sender.clone(),
             );
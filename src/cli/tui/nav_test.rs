@@ -55,8 +55,8 @@ fn render_with_navigation_input_selects_correct_item(
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -129,8 +129,8 @@ fn render_with_page_up_or_down_navigation_input_selects_correct_item(
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -180,8 +180,8 @@ fn render_with_first_navigation_input_selects_first_item(
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -231,8 +231,8 @@ fn render_with_last_navigation_input_selects_last_item(
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
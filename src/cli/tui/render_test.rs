@@ -30,8 +30,8 @@ fn render_outputs_crate_version_number() -> anyhow::Result<()> {
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -61,8 +61,8 @@ fn render_outputs_full_path_as_first_row_with_100_percent_size() -> anyhow::Resu
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -123,8 +123,8 @@ fn render_with_delete_input_without_license_terms_accepted_shows_license_dialog(
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -187,8 +187,8 @@ fn render_with_cancelled_delete_does_not_delete_item(
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -258,8 +258,8 @@ fn render_with_confirmed_delete_deletes_selected_item(
         &mut input_event_source,
         &Skin::default(),
         should_exit,
-        crossfire::mpsc::unbounded_blocking().0,
-        crossfire::mpsc::unbounded_blocking().1,
+        ProgressiveScan::channel().0,
+        ProgressiveScan::channel().1,
     )?;
 
     // Assert
@@ -34,6 +34,10 @@ pub(in crate::cli) fn create_frame<B: Backend>(
 
     if view_state.show_help {
         super::help::render_help(f, skin);
+    } else if view_state.show_scan_errors {
+        super::errors::render_scan_errors(f, view_state, skin);
+    } else if view_state.show_details {
+        super::dialogs::render_details_dialog(f, view_state, skin);
     } else if view_state.show_delete_dialog {
         if view_state.accepted_license_terms {
             super::dialogs::render_delete_dialog(f, view_state, skin);
@@ -2,8 +2,8 @@ use crate::cli::{
     row_item::RowItem,
     skin::Skin,
     view_state::{
-        ViewState, APPARENT_SIZE_COLUMN_WIDTH, EXPAND_INDICATOR_COLUMN_WIDTH,
-        INCL_PERCENTAGE_COLUMN_WIDTH,
+        ViewState, APPARENT_SIZE_COLUMN_WIDTH, COUNT_COLUMN_HEADERS, COUNT_COLUMN_WIDTH,
+        EXPAND_INDICATOR_COLUMN_WIDTH, INCL_PERCENTAGE_COLUMN_WIDTH,
     },
 };
 use ratatui::{
@@ -16,6 +16,7 @@ use ratatui::{
     },
     Frame,
 };
+use space_rs::SizeMode;
 use std::{cell::RefCell, rc::Rc};
 
 pub(super) fn render_table<B: Backend>(
@@ -31,9 +32,22 @@ pub(super) fn render_table<B: Backend>(
 
     let table_selected_index = view_state.table_selected_index;
 
-    let header_cells = ["Size", "", "Path", "", "Incl"]
-        .iter()
-        .map(|h| Cell::from(*h));
+    let size_header = match view_state.size_mode {
+        SizeMode::Apparent => "Size",
+        SizeMode::Allocated => "Alloc",
+        SizeMode::Count => "Count",
+    };
+    let mut header_cells: Vec<Cell> = [size_header, "", "Path", "", "Incl"]
+        .into_iter()
+        .map(Cell::from)
+        .collect();
+    if view_state.show_counts {
+        header_cells.extend(
+            COUNT_COLUMN_HEADERS
+                .iter()
+                .map(|header| Cell::from(format!("{header:>0$}", COUNT_COLUMN_WIDTH as usize))),
+        );
+    }
     let header = Row::new(header_cells)
         .style(table_header_style)
         .height(1)
@@ -46,10 +60,11 @@ pub(super) fn render_table<B: Backend>(
     // so the path column fits its content and the size bar fills remaining space.
     let max_path_width = measure_max_path_width(&view_state.visible_row_items);
 
+    let constraints = table_column_constraints(area.width, max_path_width, view_state.show_counts);
+
     // Only then update the visible rows, as we need the available width here.
     let rows = view_state.update_visible_rows();
 
-    let constraints = table_column_constraints(area.width, max_path_width);
     let table = Table::new(rows)
         .header(header)
         .block(Block::default().borders(Borders::NONE))
@@ -100,10 +115,21 @@ pub(super) fn render_vertical_scrollbar<B: Backend>(
     );
 }
 
-pub(crate) fn table_column_constraints(width: u16, path_width: u16) -> [Constraint; 5] {
+/// Returns the widths of the table columns, including the four count columns at the end if `show_counts` is set.
+pub(crate) fn table_column_constraints(
+    width: u16,
+    path_width: u16,
+    show_counts: bool,
+) -> Vec<Constraint> {
+    let count_column_count = if show_counts {
+        COUNT_COLUMN_HEADERS.len() as u16
+    } else {
+        0
+    };
     let fixed = APPARENT_SIZE_COLUMN_WIDTH
         + EXPAND_INDICATOR_COLUMN_WIDTH
         + INCL_PERCENTAGE_COLUMN_WIDTH
+        + count_column_count * (COUNT_COLUMN_WIDTH + 1)
         + 4; // column separators
     let available = (width as i32 - fixed as i32).max(0) as u16;
     // Size bar gets leftover space after path, capped at 50% of total width.
@@ -112,13 +138,15 @@ pub(crate) fn table_column_constraints(width: u16, path_width: u16) -> [Constrai
     let bar_col = available.saturating_sub(path_width).min(max_bar);
     let path_col = available.saturating_sub(bar_col);
 
-    [
+    let mut constraints = vec![
         Constraint::Length(APPARENT_SIZE_COLUMN_WIDTH),
         Constraint::Length(EXPAND_INDICATOR_COLUMN_WIDTH),
         Constraint::Length(path_col),
         Constraint::Length(bar_col),
         Constraint::Length(INCL_PERCENTAGE_COLUMN_WIDTH),
-    ]
+    ];
+    constraints.extend((0..count_column_count).map(|_| Constraint::Length(COUNT_COLUMN_WIDTH)));
+    constraints
 }
 
 /// Returns the maximum path cell width across visible rows.  Uses +2 for the
@@ -133,7 +161,12 @@ fn measure_max_path_width(visible_row_items: &[Rc<RefCell<RowItem>>]) -> u16 {
             } else {
                 0
             };
-            r.tree_prefix.chars().count() + 2 + r.path_segment.len() + suffix_len
+            r.tree_prefix.chars().count()
+                + 2
+                + r.path_segment.len()
+                + r.get_link_target_suffix().chars().count()
+                + suffix_len
+                + r.get_ignored_share_suffix().len()
         })
         .max()
         .unwrap_or(0) as u16
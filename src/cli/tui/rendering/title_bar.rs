@@ -4,8 +4,8 @@ mod title_bar_test;
 
 use super::super::{
     COLLAPSE_CHILDREN_KEY_SYMBOL, COLLAPSE_KEY_SYMBOL, DELETE_KEY, EXPAND_CHILDREN_KEY_SYMBOL,
-    EXPAND_KEY_SYMBOL, FILTER_KEY, HELP_KEY, QUIT_KEY_1, QUIT_KEY_2_SYMBOL, SELECT_NEXT_KEY_SYMBOL,
-    SELECT_PREV_KEY_SYMBOL, VERSION,
+    EXPAND_KEY_SYMBOL, FILTER_KEY, HELP_KEY, QUIT_KEY_1, QUIT_KEY_2_SYMBOL, SCAN_ERRORS_KEY,
+    SELECT_NEXT_KEY_SYMBOL, SELECT_PREV_KEY_SYMBOL, VERSION,
 };
 use crate::cli::{skin::Skin, view_state::ViewState};
 use ratatui::{
@@ -83,9 +83,9 @@ fn build_scanning_display(data: &ViewState, skin: &Skin) -> (String, Style) {
     let text = if let Some(ref msg) = data.status_message {
         format!(" {msg}")
     } else if data.is_scanning {
-        " Scanning...".to_string()
+        format!(" Scanning...{}", build_scan_errors_display(data))
     } else {
-        String::new()
+        build_scan_errors_display(data)
     };
     let style = if data.status_message.is_some() {
         Style::default()
@@ -99,6 +99,15 @@ fn build_scanning_display(data: &ViewState, skin: &Skin) -> (String, Style) {
     (text, style)
 }
 
+/// Returns the number of scan errors so far, with the key that shows them, or an empty string if there are none.
+fn build_scan_errors_display(data: &ViewState) -> String {
+    match data.scan_errors.len() {
+        0 => String::new(),
+        1 => format!(" 1 error [{SCAN_ERRORS_KEY}]"),
+        count => format!(" {count} errors [{SCAN_ERRORS_KEY}]"),
+    }
+}
+
 fn build_filter_style(data: &ViewState, skin: &Skin) -> Style {
     if data.is_filter_input_active {
         Style::default()
@@ -1,5 +1,7 @@
-use super::build_filter_display_text;
+use super::{build_filter_display_text, build_scan_errors_display};
 use crate::cli::view_state::ViewState;
+use space_rs::ScanError;
+use std::{io, path::Path};
 
 // ─── Tests for build_filter_display_text ─────────────────────────────────────
 
@@ -54,3 +56,24 @@ fn build_filter_display_text_input_active_takes_precedence_over_filter_display()
     };
     assert_eq!("/new_", build_filter_display_text(&view_state));
 }
+
+// ─── Tests for build_scan_errors_display ─────────────────────────────────────
+
+#[test]
+fn build_scan_errors_display_when_no_errors_returns_empty_string() {
+    let view_state = ViewState::default();
+    assert_eq!("", build_scan_errors_display(&view_state));
+}
+
+#[test]
+fn build_scan_errors_display_when_errors_shows_count_and_key() {
+    let error = ScanError::new(
+        Path::new("/a"),
+        &io::Error::from(io::ErrorKind::PermissionDenied),
+    );
+    let view_state = ViewState {
+        scan_errors: vec![error.clone(), error],
+        ..Default::default()
+    };
+    assert_eq!(" 2 errors [e]", build_scan_errors_display(&view_state));
+}
@@ -1,16 +1,15 @@
-use super::scan_worker::{self, ScanMessage};
 use crate::cli::row_item::RowItem;
 use crate::cli::view_state::{
     scan_helpers::{build_child_prefix_base, find_child_by_name, resort_child_in_parent},
     ViewState,
 };
-use crossfire::TryRecvError;
-use std::{cell::RefCell, rc::Rc, time::Instant};
+use space_rs::{ScanEvent, ScanEventReceiver};
+use std::{cell::RefCell, rc::Rc, sync::mpsc::TryRecvError, time::Instant};
 
 /// Drains pending scan messages from the receiver into the view state.
 /// Returns `true` when all scans are complete (`active_scan_count` reaches 0).
 pub(super) fn drain_scan_channel(
-    receiver: &scan_worker::ScanReceiver,
+    receiver: &ScanEventReceiver,
     view_state: &mut ViewState,
     deadline: Instant,
     active_scan_count: &mut usize,
@@ -30,15 +29,15 @@ pub(super) fn drain_scan_channel(
             break;
         }
         match receiver.try_recv() {
-            Ok(ScanMessage::Item(dir_item)) => {
+            Ok(ScanEvent::Item(dir_item)) => {
                 view_state.add_scanned_item(dir_item);
                 items_added = true;
             }
-            Ok(ScanMessage::ChildItem(child_item)) => {
+            Ok(ScanEvent::ChildItem(child_item)) => {
                 view_state.add_scanned_child(child_item);
                 items_added = true;
             }
-            Ok(ScanMessage::DescendantBatch {
+            Ok(ScanEvent::DescendantBatch {
                 ancestor_path,
                 children,
             }) => {
@@ -51,13 +50,16 @@ pub(super) fn drain_scan_channel(
                 );
                 items_added = true;
             }
-            Ok(ScanMessage::AccessDenied(ancestor_path)) => {
+            Ok(ScanEvent::AccessDenied(ancestor_path)) => {
                 view_state.mark_access_denied(&ancestor_path);
             }
-            Ok(ScanMessage::ChildScanComplete(name)) => {
+            Ok(ScanEvent::Errors(errors)) => {
+                view_state.add_scan_errors(errors);
+            }
+            Ok(ScanEvent::ChildScanComplete(name)) => {
                 view_state.mark_child_scan_complete(&name);
             }
-            Ok(ScanMessage::Complete) => {
+            Ok(ScanEvent::Complete) => {
                 *active_scan_count = active_scan_count.saturating_sub(1);
                 if *active_scan_count == 0 {
                     finalize_scan(view_state);
@@ -1,16 +1,16 @@
 use super::scan_drain::drain_scan_channel;
-use crate::cli::scan_worker::ScanMessage;
-use crate::cli::scan_worker::ScanReceiver;
 use crate::cli::view_state::ViewState;
-use crossfire::mpsc as cf_mpsc;
-use space_rs::{DirectoryItem, DirectoryItemType, Size};
+use space_rs::{
+    DescendantCounts, DirectoryItem, DirectoryItemType, ProgressiveScan, ScanEvent,
+    ScanEventReceiver, Size,
+};
 use std::time::{Duration, Instant};
 
 fn test_deadline() -> Instant {
     Instant::now() + Duration::from_secs(1)
 }
 
-fn test_drain(receiver: &ScanReceiver, view_state: &mut ViewState) -> bool {
+fn test_drain(receiver: &ScanEventReceiver, view_state: &mut ViewState) -> bool {
     let mut count = 1usize;
     drain_scan_channel(receiver, view_state, test_deadline(), &mut count)
 }
@@ -20,8 +20,15 @@ fn make_file_dir_item(path_segment: &str, size: u64) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::File,
         size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -38,20 +45,14 @@ fn drain_scan_channel_ignores_items_queued_after_complete() {
         is_scanning: true,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
     sender
-        .send(ScanMessage::Item(make_file_dir_item(
-            "before_complete",
-            1000,
-        )))
+        .send(ScanEvent::Item(make_file_dir_item("before_complete", 1000)))
         .unwrap();
-    sender.send(ScanMessage::Complete).unwrap();
+    sender.send(ScanEvent::Complete).unwrap();
     // This message arrives in the buffer AFTER Complete and must be ignored.
     sender
-        .send(ScanMessage::Item(make_file_dir_item(
-            "after_complete",
-            2000,
-        )))
+        .send(ScanEvent::Item(make_file_dir_item("after_complete", 2000)))
         .unwrap();
 
     let complete = test_drain(&receiver, &mut view_state);
@@ -76,8 +77,15 @@ fn make_dir_item_for_scan(path_segment: &str, size: u64) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: space_rs::DirectoryItemType::Directory,
         size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -103,14 +111,11 @@ fn drain_scan_channel_preserves_selection_when_child_inserted_after_selected_ite
     // Tree before: root(0), big_child(1).  Select big_child.
     let mut view_state = make_scanning_view_state(&[("big_child", 100)]);
     view_state.table_selected_index = 1;
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
 
     // Insert small_child(10) -> goes AFTER big_child in sorted order.
     sender
-        .send(ScanMessage::ChildItem(make_file_dir_item(
-            "small_child",
-            10,
-        )))
+        .send(ScanEvent::ChildItem(make_file_dir_item("small_child", 10)))
         .unwrap();
 
     test_drain(&receiver, &mut view_state);
@@ -125,12 +130,12 @@ fn drain_scan_channel_preserves_selection_when_child_inserted_before_selected_it
     // Tree before: root(0), small_child(1).  Select small_child.
     let mut view_state = make_scanning_view_state(&[("small_child", 10)]);
     view_state.table_selected_index = 1;
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
 
     // Insert big_child(100) -> goes BEFORE small_child in sorted order.
     // Without selection tracking, table_selected_index=1 would land on big_child.
     sender
-        .send(ScanMessage::ChildItem(make_file_dir_item("big_child", 100)))
+        .send(ScanEvent::ChildItem(make_file_dir_item("big_child", 100)))
         .unwrap();
 
     test_drain(&receiver, &mut view_state);
@@ -145,13 +150,13 @@ fn drain_scan_channel_preserves_root_selection_when_children_added() {
     // Root selected at index 0.
     let mut view_state = make_scanning_view_state(&[]);
     view_state.table_selected_index = 0;
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
 
     sender
-        .send(ScanMessage::ChildItem(make_file_dir_item("child_a", 200)))
+        .send(ScanEvent::ChildItem(make_file_dir_item("child_a", 200)))
         .unwrap();
     sender
-        .send(ScanMessage::ChildItem(make_file_dir_item("child_b", 50)))
+        .send(ScanEvent::ChildItem(make_file_dir_item("child_b", 50)))
         .unwrap();
 
     test_drain(&receiver, &mut view_state);
@@ -170,17 +175,14 @@ fn drain_scan_channel_child_scan_complete_clears_is_scanning() {
         table_width: 80,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
 
     // Add root and a directory child - is_scanning=true is set by add_scanned_child.
     sender
-        .send(ScanMessage::Item(make_dir_item_for_scan("/root", 0)))
+        .send(ScanEvent::Item(make_dir_item_for_scan("/root", 0)))
         .unwrap();
     sender
-        .send(ScanMessage::ChildItem(make_dir_item_for_scan(
-            "dir_child",
-            0,
-        )))
+        .send(ScanEvent::ChildItem(make_dir_item_for_scan("dir_child", 0)))
         .unwrap();
     test_drain(&receiver, &mut view_state);
 
@@ -193,9 +195,9 @@ fn drain_scan_channel_child_scan_complete_clears_is_scanning() {
     }
 
     // ChildScanComplete should clear the flag.
-    let (sender2, receiver2) = cf_mpsc::unbounded_blocking();
+    let (sender2, receiver2) = ProgressiveScan::channel();
     sender2
-        .send(ScanMessage::ChildScanComplete("dir_child".to_string()))
+        .send(ScanEvent::ChildScanComplete("dir_child".to_string()))
         .unwrap();
     test_drain(&receiver2, &mut view_state);
 
@@ -214,20 +216,20 @@ fn drain_scan_channel_processes_grandchild_item_and_adds_to_parent() {
         table_width: 80,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
 
     // Scanning protocol: root Item -> empty ChildItem shell -> GrandchildItem.
     sender
-        .send(ScanMessage::Item(make_dir_item_for_scan("/root", 0)))
+        .send(ScanEvent::Item(make_dir_item_for_scan("/root", 0)))
         .unwrap();
     sender
-        .send(ScanMessage::ChildItem(make_dir_item_for_scan(
+        .send(ScanEvent::ChildItem(make_dir_item_for_scan(
             "parent_dir",
             0,
         )))
         .unwrap();
     sender
-        .send(ScanMessage::DescendantBatch {
+        .send(ScanEvent::DescendantBatch {
             ancestor_path: vec!["parent_dir".to_string()],
             children: vec![make_file_dir_item("grandchild.txt", 500)],
         })
@@ -257,13 +259,13 @@ fn drain_scan_channel_descendant_batch_sets_items_added_and_triggers_selection_r
         table_width: 80,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
 
     sender
-        .send(ScanMessage::Item(make_dir_item_for_scan("/root", 0)))
+        .send(ScanEvent::Item(make_dir_item_for_scan("/root", 0)))
         .unwrap();
     sender
-        .send(ScanMessage::ChildItem(make_dir_item_for_scan("dir_a", 0)))
+        .send(ScanEvent::ChildItem(make_dir_item_for_scan("dir_a", 0)))
         .unwrap();
 
     test_drain(&receiver, &mut view_state);
@@ -274,9 +276,9 @@ fn drain_scan_channel_descendant_batch_sets_items_added_and_triggers_selection_r
     // Select the root item at screen position 0.
     view_state.table_selected_index = 0;
 
-    let (sender2, receiver2) = cf_mpsc::unbounded_blocking();
+    let (sender2, receiver2) = ProgressiveScan::channel();
     sender2
-        .send(ScanMessage::DescendantBatch {
+        .send(ScanEvent::DescendantBatch {
             ancestor_path: vec!["dir_a".to_string()],
             children: vec![make_file_dir_item("file.txt", 300)],
         })
@@ -300,16 +302,16 @@ fn drain_scan_channel_resorts_children_after_descendant_batch_changes_sizes() {
         table_width: 80,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
 
     sender
-        .send(ScanMessage::Item(make_dir_item_for_scan("/root", 0)))
+        .send(ScanEvent::Item(make_dir_item_for_scan("/root", 0)))
         .unwrap();
     sender
-        .send(ScanMessage::ChildItem(make_dir_item_for_scan("dir_a", 0)))
+        .send(ScanEvent::ChildItem(make_dir_item_for_scan("dir_a", 0)))
         .unwrap();
     sender
-        .send(ScanMessage::ChildItem(make_file_dir_item("dir_b", 100)))
+        .send(ScanEvent::ChildItem(make_file_dir_item("dir_b", 100)))
         .unwrap();
     test_drain(&receiver, &mut view_state);
 
@@ -320,9 +322,9 @@ fn drain_scan_channel_resorts_children_after_descendant_batch_changes_sizes() {
         assert_eq!("dir_a", root.children[1].borrow().path_segment);
     }
 
-    let (sender2, receiver2) = cf_mpsc::unbounded_blocking();
+    let (sender2, receiver2) = ProgressiveScan::channel();
     sender2
-        .send(ScanMessage::DescendantBatch {
+        .send(ScanEvent::DescendantBatch {
             ancestor_path: vec!["dir_a".to_string()],
             children: vec![make_file_dir_item("big_file.txt", 500)],
         })
@@ -348,7 +350,7 @@ fn drain_scan_channel_does_not_restore_selection_on_empty_channel() {
     // No messages in channel; items_added stays false -> restore_selection not called.
     let mut view_state = make_scanning_view_state(&[("existing_child", 100)]);
     view_state.table_selected_index = 1;
-    let (_sender, receiver) = cf_mpsc::unbounded_blocking::<ScanMessage>();
+    let (_sender, receiver) = ProgressiveScan::channel();
 
     test_drain(&receiver, &mut view_state);
 
@@ -17,7 +17,7 @@ fn render_loop_exits_on_ctrl_c() {
     });
     let mut input_event_source = TestInputEventSource::new(vec![ctrl_c]);
     let should_exit = Arc::new(AtomicBool::new(false));
-    let (scan_sender, scan_receiver) = crossfire::mpsc::unbounded_blocking();
+    let (scan_sender, scan_receiver) = ProgressiveScan::channel();
 
     let result = render(
         &mut view_state,
@@ -45,7 +45,7 @@ fn render_loop_exits_when_should_exit_flag_is_set() {
     // Provide no key events; the should_exit flag will cause the loop to bail.
     let mut input_event_source = TestInputEventSource::new(vec![]);
     let should_exit = Arc::new(AtomicBool::new(true));
-    let (scan_sender, scan_receiver) = crossfire::mpsc::unbounded_blocking();
+    let (scan_sender, scan_receiver) = ProgressiveScan::channel();
 
     let result = render(
         &mut view_state,
@@ -1,18 +1,18 @@
 use super::scan_drain::drain_scan_channel;
-use crate::cli::scan_worker::ScanMessage;
-use crate::cli::scan_worker::ScanReceiver;
 use crate::cli::tui_test_utils::TestInputEventSource;
 use crate::cli::view_state::ViewState;
-use crossfire::mpsc as cf_mpsc;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
-use space_rs::{DirectoryItem, DirectoryItemType, Size};
+use space_rs::{
+    DescendantCounts, DirectoryItem, DirectoryItemType, ProgressiveScan, ScanEvent,
+    ScanEventReceiver, Size,
+};
 use std::time::{Duration, Instant};
 
 fn test_deadline() -> Instant {
     Instant::now() + Duration::from_secs(1)
 }
 
-fn test_drain(receiver: &ScanReceiver, view_state: &mut ViewState) -> bool {
+fn test_drain(receiver: &ScanEventReceiver, view_state: &mut ViewState) -> bool {
     let mut count = 1usize;
     drain_scan_channel(receiver, view_state, test_deadline(), &mut count)
 }
@@ -84,8 +84,15 @@ fn make_file_dir_item(path_segment: &str, size: u64) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::File,
         size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -95,9 +102,9 @@ fn drain_scan_channel_processes_item_and_adds_to_view_state() {
         is_scanning: true,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
     sender
-        .send(ScanMessage::Item(make_file_dir_item("a", 1000)))
+        .send(ScanEvent::Item(make_file_dir_item("a", 1000)))
         .unwrap();
 
     let complete = test_drain(&receiver, &mut view_state);
@@ -118,20 +125,27 @@ fn drain_scan_channel_processes_child_item_and_adds_to_root() {
         is_scanning: true,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
 
     // Send a root item, then a child item.
     sender
-        .send(ScanMessage::Item(DirectoryItem {
+        .send(ScanEvent::Item(DirectoryItem {
             path_segment: "/root".to_string(),
             item_type: DirectoryItemType::Directory,
             size_in_bytes: Size::new(0),
+            allocated_size_in_bytes: Size::default(),
             descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
             children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
         }))
         .unwrap();
     sender
-        .send(ScanMessage::ChildItem(make_file_dir_item("child", 500)))
+        .send(ScanEvent::ChildItem(make_file_dir_item("child", 500)))
         .unwrap();
 
     test_drain(&receiver, &mut view_state);
@@ -149,8 +163,8 @@ fn drain_scan_channel_on_complete_sets_is_scanning_false_and_returns_true() {
         is_scanning: true,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
-    sender.send(ScanMessage::Complete).unwrap();
+    let (sender, receiver) = ProgressiveScan::channel();
+    sender.send(ScanEvent::Complete).unwrap();
 
     let complete = test_drain(&receiver, &mut view_state);
 
@@ -164,15 +178,15 @@ fn drain_scan_channel_on_complete_recalculates_fractions() {
         is_scanning: true,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking();
+    let (sender, receiver) = ProgressiveScan::channel();
     // Send two items with different sizes, then complete.
     sender
-        .send(ScanMessage::Item(make_file_dir_item("a", 1000)))
+        .send(ScanEvent::Item(make_file_dir_item("a", 1000)))
         .unwrap();
     sender
-        .send(ScanMessage::Item(make_file_dir_item("b", 3000)))
+        .send(ScanEvent::Item(make_file_dir_item("b", 3000)))
         .unwrap();
-    sender.send(ScanMessage::Complete).unwrap();
+    sender.send(ScanEvent::Complete).unwrap();
 
     test_drain(&receiver, &mut view_state);
 
@@ -196,7 +210,7 @@ fn drain_scan_channel_on_disconnected_sender_sets_is_scanning_false_and_returns_
         is_scanning: true,
         ..Default::default()
     };
-    let (sender, receiver) = cf_mpsc::unbounded_blocking::<ScanMessage>();
+    let (sender, receiver) = ProgressiveScan::channel();
     drop(sender); // Disconnect without sending Complete.
 
     let complete = test_drain(&receiver, &mut view_state);
@@ -211,7 +225,7 @@ fn drain_scan_channel_with_empty_channel_does_not_change_is_scanning() {
         is_scanning: true,
         ..Default::default()
     };
-    let (_sender, receiver) = cf_mpsc::unbounded_blocking::<ScanMessage>();
+    let (_sender, receiver) = ProgressiveScan::channel();
     // Channel is empty - no messages.
 
     let complete = test_drain(&receiver, &mut view_state);
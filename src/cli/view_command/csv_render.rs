@@ -0,0 +1,234 @@
+#[cfg(test)]
+#[path = "csv_render_test.rs"]
+mod csv_render_test;
+
+use super::{
+    json_render::format_time,
+    non_interactive_render::{is_rendered, CANCEL_CHECK_INTERVAL},
+};
+use crate::cli::{row_item::RowItem, view_state::ViewState};
+use space_rs::SizeMode;
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    io::Write,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The headers of the columns before the size, which is named after the size mode, see [`size_header`].
+const LEADING_HEADERS: [&str; 3] = ["path", "depth", "type"];
+const TRAILING_HEADERS: [&str; 6] = [
+    "percentage",
+    "descendant_count",
+    "files",
+    "directories",
+    "symlinks",
+    "other",
+];
+const METADATA_HEADERS: [&str; 4] = ["modified", "uid", "gid", "permissions"];
+
+/// The separator between the fields of a row.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Delimiter {
+    /// Comma-separated values, with fields quoted as per RFC 4180 where required.
+    Comma,
+    /// Tab-separated values, with tabs, line breaks and backslashes in fields escaped using a backslash.
+    Tab,
+}
+
+impl Delimiter {
+    fn as_char(&self) -> char {
+        match self {
+            Delimiter::Comma => ',',
+            Delimiter::Tab => '\t',
+        }
+    }
+
+    /// Returns the field as it should be written, quoted or escaped if required.
+    fn escape<'a>(&self, field: &'a str) -> Cow<'a, str> {
+        match self {
+            Delimiter::Comma => {
+                if field.contains([',', '"', '\n', '\r']) {
+                    Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+                } else {
+                    Cow::Borrowed(field)
+                }
+            }
+            Delimiter::Tab => {
+                if field.contains(['\t', '\n', '\r', '\\']) {
+                    Cow::Owned(
+                        field
+                            .replace('\\', "\\\\")
+                            .replace('\t', "\\t")
+                            .replace('\n', "\\n")
+                            .replace('\r', "\\r"),
+                    )
+                } else {
+                    Cow::Borrowed(field)
+                }
+            }
+        }
+    }
+}
+
+/// Writes the items that are rendered in the table, i.e. those that are at least the size threshold and match the regex
+/// filter, as one row per item with a header row. Sizes are written as raw values rather than in display units, so that
+/// they can be sorted, in a column named after the size mode. The metadata columns are only included if the metadata
+/// was collected. Returns the number of items written.
+pub(crate) fn render_delimited<W: Write>(
+    view_state: &ViewState,
+    size_threshold_fraction: f32,
+    delimiter: Delimiter,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+) -> anyhow::Result<usize> {
+    let show_metadata = view_state
+        .item_tree
+        .iter()
+        .any(|item| item.borrow().metadata.is_some());
+
+    let mut headers = LEADING_HEADERS.to_vec();
+    headers.push(size_header(view_state.size_mode));
+    headers.extend(TRAILING_HEADERS);
+    if show_metadata {
+        headers.extend(METADATA_HEADERS);
+    }
+    write_row(&headers, delimiter, writer)?;
+
+    let mut rows_since_check = 0;
+    render_items(
+        &view_state.item_tree,
+        None,
+        0,
+        &RenderOptions {
+            size_threshold_fraction,
+            delimiter,
+            show_metadata,
+        },
+        writer,
+        should_exit,
+        &mut rows_since_check,
+    )
+}
+
+/// Returns the header of the size column, which tells what the size is, e.g. `allocated_size` for allocated bytes.
+fn size_header(size_mode: SizeMode) -> &'static str {
+    match size_mode {
+        SizeMode::Apparent => "apparent_size",
+        SizeMode::Allocated => "allocated_size",
+        SizeMode::Count => "entries",
+    }
+}
+
+struct RenderOptions {
+    size_threshold_fraction: f32,
+    delimiter: Delimiter,
+    show_metadata: bool,
+}
+
+fn render_items<W: Write>(
+    items: &[Rc<RefCell<RowItem>>],
+    parent_path: Option<&Path>,
+    depth: usize,
+    options: &RenderOptions,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+    rows_since_check: &mut usize,
+) -> anyhow::Result<usize> {
+    let mut rendered_count = 0;
+
+    for item in items {
+        let item_ref = item.borrow();
+        if !is_rendered(&item_ref, options.size_threshold_fraction) {
+            continue;
+        }
+
+        // Periodically check for cancellation.
+        *rows_since_check += 1;
+        if *rows_since_check >= CANCEL_CHECK_INTERVAL {
+            *rows_since_check = 0;
+            if should_exit.load(Ordering::Relaxed) {
+                anyhow::bail!("Cancelled.");
+            }
+        }
+
+        let path = match parent_path {
+            Some(parent_path) => parent_path.join(&item_ref.path_segment),
+            None => PathBuf::from(&item_ref.path_segment),
+        };
+        write_row(
+            &get_fields(&item_ref, &path, depth, options.show_metadata),
+            options.delimiter,
+            writer,
+        )?;
+        rendered_count += 1;
+
+        if item_ref.has_children {
+            rendered_count += render_items(
+                &item_ref.children,
+                Some(&path),
+                depth + 1,
+                options,
+                writer,
+                should_exit,
+                rows_since_check,
+            )?;
+        }
+    }
+
+    Ok(rendered_count)
+}
+
+fn get_fields(item: &RowItem, path: &Path, depth: usize, show_metadata: bool) -> Vec<String> {
+    let counts = &item.descendant_counts;
+    let mut fields = vec![
+        path.to_string_lossy().to_string(),
+        depth.to_string(),
+        item.item_type.name().to_string(),
+        item.size.get_value().to_string(),
+        format!("{:.2}", item.incl_fraction * 100f32),
+        item.descendant_count.to_string(),
+        counts.files.to_string(),
+        counts.directories.to_string(),
+        counts.symlinks.to_string(),
+        counts.other.to_string(),
+    ];
+    if show_metadata {
+        let metadata = item.metadata.as_deref();
+        fields.extend([
+            metadata
+                .and_then(|metadata| metadata.modified)
+                .map(format_time)
+                .unwrap_or_default(),
+            metadata
+                .and_then(|metadata| metadata.uid)
+                .map(|uid| uid.to_string())
+                .unwrap_or_default(),
+            metadata
+                .and_then(|metadata| metadata.gid)
+                .map(|gid| gid.to_string())
+                .unwrap_or_default(),
+            metadata
+                .and_then(|metadata| metadata.format_permissions())
+                .unwrap_or_default(),
+        ]);
+    }
+    fields
+}
+
+fn write_row<W: Write, S: AsRef<str>>(
+    fields: &[S],
+    delimiter: Delimiter,
+    writer: &mut W,
+) -> anyhow::Result<()> {
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            write!(writer, "{}", delimiter.as_char())?;
+        }
+        writer.write_all(delimiter.escape(field.as_ref()).as_bytes())?;
+    }
+    writer.write_all(b"\n")?;
+    Ok(())
+}
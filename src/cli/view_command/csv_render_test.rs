@@ -0,0 +1,180 @@
+use super::{render_delimited, Delimiter};
+use crate::cli::view_state_test_utils::{
+    make_test_view_state, make_test_view_state_from_path_with_scanner,
+    TEST_DIRECTORY_TREE_ITEM_COUNT, TEST_DIRECTORY_TREE_TOTAL_SIZE,
+};
+use crate::test_directory_utils::{create_test_directory_tree, delete_test_directory_tree};
+use rstest::rstest;
+use space_rs::{ScanOptions, SizeMode};
+use std::sync::atomic::AtomicBool;
+
+fn render_to_lines(
+    view_state: &crate::cli::view_state::ViewState,
+    size_threshold_fraction: f32,
+    delimiter: Delimiter,
+) -> anyhow::Result<(usize, Vec<String>)> {
+    let mut output = vec![];
+    let rendered_count = render_delimited(
+        view_state,
+        size_threshold_fraction,
+        delimiter,
+        &mut output,
+        &AtomicBool::new(false),
+    )?;
+    let lines = String::from_utf8(output)?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    Ok((rendered_count, lines))
+}
+
+#[rstest]
+#[case(0f32, TEST_DIRECTORY_TREE_ITEM_COUNT)]
+#[case(0.01f32, 20)]
+#[case(0.1f32, 8)]
+fn render_delimited_given_size_threshold_writes_rows_at_least_threshold(
+    #[case] size_threshold_fraction: f32,
+    #[case] expected_count: usize,
+) -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+
+    // Act
+    let (rendered_count, lines) =
+        render_to_lines(&view_state, size_threshold_fraction, Delimiter::Comma)?;
+
+    // Assert
+    assert_eq!(expected_count, rendered_count);
+    assert_eq!(expected_count + 1, lines.len());
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+#[case(Delimiter::Comma, ",")]
+#[case(Delimiter::Tab, "\t")]
+fn render_delimited_writes_header_and_raw_values(
+    #[case] delimiter: Delimiter,
+    #[case] separator: &str,
+) -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+
+    // Act
+    let (_, lines) = render_to_lines(&view_state, 0f32, delimiter)?;
+
+    // Assert
+    assert_eq!(
+        "path,depth,type,apparent_size,percentage,descendant_count,files,directories,symlinks,other"
+            .replace(',', separator),
+        lines[0]
+    );
+    let root: Vec<_> = lines[1].split(separator).collect();
+    assert_eq!(
+        vec![
+            temp_dir_path.to_string_lossy().as_ref(),
+            "0",
+            "directory",
+            &TEST_DIRECTORY_TREE_TOTAL_SIZE.to_string(),
+            "100.00",
+            "28",
+            "19",
+            "7",
+            "2",
+            "0",
+        ],
+        root
+    );
+    let d1: Vec<_> = lines[2].split(separator).collect();
+    assert_eq!(temp_dir_path.join("1").to_string_lossy().as_ref(), d1[0]);
+    assert_eq!("1", d1[1]);
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+#[case(SizeMode::Allocated, "allocated_size")]
+#[case(SizeMode::Count, "entries")]
+fn render_delimited_names_size_column_after_size_mode(
+    #[case] size_mode: SizeMode,
+    #[case] expected_header: &str,
+) -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    view_state.size_mode = size_mode;
+
+    // Act
+    let (_, lines) = render_to_lines(&view_state, 0f32, Delimiter::Comma)?;
+
+    // Assert
+    let headers: Vec<_> = lines[0].split(',').collect();
+    assert_eq!(expected_header, headers[3]);
+    assert!(!headers.contains(&"apparent_size"));
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_delimited_given_regex_filter_writes_matching_items_and_ancestors() -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    view_state.set_filter_regex(Some(regex::Regex::new("1\\.5\\.3\\.4")?));
+
+    // Act
+    let (rendered_count, lines) = render_to_lines(&view_state, 0f32, Delimiter::Comma)?;
+
+    // Assert
+    assert_eq!(5, rendered_count);
+    let depths: Vec<_> = lines[1..]
+        .iter()
+        .map(|line| line.split(',').nth(1).unwrap())
+        .collect();
+    assert_eq!(vec!["0", "1", "2", "3", "4"], depths);
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_delimited_given_metadata_writes_metadata_columns() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir_path = create_test_directory_tree()?;
+    let scanner = ScanOptions::default().collect_metadata(true).build()?;
+    let view_state =
+        make_test_view_state_from_path_with_scanner(&temp_dir_path, 7, 0, 0f32, scanner)?;
+
+    // Act
+    let (_, lines) = render_to_lines(&view_state, 0f32, Delimiter::Comma)?;
+
+    // Assert
+    assert!(lines[0].ends_with(",modified,uid,gid,permissions"));
+    let root: Vec<_> = lines[1].split(',').collect();
+    assert_eq!(14, root.len());
+    assert!(root[10].ends_with('Z'));
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+#[case(Delimiter::Comma, "plain", "plain")]
+#[case(Delimiter::Comma, "a,b", "\"a,b\"")]
+#[case(Delimiter::Comma, "say \"hi\"", "\"say \"\"hi\"\"\"")]
+#[case(Delimiter::Comma, "a\nb", "\"a\nb\"")]
+#[case(Delimiter::Tab, "a,b", "a,b")]
+#[case(Delimiter::Tab, "a\tb", "a\\tb")]
+#[case(Delimiter::Tab, "a\\b\nc", "a\\\\b\\nc")]
+fn escape_quotes_or_escapes_special_characters(
+    #[case] delimiter: Delimiter,
+    #[case] field: &str,
+    #[case] expected: &str,
+) {
+    // Act
+    let escaped = delimiter.escape(field);
+
+    // Assert
+    assert_eq!(expected, escaped);
+}
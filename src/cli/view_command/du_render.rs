@@ -0,0 +1,153 @@
+#[cfg(test)]
+#[path = "du_render_test.rs"]
+mod du_render_test;
+
+use super::non_interactive_render::{is_rendered, CANCEL_CHECK_INTERVAL};
+use crate::cli::{
+    row_item::{RowItem, RowItemType},
+    view_state::ViewState,
+};
+use space_rs::{Size, SizeDisplayFormat, SizeMode};
+use std::{
+    cell::RefCell,
+    io::Write,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The units of `du -h`, for binary sizes, and `du --si`, for metric sizes, from kilo upwards.
+const BINARY_UNITS: [&str; 6] = ["K", "M", "G", "T", "P", "E"];
+const METRIC_UNITS: [&str; 6] = ["k", "M", "G", "T", "P", "E"];
+
+/// Writes the items that are rendered in the table, i.e. those that are at least the size threshold and match the
+/// regex filter, as `<size>\t<path>` lines in the order of `du`, i.e. each item after its descendants. Only the
+/// directories and the root items are written, unless `include_files` is `true`. Returns the number of lines
+/// written.
+pub(crate) fn render_du<W: Write>(
+    view_state: &ViewState,
+    size_threshold_fraction: f32,
+    include_files: bool,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+) -> anyhow::Result<usize> {
+    let options = RenderOptions {
+        size_threshold_fraction,
+        include_files,
+        size_mode: view_state.size_mode,
+        size_display_format: view_state.size_display_format,
+    };
+    let mut lines_since_check = 0;
+    render_items(
+        &view_state.item_tree,
+        None,
+        &options,
+        writer,
+        should_exit,
+        &mut lines_since_check,
+    )
+}
+
+struct RenderOptions {
+    size_threshold_fraction: f32,
+    include_files: bool,
+    size_mode: SizeMode,
+    size_display_format: SizeDisplayFormat,
+}
+
+fn render_items<W: Write>(
+    items: &[Rc<RefCell<RowItem>>],
+    parent_path: Option<&Path>,
+    options: &RenderOptions,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+    lines_since_check: &mut usize,
+) -> anyhow::Result<usize> {
+    let mut rendered_count = 0;
+
+    for item in items {
+        let item_ref = item.borrow();
+        if !is_rendered(&item_ref, options.size_threshold_fraction) {
+            continue;
+        }
+
+        // Periodically check for cancellation.
+        *lines_since_check += 1;
+        if *lines_since_check >= CANCEL_CHECK_INTERVAL {
+            *lines_since_check = 0;
+            if should_exit.load(Ordering::Relaxed) {
+                anyhow::bail!("Cancelled.");
+            }
+        }
+
+        let path = match parent_path {
+            Some(parent_path) => parent_path.join(&item_ref.path_segment),
+            None => PathBuf::from(&item_ref.path_segment),
+        };
+        if item_ref.has_children {
+            rendered_count += render_items(
+                &item_ref.children,
+                Some(&path),
+                options,
+                writer,
+                should_exit,
+                lines_since_check,
+            )?;
+        }
+
+        // Like du, the target paths are always written, even if they are files.
+        if item_ref.item_type == RowItemType::Directory
+            || options.include_files
+            || parent_path.is_none()
+        {
+            writeln!(
+                writer,
+                "{}\t{}",
+                format_size(
+                    item_ref.size,
+                    options.size_mode,
+                    options.size_display_format
+                ),
+                path.display()
+            )?;
+            rendered_count += 1;
+        }
+    }
+
+    Ok(rendered_count)
+}
+
+/// Formats a size like `du -h` does for binary sizes, or `du --si` for metric sizes, i.e. rounded up to one decimal
+/// below 10 and to a whole number otherwise, with a single letter unit. Entry counts are written in full, like
+/// `du --inodes`.
+fn format_size(size: Size, size_mode: SizeMode, size_display_format: SizeDisplayFormat) -> String {
+    let value = size.get_value();
+    let (base, units) = match (size_mode, size_display_format) {
+        (SizeMode::Count, _) => return value.to_string(),
+        (_, SizeDisplayFormat::Binary) => (1024u64, BINARY_UNITS),
+        (_, SizeDisplayFormat::Metric) => (1000u64, METRIC_UNITS),
+    };
+    if value < base {
+        return value.to_string();
+    }
+
+    let mut unit_index = 0;
+    let mut divisor = base;
+    while unit_index + 1 < units.len() && value / divisor >= base {
+        unit_index += 1;
+        divisor *= base;
+    }
+
+    let scaled = value as f64 / divisor as f64;
+    if scaled < 10f64 {
+        let rounded = (scaled * 10f64).ceil() / 10f64;
+        if rounded < 10f64 {
+            return format!("{rounded:.1}{}", units[unit_index]);
+        }
+    }
+    let rounded = scaled.ceil() as u64;
+    if rounded >= base && unit_index + 1 < units.len() {
+        return format!("1.0{}", units[unit_index + 1]);
+    }
+    format!("{rounded}{}", units[unit_index])
+}
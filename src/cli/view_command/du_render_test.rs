@@ -0,0 +1,127 @@
+use super::{format_size, render_du};
+use crate::cli::view_state::ViewState;
+use crate::cli::view_state_test_utils::{make_test_view_state, TEST_DIRECTORY_TREE_ITEM_COUNT};
+use crate::test_directory_utils::delete_test_directory_tree;
+use rstest::rstest;
+use space_rs::{Size, SizeDisplayFormat, SizeMode};
+use std::{path::PathBuf, sync::atomic::AtomicBool};
+
+const TEST_DIRECTORY_TREE_DIRECTORY_COUNT: usize = 8;
+
+fn render_to_lines(
+    view_state: &ViewState,
+    include_files: bool,
+) -> anyhow::Result<(usize, Vec<(String, PathBuf)>)> {
+    let mut output = vec![];
+    let rendered_count = render_du(
+        view_state,
+        0f32,
+        include_files,
+        &mut output,
+        &AtomicBool::new(false),
+    )?;
+    let lines = String::from_utf8(output)?
+        .lines()
+        .map(|line| {
+            let (size, path) = line.split_once('\t').unwrap();
+            (size.to_string(), PathBuf::from(path))
+        })
+        .collect();
+    Ok((rendered_count, lines))
+}
+
+#[rstest]
+#[case(false, TEST_DIRECTORY_TREE_DIRECTORY_COUNT)]
+#[case(true, TEST_DIRECTORY_TREE_ITEM_COUNT)]
+fn render_du_writes_directories_and_optionally_files(
+    #[case] include_files: bool,
+    #[case] expected_count: usize,
+) -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+
+    // Act
+    let (rendered_count, lines) = render_to_lines(&view_state, include_files)?;
+
+    // Assert
+    assert_eq!(expected_count, rendered_count);
+    assert_eq!(expected_count, lines.len());
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_du_writes_each_item_after_its_descendants() -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+
+    // Act
+    let (_, lines) = render_to_lines(&view_state, true)?;
+
+    // Assert
+    assert_eq!(
+        &("180k".to_string(), temp_dir_path.clone()),
+        lines.last().unwrap()
+    );
+    for (index, (_, path)) in lines.iter().enumerate() {
+        // None of the ancestors of an item have been written yet.
+        assert!(lines[..index]
+            .iter()
+            .all(|(_, earlier_path)| !path.starts_with(earlier_path)));
+    }
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_du_given_regex_filter_writes_matching_items_and_ancestors() -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    view_state.set_filter_regex(Some(regex::Regex::new("1\\.5\\.3\\.4")?));
+
+    // Act
+    let (_, lines) = render_to_lines(&view_state, false)?;
+
+    // Assert
+    let d1_5 = temp_dir_path.join("1").join("1.5");
+    let paths: Vec<_> = lines.into_iter().map(|(_, path)| path).collect();
+    assert_eq!(
+        vec![
+            d1_5.join("1.5.3"),
+            d1_5.clone(),
+            temp_dir_path.join("1"),
+            temp_dir_path.clone()
+        ],
+        paths
+    );
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+#[case(0, SizeMode::Apparent, SizeDisplayFormat::Binary, "0")]
+#[case(1023, SizeMode::Apparent, SizeDisplayFormat::Binary, "1023")]
+#[case(1024, SizeMode::Apparent, SizeDisplayFormat::Binary, "1.0K")]
+#[case(1025, SizeMode::Apparent, SizeDisplayFormat::Binary, "1.1K")]
+#[case(10 * 1024 - 1, SizeMode::Apparent, SizeDisplayFormat::Binary, "10K")]
+#[case(1024 * 1024 - 1, SizeMode::Apparent, SizeDisplayFormat::Binary, "1.0M")]
+#[case(5 * 1024 * 1024 * 1024, SizeMode::Allocated, SizeDisplayFormat::Binary, "5.0G")]
+#[case(999, SizeMode::Apparent, SizeDisplayFormat::Metric, "999")]
+#[case(180000, SizeMode::Apparent, SizeDisplayFormat::Metric, "180k")]
+#[case(2_500_000, SizeMode::Apparent, SizeDisplayFormat::Metric, "2.5M")]
+#[case(2_500_000, SizeMode::Count, SizeDisplayFormat::Metric, "2500000")]
+fn format_size_formats_like_du_human_readable(
+    #[case] value: u64,
+    #[case] size_mode: SizeMode,
+    #[case] size_display_format: SizeDisplayFormat,
+    #[case] expected: &str,
+) {
+    // Act
+    let formatted = format_size(Size::new(value), size_mode, size_display_format);
+
+    // Assert
+    assert_eq!(expected, formatted);
+}
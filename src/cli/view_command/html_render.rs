@@ -0,0 +1,206 @@
+#[cfg(test)]
+#[path = "html_render_test.rs"]
+mod html_render_test;
+
+use super::{
+    json_render::{format_time, size_mode_name, to_percentage, write_value},
+    non_interactive_render::{
+        describe_filters, get_total_size, is_rendered, CANCEL_CHECK_INTERVAL,
+    },
+};
+use crate::cli::{row_item::RowItem, view_state::ViewState};
+use space_rs::{SizeDisplayFormat, SizeMode};
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    path::MAIN_SEPARATOR_STR,
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+    time::SystemTime,
+};
+
+/// The page, with inline styles and scripts so that it can be viewed offline.
+const TEMPLATE: &str = include_str!("html_render_template.html");
+const TITLE_PLACEHOLDER: &str = "{{title}}";
+/// Replaced with the data of the report. The `null` keeps the template a valid script.
+const DATA_PLACEHOLDER: &str = "/*{{data}}*/null";
+
+/// Writes the items that are rendered in the table, i.e. those that are at least the size threshold and match the
+/// regex filter, as a single self-contained HTML page with a zoomable treemap and a collapsible tree. The items are
+/// embedded in the page as JSON data, which is written as the items are visited. Returns the number of items
+/// written.
+pub(crate) fn render_html<W: Write>(
+    view_state: &ViewState,
+    size_threshold_fraction: f32,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+) -> anyhow::Result<usize> {
+    let (head, tail) = TEMPLATE
+        .split_once(DATA_PLACEHOLDER)
+        .expect("The HTML template has a data placeholder");
+    let title = get_title(view_state);
+    let total_size = get_total_size(view_state);
+    writer.write_all(
+        head.replace(TITLE_PLACEHOLDER, &escape_html(&title))
+            .as_bytes(),
+    )?;
+
+    let mut writer = ScriptSafeWriter(writer);
+    writer.write_all(b"{\"title\":")?;
+    write_value(&mut writer, &title)?;
+    writer.write_all(b",\"generated\":")?;
+    write_value(&mut writer, &format_time(SystemTime::now()))?;
+    write!(
+        writer,
+        ",\"size_mode\":\"{}\",\"total\":{},\"total_display\":",
+        size_mode_name(view_state.size_mode),
+        total_size.get_value()
+    )?;
+    write_value(
+        &mut writer,
+        &total_size.to_string_for_mode(view_state.size_mode, view_state.size_display_format),
+    )?;
+    writer.write_all(b",\"filters\":")?;
+    write_value(
+        &mut writer,
+        &describe_filters(size_threshold_fraction, view_state.filter_regex.as_ref()),
+    )?;
+    write!(
+        writer,
+        ",\"error_count\":{},\"separator\":",
+        view_state.scan_errors.len()
+    )?;
+    write_value(&mut writer, MAIN_SEPARATOR_STR)?;
+    writer.write_all(b",\"items\":")?;
+
+    let options = RenderOptions {
+        size_threshold_fraction,
+        size_mode: view_state.size_mode,
+        size_display_format: view_state.size_display_format,
+    };
+    let mut items_since_check = 0;
+    let rendered_count = render_items(
+        &view_state.item_tree,
+        &options,
+        &mut writer,
+        should_exit,
+        &mut items_since_check,
+    )?;
+    writer.write_all(b"}")?;
+
+    writer.0.write_all(tail.as_bytes())?;
+
+    Ok(rendered_count)
+}
+
+struct RenderOptions {
+    size_threshold_fraction: f32,
+    size_mode: SizeMode,
+    size_display_format: SizeDisplayFormat,
+}
+
+/// Writes the rendered items as a JSON array of nodes, with short keys to keep large reports small. Only the name
+/// of each item is written, since the page builds the paths from the names of the ancestors.
+fn render_items<W: Write>(
+    items: &[Rc<RefCell<RowItem>>],
+    options: &RenderOptions,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+    items_since_check: &mut usize,
+) -> anyhow::Result<usize> {
+    let mut rendered_count = 0;
+    let mut is_first = true;
+
+    writer.write_all(b"[")?;
+    for item in items {
+        let item_ref = item.borrow();
+        if !is_rendered(&item_ref, options.size_threshold_fraction) {
+            continue;
+        }
+
+        // Periodically check for cancellation.
+        *items_since_check += 1;
+        if *items_since_check >= CANCEL_CHECK_INTERVAL {
+            *items_since_check = 0;
+            if should_exit.load(Ordering::Relaxed) {
+                anyhow::bail!("Cancelled.");
+            }
+        }
+
+        if !is_first {
+            writer.write_all(b",")?;
+        }
+        is_first = false;
+
+        writer.write_all(b"{\"n\":")?;
+        write_value(writer, &item_ref.path_segment)?;
+        write!(
+            writer,
+            ",\"t\":\"{}\",\"s\":{},\"d\":",
+            item_ref.item_type.name(),
+            item_ref.size.get_value()
+        )?;
+        write_value(
+            writer,
+            &item_ref
+                .size
+                .to_string_for_mode(options.size_mode, options.size_display_format),
+        )?;
+        writer.write_all(b",\"f\":")?;
+        write_value(writer, &to_percentage(item_ref.incl_fraction))?;
+
+        rendered_count += 1;
+        if item_ref.has_children {
+            writer.write_all(b",\"c\":")?;
+            rendered_count += render_items(
+                &item_ref.children,
+                options,
+                writer,
+                should_exit,
+                items_since_check,
+            )?;
+        }
+        writer.write_all(b"}")?;
+    }
+    writer.write_all(b"]")?;
+
+    Ok(rendered_count)
+}
+
+/// The title of the report, i.e. the paths of the root items.
+fn get_title(view_state: &ViewState) -> String {
+    let paths: Vec<_> = view_state
+        .item_tree
+        .iter()
+        .map(|item| item.borrow().path_segment.clone())
+        .collect();
+    format!("Disk usage of {}", paths.join(", "))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escapes every `<` in the JSON data that is embedded in the script element, so that a name such as `</script>` or
+/// `<!--` cannot end the element early. A `<` can only occur inside JSON strings, where
+/// `\u003c` is equivalent.
+struct ScriptSafeWriter<'a, W: Write>(&'a mut W);
+
+impl<W: Write> Write for ScriptSafeWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for (index, part) in buf.split(|byte| *byte == b'<').enumerate() {
+            if index > 0 {
+                self.0.write_all(b"\\u003c")?;
+            }
+            self.0.write_all(part)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
@@ -0,0 +1,132 @@
+use super::{escape_html, render_html, ScriptSafeWriter};
+use crate::cli::view_state::ViewState;
+use crate::cli::view_state_test_utils::{
+    make_test_view_state, TEST_DIRECTORY_TREE_ITEM_COUNT, TEST_DIRECTORY_TREE_TOTAL_SIZE,
+};
+use crate::test_directory_utils::delete_test_directory_tree;
+use rstest::rstest;
+use serde_json::Value;
+use std::{io::Write, sync::atomic::AtomicBool};
+
+const DATA_PREFIX: &str = "const data = ";
+
+fn render_to_string(view_state: &ViewState) -> anyhow::Result<(usize, String)> {
+    let mut output = vec![];
+    let rendered_count = render_html(view_state, 0f32, &mut output, &AtomicBool::new(false))?;
+    Ok((rendered_count, String::from_utf8(output)?))
+}
+
+/// Extracts the data that is embedded in the script of the page.
+fn extract_data(html: &str) -> anyhow::Result<Value> {
+    let start = html.find(DATA_PREFIX).unwrap() + DATA_PREFIX.len();
+    let end = start + html[start..].find(";\n").unwrap();
+    Ok(serde_json::from_str(&html[start..end])?)
+}
+
+fn count_nodes(nodes: &Value) -> usize {
+    nodes
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| 1 + node.get("c").map_or(0, count_nodes))
+        .sum()
+}
+
+#[rstest]
+fn render_html_writes_self_contained_page() -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+
+    // Act
+    let (rendered_count, html) = render_to_string(&view_state)?;
+
+    // Assert
+    assert_eq!(TEST_DIRECTORY_TREE_ITEM_COUNT, rendered_count);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.trim_end().ends_with("</html>"));
+    assert!(html.contains(&format!(
+        "<title>Disk usage of {}</title>",
+        temp_dir_path.display()
+    )));
+    // Nothing is loaded from elsewhere, so the page can be viewed offline.
+    assert!(!html.contains("src="));
+    assert!(!html.contains("<link"));
+    assert!(!html.contains("{{"));
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_html_embeds_tree_of_rendered_items() -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+
+    // Act
+    let (_, html) = render_to_string(&view_state)?;
+
+    // Assert
+    let data = extract_data(&html)?;
+    assert_eq!("apparent", data["size_mode"]);
+    assert_eq!(TEST_DIRECTORY_TREE_TOTAL_SIZE, data["total"]);
+    assert_eq!("180 KB", data["total_display"]);
+    assert!(data["filters"].is_null());
+    assert_eq!(TEST_DIRECTORY_TREE_ITEM_COUNT, count_nodes(&data["items"]));
+    let root = &data["items"][0];
+    assert_eq!(temp_dir_path.to_string_lossy().as_ref(), root["n"]);
+    assert_eq!("directory", root["t"]);
+    assert_eq!(100.0, root["f"]);
+    assert_eq!("1", root["c"][0]["n"]);
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_html_given_regex_filter_embeds_matching_items_and_ancestors() -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    view_state.set_filter_regex(Some(regex::Regex::new("1\\.5\\.3\\.4")?));
+
+    // Act
+    let (rendered_count, html) = render_to_string(&view_state)?;
+
+    // Assert
+    assert_eq!(5, rendered_count);
+    let data = extract_data(&html)?;
+    assert_eq!(
+        "Only showing items that match regex \"1\\.5\\.3\\.4\"",
+        data["filters"]
+    );
+    let file = &data["items"][0]["c"][0]["c"][0]["c"][0]["c"][0];
+    assert_eq!("1.5.3.4", file["n"]);
+    assert!(file.get("c").is_none());
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn script_safe_writer_escapes_less_than_signs() -> anyhow::Result<()> {
+    // Arrange
+    let mut output = vec![];
+
+    // Act
+    serde_json::to_writer(ScriptSafeWriter(&mut output), "a</script><!--b")?;
+    ScriptSafeWriter(&mut output).flush()?;
+
+    // Assert
+    let json = String::from_utf8(output)?;
+    assert_eq!("\"a\\u003c/script>\\u003c!--b\"", json);
+    assert_eq!("a</script><!--b", serde_json::from_str::<String>(&json)?);
+    Ok(())
+}
+
+#[rstest]
+fn escape_html_escapes_markup_characters() {
+    // Act
+    let escaped = escape_html("<a href=\"x\">&</a>");
+
+    // Assert
+    assert_eq!("&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;", escaped);
+}
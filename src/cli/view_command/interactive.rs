@@ -1,22 +1,34 @@
 use super::ViewCommand;
 use crate::cli::{
-    crossterm_input_event_source::CrosstermInputEventSource, scan_worker, skin::Skin, tui,
-    view_state::ViewState,
+    crossterm_input_event_source::CrosstermInputEventSource, skin::Skin, tui, view_state::ViewState,
+};
+use space_rs::{
+    DirectoryItem, ProgressiveScan, ScanEvent, ScanEventSender, SizeDisplayFormat, SizeMode,
 };
-use space_rs::SizeDisplayFormat;
 use std::io::Write;
 
 impl ViewCommand {
     pub(super) fn run_interactive<W: Write>(
-        &self,
+        &mut self,
         writer: &mut W,
         size_display_format: SizeDisplayFormat,
         size_threshold_fraction: f32,
         skin: &Skin,
     ) -> anyhow::Result<()> {
-        let paths = self.get_sanitized_paths();
-        let (scan_sender, scan_receiver) = crossfire::mpsc::unbounded_blocking();
-        scan_worker::spawn_scan(paths, self.should_exit.clone(), scan_sender.clone());
+        let (scan_sender, scan_receiver) = ProgressiveScan::channel();
+        let loaded_items = self.loaded_items.take();
+        let is_read_only = loaded_items.is_some();
+        match loaded_items {
+            Some(items) => self.send_loaded_items(items, &scan_sender),
+            None => {
+                ProgressiveScan::start(
+                    self.get_sanitized_paths(),
+                    self.scanner.clone(),
+                    self.should_exit.clone(),
+                    scan_sender.clone(),
+                );
+            }
+        }
 
         let mut view_state = ViewState::new(
             vec![],
@@ -25,7 +37,11 @@ impl ViewCommand {
             self.filter_regex.clone(),
             skin,
         );
+        view_state.size_mode = self.size_mode;
+        view_state.show_counts = self.show_counts;
+        view_state.scanner = self.scanner.clone();
         view_state.is_scanning = true;
+        view_state.is_read_only = is_read_only;
 
         // TODO: Push any error into some sort of error stream and expose in UI.
         let _ = view_state.read_config_file();
@@ -45,4 +61,22 @@ impl ViewCommand {
 
         Ok(())
     }
+
+    /// Sends the loaded items as if they were the result of a completed scan, so that the TUI shows them the same
+    /// way.
+    fn send_loaded_items(&mut self, mut items: Vec<DirectoryItem>, scan_sender: &ScanEventSender) {
+        // Items are sorted by apparent size when loaded, so re-sort for any other size mode.
+        if self.size_mode != SizeMode::Apparent {
+            items
+                .iter_mut()
+                .for_each(|item| item.sort_by_size(self.size_mode));
+        }
+        for item in items {
+            let _ = scan_sender.send(ScanEvent::Item(item));
+        }
+        if !self.scan_errors.is_empty() {
+            let _ = scan_sender.send(ScanEvent::Errors(std::mem::take(&mut self.scan_errors)));
+        }
+        let _ = scan_sender.send(ScanEvent::Complete);
+    }
 }
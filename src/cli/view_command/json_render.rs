@@ -0,0 +1,228 @@
+#[cfg(test)]
+#[path = "json_render_test.rs"]
+mod json_render_test;
+
+use super::non_interactive_render::{is_rendered, CANCEL_CHECK_INTERVAL};
+use crate::cli::{row_item::RowItem, view_state::ViewState};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use space_rs::{ItemMetadata, ScanError, SizeMode};
+use std::{
+    cell::RefCell,
+    io::Write,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+    time::SystemTime,
+};
+
+/// The version of the JSON document, which is incremented whenever a change is made that breaks existing readers.
+pub(crate) const JSON_OUTPUT_VERSION: u32 = 1;
+
+/// The metadata of an item, with the timestamps in RFC 3339 format.
+#[derive(Serialize)]
+struct JsonMetadata {
+    modified: Option<String>,
+    accessed: Option<String>,
+    changed: Option<String>,
+    uid: Option<u32>,
+    gid: Option<u32>,
+    mode: Option<u32>,
+    permissions: Option<String>,
+}
+
+impl From<&ItemMetadata> for JsonMetadata {
+    fn from(metadata: &ItemMetadata) -> Self {
+        JsonMetadata {
+            modified: metadata.modified.map(format_time),
+            accessed: metadata.accessed.map(format_time),
+            changed: metadata.changed.map(format_time),
+            uid: metadata.uid,
+            gid: metadata.gid,
+            mode: metadata.mode,
+            permissions: metadata.format_permissions(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonScanError<'a> {
+    path: &'a Path,
+    message: &'a str,
+}
+
+/// Writes the items that are rendered in the table, i.e. those that are at least the size threshold and match the
+/// regex filter, as a single JSON document with a tree of items. The items are written as they are visited rather
+/// than building the document in memory, so that large trees can be written. Returns the number of items written.
+pub(crate) fn render_json<W: Write>(
+    view_state: &ViewState,
+    size_threshold_fraction: f32,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+) -> anyhow::Result<usize> {
+    write!(
+        writer,
+        "{{\"version\":{JSON_OUTPUT_VERSION},\"size_mode\":\"{}\",\"size_threshold_percentage\":",
+        size_mode_name(view_state.size_mode)
+    )?;
+    write_value(writer, &to_percentage(size_threshold_fraction))?;
+    writer.write_all(b",\"filter_regex\":")?;
+    write_value(
+        writer,
+        &view_state.filter_regex.as_ref().map(|regex| regex.as_str()),
+    )?;
+    writer.write_all(b",\"items\":")?;
+
+    let mut items_since_check = 0;
+    let rendered_count = render_items(
+        &view_state.item_tree,
+        None,
+        size_threshold_fraction,
+        writer,
+        should_exit,
+        &mut items_since_check,
+    )?;
+
+    writer.write_all(b",\"errors\":")?;
+    let errors: Vec<_> = view_state
+        .scan_errors
+        .iter()
+        .map(|error: &ScanError| JsonScanError {
+            path: &error.path,
+            message: &error.message,
+        })
+        .collect();
+    write_value(writer, &errors)?;
+    writer.write_all(b"}\n")?;
+
+    Ok(rendered_count)
+}
+
+/// Writes the rendered items as a JSON array. The paths of the items are relative to `parent_path`, if any.
+fn render_items<W: Write>(
+    items: &[Rc<RefCell<RowItem>>],
+    parent_path: Option<&Path>,
+    size_threshold_fraction: f32,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+    items_since_check: &mut usize,
+) -> anyhow::Result<usize> {
+    let mut rendered_count = 0;
+    let mut is_first = true;
+
+    writer.write_all(b"[")?;
+    for item in items {
+        let item_ref = item.borrow();
+        if !is_rendered(&item_ref, size_threshold_fraction) {
+            continue;
+        }
+
+        // Periodically check for cancellation.
+        *items_since_check += 1;
+        if *items_since_check >= CANCEL_CHECK_INTERVAL {
+            *items_since_check = 0;
+            if should_exit.load(Ordering::Relaxed) {
+                anyhow::bail!("Cancelled.");
+            }
+        }
+
+        if !is_first {
+            writer.write_all(b",")?;
+        }
+        is_first = false;
+
+        let path = match parent_path {
+            Some(parent_path) => parent_path.join(&item_ref.path_segment),
+            None => PathBuf::from(&item_ref.path_segment),
+        };
+        rendered_count += render_item(
+            &item_ref,
+            &path,
+            size_threshold_fraction,
+            writer,
+            should_exit,
+            items_since_check,
+        )?;
+    }
+    writer.write_all(b"]")?;
+
+    Ok(rendered_count)
+}
+
+fn render_item<W: Write>(
+    item: &RowItem,
+    path: &Path,
+    size_threshold_fraction: f32,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+    items_since_check: &mut usize,
+) -> anyhow::Result<usize> {
+    writer.write_all(b"{\"path\":")?;
+    write_value(writer, &path)?;
+    write!(
+        writer,
+        ",\"type\":\"{}\",\"size\":{},\"percentage\":",
+        item.item_type.name(),
+        item.size.get_value()
+    )?;
+    write_value(writer, &to_percentage(item.incl_fraction))?;
+    write!(writer, ",\"descendant_count\":{}", item.descendant_count)?;
+    writer.write_all(b",\"counts\":")?;
+    write_value(writer, &item.descendant_counts)?;
+    if item.ignored_size.get_value() > 0 {
+        write!(
+            writer,
+            ",\"ignored_size\":{}",
+            item.ignored_size.get_value()
+        )?;
+    }
+    if let Some(link_target) = &item.link_target {
+        writer.write_all(b",\"link_target\":")?;
+        write_value(writer, link_target)?;
+    }
+    if let Some(metadata) = &item.metadata {
+        writer.write_all(b",\"metadata\":")?;
+        write_value(writer, &JsonMetadata::from(metadata.as_ref()))?;
+    }
+
+    let mut rendered_count = 1;
+    if item.has_children {
+        writer.write_all(b",\"children\":")?;
+        rendered_count += render_items(
+            &item.children,
+            Some(path),
+            size_threshold_fraction,
+            writer,
+            should_exit,
+            items_since_check,
+        )?;
+    }
+    writer.write_all(b"}")?;
+
+    Ok(rendered_count)
+}
+
+pub(super) fn write_value<W: Write, T: Serialize + ?Sized>(
+    writer: &mut W,
+    value: &T,
+) -> anyhow::Result<()> {
+    serde_json::to_writer(writer, value)?;
+    Ok(())
+}
+
+/// Converts a fraction to a percentage, rounded to two decimal places.
+pub(super) fn to_percentage(fraction: f32) -> f64 {
+    (fraction as f64 * 10000f64).round() / 100f64
+}
+
+pub(super) fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+pub(super) fn size_mode_name(size_mode: SizeMode) -> &'static str {
+    match size_mode {
+        SizeMode::Apparent => "apparent",
+        SizeMode::Allocated => "allocated",
+        SizeMode::Count => "count",
+    }
+}
@@ -0,0 +1,171 @@
+use super::render_json;
+use crate::cli::view_state_test_utils::{
+    make_test_view_state, make_test_view_state_from_path_with_scanner,
+    TEST_DIRECTORY_TREE_ITEM_COUNT, TEST_DIRECTORY_TREE_TOTAL_SIZE,
+};
+use crate::test_directory_utils::{create_test_directory_tree, delete_test_directory_tree};
+use rstest::rstest;
+use serde_json::Value;
+use space_rs::{ScanError, ScanOptions};
+use std::{io, path::PathBuf, sync::atomic::AtomicBool};
+
+/// Returns all the items in the JSON tree, depth-first.
+fn flatten(items: &Value) -> Vec<&Value> {
+    let mut flattened = vec![];
+    for item in items.as_array().unwrap() {
+        flattened.push(item);
+        if let Some(children) = item.get("children") {
+            flattened.extend(flatten(children));
+        }
+    }
+    flattened
+}
+
+#[rstest]
+#[case(0f32, TEST_DIRECTORY_TREE_ITEM_COUNT)]
+#[case(0.01f32, 20)]
+#[case(0.1f32, 8)]
+fn render_json_given_size_threshold_writes_items_at_least_threshold(
+    #[case] size_threshold_fraction: f32,
+    #[case] expected_count: usize,
+) -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    let mut output = vec![];
+
+    // Act
+    let rendered_count = render_json(
+        &view_state,
+        size_threshold_fraction,
+        &mut output,
+        &AtomicBool::new(false),
+    )?;
+
+    // Assert
+    let json: Value = serde_json::from_slice(&output)?;
+    assert_eq!(expected_count, rendered_count);
+    assert_eq!(expected_count, flatten(&json["items"]).len());
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_json_writes_path_type_size_percentage_and_counts() -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    let mut output = vec![];
+
+    // Act
+    render_json(&view_state, 0f32, &mut output, &AtomicBool::new(false))?;
+
+    // Assert
+    let json: Value = serde_json::from_slice(&output)?;
+    assert_eq!(1, json["version"]);
+    assert_eq!("apparent", json["size_mode"]);
+    let root = &json["items"][0];
+    assert_eq!(temp_dir_path.to_string_lossy().as_ref(), root["path"]);
+    assert_eq!("directory", root["type"]);
+    assert_eq!(TEST_DIRECTORY_TREE_TOTAL_SIZE, root["size"]);
+    assert_eq!(100.0, root["percentage"]);
+    assert_eq!(28, root["descendant_count"]);
+    assert_eq!(8 - 1, root["counts"]["directories"]);
+    assert_eq!(19, root["counts"]["files"]);
+    assert_eq!(2, root["counts"]["symlinks"]);
+    let d1 = flatten(&json["items"])
+        .into_iter()
+        .find(|item| item["path"] == temp_dir_path.join("1").to_string_lossy().as_ref())
+        .unwrap();
+    assert_eq!("directory", d1["type"]);
+    assert!(d1["children"].is_array());
+    let file = flatten(&json["items"])
+        .into_iter()
+        .find(|item| item["type"] == "file")
+        .unwrap();
+    assert!(file.get("children").is_none());
+    assert!(file.get("metadata").is_none());
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_json_given_regex_filter_writes_matching_items_and_ancestors() -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    view_state.set_filter_regex(Some(regex::Regex::new("1\\.5\\.3\\.4")?));
+    let mut output = vec![];
+
+    // Act
+    render_json(&view_state, 0f32, &mut output, &AtomicBool::new(false))?;
+
+    // Assert
+    let json: Value = serde_json::from_slice(&output)?;
+    assert_eq!("1\\.5\\.3\\.4", json["filter_regex"]);
+    let paths: Vec<_> = flatten(&json["items"])
+        .into_iter()
+        .map(|item| PathBuf::from(item["path"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        vec![
+            temp_dir_path.clone(),
+            temp_dir_path.join("1"),
+            temp_dir_path.join("1").join("1.5"),
+            temp_dir_path.join("1").join("1.5").join("1.5.3"),
+            temp_dir_path
+                .join("1")
+                .join("1.5")
+                .join("1.5.3")
+                .join("1.5.3.4"),
+        ],
+        paths
+    );
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_json_given_metadata_writes_metadata() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir_path = create_test_directory_tree()?;
+    let scanner = ScanOptions::default().collect_metadata(true).build()?;
+    let view_state =
+        make_test_view_state_from_path_with_scanner(&temp_dir_path, 7, 0, 0f32, scanner)?;
+    let mut output = vec![];
+
+    // Act
+    render_json(&view_state, 0f32, &mut output, &AtomicBool::new(false))?;
+
+    // Assert
+    let json: Value = serde_json::from_slice(&output)?;
+    let metadata = &json["items"][0]["metadata"];
+    assert!(metadata["modified"].as_str().unwrap().ends_with('Z'));
+    #[cfg(unix)]
+    assert_eq!(Some(9), metadata["permissions"].as_str().map(str::len));
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_json_writes_scan_errors() -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    view_state.scan_errors = vec![ScanError::new(
+        &PathBuf::from("/denied"),
+        &io::Error::from(io::ErrorKind::PermissionDenied),
+    )];
+    let mut output = vec![];
+
+    // Act
+    render_json(&view_state, 0f32, &mut output, &AtomicBool::new(false))?;
+
+    // Assert
+    let json: Value = serde_json::from_slice(&output)?;
+    assert_eq!("/denied", json["errors"][0]["path"]);
+    assert!(json["errors"][0]["message"].is_string());
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
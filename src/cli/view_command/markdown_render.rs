@@ -0,0 +1,219 @@
+#[cfg(test)]
+#[path = "markdown_render_test.rs"]
+mod markdown_render_test;
+
+use super::non_interactive_render::{
+    describe_filters, get_total_size, is_rendered, CANCEL_CHECK_INTERVAL,
+};
+use crate::cli::{
+    row_item::{RowItem, RowItemType},
+    view_state::ViewState,
+};
+use space_rs::{SizeDisplayFormat, SizeMode};
+use std::{
+    cell::RefCell,
+    io::Write,
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Writes the items that are rendered in the table, i.e. those that are at least the size threshold and match the
+/// regex filter, as a Markdown document with a nested list of items, after a summary of the roots, the total and the
+/// filters. Any scan errors are listed at the end. Returns the number of items written.
+pub(crate) fn render_markdown<W: Write>(
+    view_state: &ViewState,
+    size_threshold_fraction: f32,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+) -> anyhow::Result<usize> {
+    render_summary(view_state, size_threshold_fraction, writer)?;
+
+    writeln!(writer, "\n## Items\n")?;
+    let options = RenderOptions {
+        size_threshold_fraction,
+        size_mode: view_state.size_mode,
+        size_display_format: view_state.size_display_format,
+    };
+    let mut items_since_check = 0;
+    let rendered_count = render_items(
+        &view_state.item_tree,
+        0,
+        &options,
+        writer,
+        should_exit,
+        &mut items_since_check,
+    )?;
+    if rendered_count == 0 {
+        writeln!(writer, "_No items._")?;
+    }
+
+    if !view_state.scan_errors.is_empty() {
+        writeln!(writer, "\n## Scan errors\n")?;
+        writeln!(
+            writer,
+            "The sizes of the items containing the following paths are undercounted:\n"
+        )?;
+        for error in &view_state.scan_errors {
+            writeln!(
+                writer,
+                "- {}: {}",
+                code_span(&error.path.to_string_lossy()),
+                escape_markdown(&error.message)
+            )?;
+        }
+    }
+
+    Ok(rendered_count)
+}
+
+struct RenderOptions {
+    size_threshold_fraction: f32,
+    size_mode: SizeMode,
+    size_display_format: SizeDisplayFormat,
+}
+
+/// Writes the title and a list with the roots, the total and the active filters, i.e. the same information as the
+/// message after the table.
+fn render_summary<W: Write>(
+    view_state: &ViewState,
+    size_threshold_fraction: f32,
+    writer: &mut W,
+) -> anyhow::Result<()> {
+    writeln!(writer, "# Disk usage\n")?;
+
+    let roots: Vec<_> = view_state
+        .item_tree
+        .iter()
+        .map(|item| code_span(&item.borrow().path_segment))
+        .collect();
+    writeln!(
+        writer,
+        "- **{}:** {}",
+        if roots.len() == 1 { "Root" } else { "Roots" },
+        roots.join(", ")
+    )?;
+
+    let total_size = get_total_size(view_state);
+    let item_count: usize = view_state
+        .item_tree
+        .iter()
+        .map(|item| item.borrow().descendant_count + 1)
+        .sum();
+    match view_state.size_mode {
+        SizeMode::Count => writeln!(writer, "- **Total:** {item_count} entries")?,
+        size_mode => writeln!(
+            writer,
+            "- **Total:** {} ({} size) in {item_count} items",
+            total_size.to_string(view_state.size_display_format),
+            if size_mode == SizeMode::Allocated {
+                "allocated"
+            } else {
+                "apparent"
+            }
+        )?,
+    }
+
+    let filters = describe_filters(size_threshold_fraction, view_state.filter_regex.as_ref())
+        .unwrap_or_else(|| String::from("None, all items are shown"));
+    writeln!(writer, "- **Filters:** {}", escape_markdown(&filters))?;
+
+    if !view_state.scan_errors.is_empty() {
+        let error_count = view_state.scan_errors.len();
+        writeln!(
+            writer,
+            "- **Scan errors:** {error_count}, see [below](#scan-errors)"
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the rendered items as list items, indented by their depth, with their descendants nested below them.
+fn render_items<W: Write>(
+    items: &[Rc<RefCell<RowItem>>],
+    depth: usize,
+    options: &RenderOptions,
+    writer: &mut W,
+    should_exit: &AtomicBool,
+    items_since_check: &mut usize,
+) -> anyhow::Result<usize> {
+    let mut rendered_count = 0;
+
+    for item in items {
+        let item_ref = item.borrow();
+        if !is_rendered(&item_ref, options.size_threshold_fraction) {
+            continue;
+        }
+
+        // Periodically check for cancellation.
+        *items_since_check += 1;
+        if *items_since_check >= CANCEL_CHECK_INTERVAL {
+            *items_since_check = 0;
+            if should_exit.load(Ordering::Relaxed) {
+                anyhow::bail!("Cancelled.");
+            }
+        }
+
+        write!(
+            writer,
+            "{:indent$}- {} {} ({:.1}%)",
+            "",
+            code_span(&item_ref.path_segment),
+            item_ref
+                .size
+                .to_string_for_mode(options.size_mode, options.size_display_format),
+            item_ref.incl_fraction * 100f32,
+            indent = depth * 2
+        )?;
+        if item_ref.item_type != RowItemType::Directory && item_ref.item_type != RowItemType::File {
+            write!(writer, " _{}_", item_ref.item_type.name().replace('_', " "))?;
+        }
+        writeln!(writer)?;
+        rendered_count += 1;
+
+        if item_ref.has_children {
+            rendered_count += render_items(
+                &item_ref.children,
+                depth + 1,
+                options,
+                writer,
+                should_exit,
+                items_since_check,
+            )?;
+        }
+    }
+
+    Ok(rendered_count)
+}
+
+/// Formats the text as inline code, using a fence of more backticks than the text contains, so that paths are
+/// shown as is.
+fn code_span(text: &str) -> String {
+    let mut longest_run = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest_run = longest_run.max(run);
+    }
+    let fence = "`".repeat(longest_run + 1);
+    // Spaces separate backticks at the start or end of the text from the fence, and are stripped when rendered.
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{fence} {text} {fence}")
+    } else {
+        format!("{fence}{text}{fence}")
+    }
+}
+
+/// Escapes the characters that Markdown would otherwise interpret in inline text.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,165 @@
+use super::{code_span, escape_markdown, render_markdown};
+use crate::cli::view_state::ViewState;
+use crate::cli::view_state_test_utils::{make_test_view_state, TEST_DIRECTORY_TREE_ITEM_COUNT};
+use crate::test_directory_utils::delete_test_directory_tree;
+use rstest::rstest;
+use space_rs::{ScanError, SizeMode};
+use std::{io, path::Path, sync::atomic::AtomicBool};
+
+fn render_to_string(
+    view_state: &ViewState,
+    size_threshold_fraction: f32,
+) -> anyhow::Result<(usize, String)> {
+    let mut output = vec![];
+    let rendered_count = render_markdown(
+        view_state,
+        size_threshold_fraction,
+        &mut output,
+        &AtomicBool::new(false),
+    )?;
+    Ok((rendered_count, String::from_utf8(output)?))
+}
+
+fn list_items(markdown: &str) -> Vec<&str> {
+    markdown
+        .split("## Items\n\n")
+        .nth(1)
+        .unwrap()
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .collect()
+}
+
+#[rstest]
+fn render_markdown_writes_summary_of_roots_total_and_filters() -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+
+    // Act
+    let (_, markdown) = render_to_string(&view_state, 0f32)?;
+
+    // Assert
+    let expected = format!(
+        "# Disk usage\n\n\
+        - **Root:** `{}`\n\
+        - **Total:** 180 KB (apparent size) in {TEST_DIRECTORY_TREE_ITEM_COUNT} items\n\
+        - **Filters:** None, all items are shown\n\n\
+        ## Items\n\n",
+        temp_dir_path.display()
+    );
+    assert!(markdown.starts_with(&expected), "{markdown}");
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_markdown_writes_nested_list_of_items() -> anyhow::Result<()> {
+    // Arrange
+    let (view_state, temp_dir_path) = make_test_view_state(0f32)?;
+
+    // Act
+    let (rendered_count, markdown) = render_to_string(&view_state, 0f32)?;
+
+    // Assert
+    assert_eq!(TEST_DIRECTORY_TREE_ITEM_COUNT, rendered_count);
+    let items = list_items(&markdown);
+    assert_eq!(TEST_DIRECTORY_TREE_ITEM_COUNT, items.len());
+    assert_eq!(
+        format!("- `{}` 180 KB (100.0%)", temp_dir_path.display()),
+        items[0]
+    );
+    assert_eq!("  - `1` 180 KB (100.0%)", items[1]);
+    assert_eq!("    - `1.1` 25 KB (13.9%)", items[2]);
+    assert!(items.contains(&"    - `1.11` 0 KB (0.0%) _symbolic link_"));
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_markdown_given_size_threshold_and_regex_filter_describes_and_applies_them(
+) -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0.01f32)?;
+    view_state.set_filter_regex(Some(regex::Regex::new("1\\.5\\.3")?));
+
+    // Act
+    let (rendered_count, markdown) = render_to_string(&view_state, 0.01f32)?;
+
+    // Assert
+    assert!(markdown.contains(
+        "- **Filters:** Only showing items that are at least 1% of the total size and match regex \
+        \"1\\\\.5\\\\.3\"\n"
+    ));
+    let items = list_items(&markdown);
+    assert_eq!(rendered_count, items.len());
+    assert_eq!("      - `1.5.3` 4 KB (2.2%)", items[3]);
+    assert_eq!(5, rendered_count);
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_markdown_given_count_size_mode_writes_entry_counts() -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    view_state.size_mode = SizeMode::Count;
+
+    // Act
+    let (_, markdown) = render_to_string(&view_state, 0f32)?;
+
+    // Assert
+    assert!(markdown.contains(&format!(
+        "- **Total:** {TEST_DIRECTORY_TREE_ITEM_COUNT} entries\n"
+    )));
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+fn render_markdown_given_scan_errors_lists_them() -> anyhow::Result<()> {
+    // Arrange
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    view_state.scan_errors = vec![ScanError::new(
+        Path::new("/a/locked"),
+        &io::Error::from(io::ErrorKind::PermissionDenied),
+    )];
+
+    // Act
+    let (_, markdown) = render_to_string(&view_state, 0f32)?;
+
+    // Assert
+    assert!(markdown.contains("- **Scan errors:** 1, see [below](#scan-errors)\n"));
+    assert!(markdown.ends_with(
+        "## Scan errors\n\n\
+        The sizes of the items containing the following paths are undercounted:\n\n\
+        - `/a/locked`: permission denied\n"
+    ));
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
+#[rstest]
+#[case("plain", "`plain`")]
+#[case("a`b", "``a`b``")]
+#[case("``x", "``` ``x ```")]
+fn code_span_uses_fence_longer_than_backticks_in_text(#[case] text: &str, #[case] expected: &str) {
+    // Act
+    let span = code_span(text);
+
+    // Assert
+    assert_eq!(expected, span);
+}
+
+#[rstest]
+fn escape_markdown_escapes_inline_markup() {
+    // Act
+    let escaped = escape_markdown("a_b *c* [d] <e> #f | `g`");
+
+    // Assert
+    assert_eq!("a\\_b \\*c\\* \\[d\\] \\<e\\> \\#f \\| \\`g\\`", escaped);
+}
@@ -1,18 +1,33 @@
+mod csv_render;
+mod du_render;
+mod html_render;
+mod json_render;
+mod markdown_render;
 mod non_interactive;
 pub(crate) mod non_interactive_render;
 mod skin_selection;
 
+#[cfg(not(test))]
+pub(crate) use skin_selection::select_skin;
+
 #[cfg(not(test))]
 mod interactive;
 
-use super::{cli_command::CliCommand, environment::EnvServiceTrait, row_item::RowItem};
-use space_rs::{DirectoryItem, SizeDisplayFormat};
+use super::{
+    cli_command::CliCommand, environment::EnvServiceTrait, ncdu, output_format::OutputFormat,
+    row_item::RowItem, snapshot,
+};
+use clap::Args;
+use space_rs::{DirectoryItem, ScanError, Scanner, SizeDisplayFormat, SizeMode, SvgChart};
 use std::{
     cell::RefCell,
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
     rc::Rc,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 #[cfg(test)]
@@ -22,13 +37,83 @@ mod view_command_test;
 pub(crate) const COLORTERM_ENV_VAR: &str = "COLORTERM";
 pub(crate) const TERM_ENV_VAR: &str = "TERM";
 
+/// Where the items to view come from.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) enum ItemSource {
+    /// The target paths are scanned.
+    #[default]
+    Scan,
+    /// The items are loaded from an ncdu export rather than scanned.
+    NcduExport(PathBuf),
+    /// The items are loaded from a snapshot file, saved with `--save-snapshot`, rather than scanned.
+    Snapshot(PathBuf),
+}
+
+/// The arguments that load the items from a file rather than scanning the target paths.
+#[derive(Args, Clone, Debug, Default)]
+pub(crate) struct ItemSourceArgs {
+    /// Loads the items from an ncdu JSON export, e.g. created with `ncdu -o FILE`, rather than scanning the target
+    /// paths. The scan options do not apply, and the items cannot be rescanned or deleted.
+    #[arg(long, value_name = "FILE", conflicts_with = "target_paths")]
+    pub(crate) import_ncdu: Option<PathBuf>,
+
+    /// Loads the items from a snapshot FILE saved with `--save-snapshot`, rather than scanning the target paths. The
+    /// scan options do not apply, and the items cannot be rescanned or deleted.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["target_paths", "import_ncdu"])]
+    pub(crate) load_snapshot: Option<PathBuf>,
+}
+
+impl From<ItemSourceArgs> for ItemSource {
+    fn from(args: ItemSourceArgs) -> Self {
+        // The arguments conflict, so at most one of them is given.
+        match (args.import_ncdu, args.load_snapshot) {
+            (Some(path), _) => ItemSource::NcduExport(path),
+            (None, Some(path)) => ItemSource::Snapshot(path),
+            (None, None) => ItemSource::Scan,
+        }
+    }
+}
+
+/// The settings of the outputs other than the table, i.e. the du, SVG and snapshot outputs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct OutputSettings {
+    /// If `true` then the du output also has a line for each file, like `du -a`.
+    pub(crate) du_all_files: bool,
+    /// The kind of chart of the SVG output.
+    pub(crate) svg_chart: SvgChart,
+    /// The width and height of the SVG output in pixels.
+    pub(crate) svg_dimensions: (u32, u32),
+    /// The snapshot file to save the completed scan to, rather than viewing it, if any.
+    pub(crate) save_snapshot_path: Option<PathBuf>,
+}
+
+impl Default for OutputSettings {
+    fn default() -> Self {
+        OutputSettings {
+            du_all_files: false,
+            svg_chart: SvgChart::Treemap,
+            svg_dimensions: (1200, 800),
+            save_snapshot_path: None,
+        }
+    }
+}
+
 pub(crate) struct ViewCommand {
     target_paths: Option<Vec<PathBuf>>,
     size_display_format: Option<SizeDisplayFormat>,
+    size_mode: SizeMode,
     size_threshold_percentage: u8,
+    show_counts: bool,
+    output_format: OutputFormat,
+    output_settings: OutputSettings,
     #[cfg(not(test))]
     non_interactive: bool,
     filter_regex: Option<regex::Regex>,
+    scanner: Arc<Scanner>,
+    item_source: ItemSource,
+    /// The items that were loaded rather than scanned, if any, until they are viewed.
+    loaded_items: Option<Vec<DirectoryItem>>,
+    scan_errors: Vec<ScanError>,
     total_size_in_bytes: u64,
     env_service: Box<dyn EnvServiceTrait>,
     should_exit: Arc<AtomicBool>,
@@ -36,6 +121,17 @@ pub(crate) struct ViewCommand {
 
 impl CliCommand for ViewCommand {
     fn prepare(&mut self) -> anyhow::Result<&mut Self> {
+        let loaded = match &self.item_source {
+            ItemSource::Scan => None,
+            ItemSource::NcduExport(path) => Some(ncdu::read_ncdu_file(path)?),
+            ItemSource::Snapshot(path) => Some(snapshot::read_snapshot_file(path)?.1),
+        };
+        if let Some(result) = loaded {
+            self.loaded_items = Some(result.items);
+            self.scan_errors = result.errors;
+            return Ok(self);
+        }
+
         let has_target_paths = match &self.target_paths {
             Some(target_paths) => !target_paths.is_empty(),
             None => false,
@@ -61,16 +157,13 @@ impl CliCommand for ViewCommand {
     }
 
     fn run<W: Write>(&mut self, writer: &mut W) -> anyhow::Result<()> {
-        if let Some(target_paths) = &self.target_paths {
-            if target_paths.len() == 1 {
-                writeln!(writer, "Analyzing path {}", target_paths[0].display())?;
-            } else {
-                writeln!(writer, "Analyzing the following paths:")?;
-                target_paths.iter().try_for_each(|path| {
-                    writeln!(writer, "  - {}", path.display())?;
-                    anyhow::Ok(())
-                })?;
-            }
+        // Machine readable output must only contain the items.
+        if !self.output_format.is_machine_readable() {
+            self.write_target_paths(writer)?;
+        }
+
+        if let Some(path) = self.output_settings.save_snapshot_path.clone() {
+            return self.save_snapshot(writer, &path);
         }
 
         let size_display_format = match &self.size_display_format {
@@ -96,28 +189,90 @@ impl CliCommand for ViewCommand {
 }
 
 impl ViewCommand {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         target_paths: Option<Vec<PathBuf>>,
+        item_source: ItemSource,
         size_display_format: Option<SizeDisplayFormat>,
+        size_mode: SizeMode,
         size_threshold_percentage: u8,
+        show_counts: bool,
+        output_format: OutputFormat,
+        output_settings: OutputSettings,
         #[cfg(not(test))] non_interactive: bool,
         filter_regex: Option<regex::Regex>,
+        scanner: Arc<Scanner>,
         env_service: Box<dyn EnvServiceTrait>,
         should_exit: Arc<AtomicBool>,
     ) -> Self {
         ViewCommand {
             target_paths,
             size_display_format,
+            size_mode,
             size_threshold_percentage,
+            show_counts,
+            output_format,
+            output_settings,
             #[cfg(not(test))]
             non_interactive,
             filter_regex,
+            scanner,
+            item_source,
+            loaded_items: None,
+            scan_errors: vec![],
             total_size_in_bytes: 0,
             env_service,
             should_exit,
         }
     }
 
+    fn write_target_paths<W: Write>(&self, writer: &mut W) -> anyhow::Result<()> {
+        match &self.item_source {
+            ItemSource::Scan => {}
+            ItemSource::NcduExport(path) => {
+                writeln!(writer, "Loading ncdu export {}", path.display())?;
+                return Ok(());
+            }
+            ItemSource::Snapshot(path) => {
+                writeln!(writer, "Loading snapshot {}", path.display())?;
+                return Ok(());
+            }
+        }
+        if let Some(target_paths) = &self.target_paths {
+            if target_paths.len() == 1 {
+                writeln!(writer, "Analyzing path {}", target_paths[0].display())?;
+            } else {
+                writeln!(writer, "Analyzing the following paths:")?;
+                target_paths.iter().try_for_each(|path| {
+                    writeln!(writer, "  - {}", path.display())?;
+                    anyhow::Ok(())
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Scans the target paths, or loads the items, and saves the completed scan to the snapshot file at `path`,
+    /// along with where and when it was done.
+    fn save_snapshot<W: Write>(&mut self, writer: &mut W, path: &Path) -> anyhow::Result<()> {
+        let items = self.get_directory_items();
+        if self.should_exit.load(Ordering::Relaxed) {
+            anyhow::bail!("Cancelled.");
+        }
+
+        let header = snapshot::SnapshotHeader::new(&items, &self.scan_errors);
+        let item_count: usize = items.iter().map(|item| item.descendant_count + 1).sum();
+        snapshot::write_snapshot_file(path, &header, items)?;
+
+        non_interactive_render::render_scan_errors(&self.scan_errors, writer)?;
+        writeln!(
+            writer,
+            "Saved a snapshot of {item_count} items to {}",
+            path.display()
+        )?;
+        Ok(())
+    }
+
     #[cfg(test)]
     #[allow(dead_code)]
     pub(crate) fn target_paths(&self) -> &Option<Vec<PathBuf>> {
@@ -134,9 +289,11 @@ impl ViewCommand {
         use crossterm::tty::IsTty;
         use std::io;
         if cfg!(debug_assertions) {
-            !self.non_interactive
+            !self.non_interactive && !self.output_format.is_machine_readable()
         } else {
-            !self.non_interactive && io::stdout().is_tty()
+            !self.non_interactive
+                && !self.output_format.is_machine_readable()
+                && io::stdout().is_tty()
         }
     }
 
@@ -163,10 +320,15 @@ impl ViewCommand {
         size_threshold_fraction: f32,
         rows: &mut Vec<Rc<RefCell<RowItem>>>,
     ) -> Option<Rc<RefCell<RowItem>>> {
-        if item.get_fraction(self.total_size_in_bytes) < size_threshold_fraction {
+        if item
+            .get_size(self.size_mode)
+            .get_fraction(self.total_size_in_bytes)
+            < size_threshold_fraction
+        {
             return None;
         }
-        let value = RowItem::from_directory_item(&item, self.total_size_in_bytes, None, 0);
+        let value =
+            RowItem::from_directory_item(&item, self.size_mode, self.total_size_in_bytes, None, 0);
         // from_directory_item creates all nodes collapsed; expand the full tree
         // for the non-scanning (pre-built) view.
         Self::expand_tree(&value);
@@ -201,12 +363,28 @@ impl ViewCommand {
     }
 
     fn analyze_space(&mut self) -> Vec<DirectoryItem> {
-        let sanitized_paths = self.get_sanitized_paths();
+        let mut items = match self.loaded_items.take() {
+            Some(items) => items,
+            None => {
+                let sanitized_paths = self.get_sanitized_paths();
+                let result = self.scanner.scan(sanitized_paths, &self.should_exit);
+                self.scan_errors = result.errors;
+                result.items
+            }
+        };
 
-        let items = DirectoryItem::build(sanitized_paths, &self.should_exit);
+        // Items are sorted by apparent size when built, so re-sort for any other size mode.
+        if self.size_mode != SizeMode::Apparent {
+            items
+                .iter_mut()
+                .for_each(|item| item.sort_by_size(self.size_mode));
+        }
 
         // TODO: Do this inline
-        self.total_size_in_bytes = items.iter().map(|t| t.size_in_bytes.get_value()).sum();
+        self.total_size_in_bytes = items
+            .iter()
+            .map(|t| t.get_size(self.size_mode).get_value())
+            .sum();
 
         items
     }
@@ -1,18 +1,122 @@
-use super::{non_interactive_render, ViewCommand};
-use crate::cli::{skin::Skin, view_state::ViewState};
-use space_rs::SizeDisplayFormat;
+use super::{
+    csv_render::{self, Delimiter},
+    du_render, html_render, json_render, markdown_render, non_interactive_render, ViewCommand,
+};
+use crate::cli::{ncdu, output_format::OutputFormat, skin::Skin, view_state::ViewState};
+use space_rs::{render_svg, SizeDisplayFormat, SvgOptions};
 use std::{
     io::Write,
     sync::{atomic::AtomicBool, Arc},
 };
 
 impl ViewCommand {
+    /// Writes the items in the output format. Cancellation of the exports relies on the Ctrl+C handler rather than
+    /// raw mode, so that the output is not altered by the terminal.
     pub(super) fn run_non_interactive<W: Write>(
         &mut self,
         writer: &mut W,
         size_display_format: SizeDisplayFormat,
         size_threshold_fraction: f32,
         skin: &Skin,
+    ) -> anyhow::Result<()> {
+        match self.output_format {
+            OutputFormat::Table => {
+                return self.run_table(writer, size_display_format, size_threshold_fraction, skin);
+            }
+            OutputFormat::Ncdu => {
+                // The export is of the full tree, so the size threshold and regex filter are not applied.
+                let items = self.get_directory_items();
+                ncdu::write_ncdu(&items, &self.scan_errors, writer)?;
+            }
+            OutputFormat::Svg => {
+                // The chart is of the full tree, so the regex filter is not applied, and the size threshold only
+                // determines which items are labeled.
+                let items = self.get_directory_items();
+                let (width, height) = self.output_settings.svg_dimensions;
+                let options = SvgOptions::default()
+                    .chart(self.output_settings.svg_chart)
+                    .dimensions(width, height)
+                    .size_mode(self.size_mode)
+                    .size_display_format(size_display_format)
+                    .label_threshold_fraction(size_threshold_fraction)
+                    .palette(skin.svg_palette());
+                render_svg(&items, &options, writer)?;
+            }
+            OutputFormat::Json => {
+                let view_state =
+                    self.create_view_state(size_display_format, size_threshold_fraction, skin);
+                json_render::render_json(
+                    &view_state,
+                    size_threshold_fraction,
+                    writer,
+                    &self.should_exit,
+                )?;
+            }
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                let delimiter = if self.output_format == OutputFormat::Csv {
+                    Delimiter::Comma
+                } else {
+                    Delimiter::Tab
+                };
+                let view_state =
+                    self.create_view_state(size_display_format, size_threshold_fraction, skin);
+                csv_render::render_delimited(
+                    &view_state,
+                    size_threshold_fraction,
+                    delimiter,
+                    writer,
+                    &self.should_exit,
+                )?;
+            }
+            OutputFormat::Du => {
+                let view_state =
+                    self.create_view_state(size_display_format, size_threshold_fraction, skin);
+                du_render::render_du(
+                    &view_state,
+                    size_threshold_fraction,
+                    self.output_settings.du_all_files,
+                    writer,
+                    &self.should_exit,
+                )?;
+            }
+            OutputFormat::Html => {
+                let view_state =
+                    self.create_view_state(size_display_format, size_threshold_fraction, skin);
+                html_render::render_html(
+                    &view_state,
+                    size_threshold_fraction,
+                    writer,
+                    &self.should_exit,
+                )?;
+            }
+            OutputFormat::Markdown => {
+                let view_state =
+                    self.create_view_state(size_display_format, size_threshold_fraction, skin);
+                markdown_render::render_markdown(
+                    &view_state,
+                    size_threshold_fraction,
+                    writer,
+                    &self.should_exit,
+                )?;
+            }
+        }
+
+        writer.flush()?;
+
+        if self.should_exit.load(std::sync::atomic::Ordering::Relaxed) {
+            anyhow::bail!("Cancelled.");
+        }
+
+        Ok(())
+    }
+
+    /// Writes the table of the items, with status messages, the filters and the scan errors.
+    fn run_table<W: Write>(
+        &mut self,
+        writer: &mut W,
+        size_display_format: SizeDisplayFormat,
+        size_threshold_fraction: f32,
+        skin: &Skin,
     ) -> anyhow::Result<()> {
         writeln!(
             writer,
@@ -29,23 +133,11 @@ impl ViewCommand {
         // Spawn a background thread that polls for Ctrl+C.
         let cancel_thread = spawn_cancel_thread(self.should_exit.clone());
 
-        let items = self.get_directory_items();
-        let items = self.get_row_items(items, size_threshold_fraction);
-
-        let mut view_state = ViewState::new(
-            items,
-            size_display_format,
-            size_threshold_fraction,
-            self.filter_regex.take(),
-            skin,
-        );
-
-        // TODO: Push any error into some sort of error stream and expose in UI.
-        let _ = view_state.read_config_file();
-
-        view_state.apply_regex_filter();
+        let mut view_state =
+            self.create_view_state(size_display_format, size_threshold_fraction, skin);
 
         let filter_regex = view_state.filter_regex.clone();
+        let scan_errors = std::mem::take(&mut view_state.scan_errors);
 
         non_interactive_render::render_rows(
             view_state,
@@ -67,30 +159,51 @@ impl ViewCommand {
             anyhow::bail!("Cancelled.");
         }
 
-        let mut filter_message = String::new();
-        if size_threshold_fraction > 0f32 || filter_regex.is_some() {
-            filter_message.push_str("^ Only showing items that");
-            if size_threshold_fraction > 0f32 {
-                filter_message.push_str(&format!(
-                    " are at least {}% of the total size",
-                    size_threshold_fraction * 100.0f32,
-                ));
-            }
-            if let Some(ex) = filter_regex {
-                if !filter_message.is_empty() {
-                    filter_message.push_str(" and");
-                }
-                filter_message.push_str(&format!(" that match regex \"{}\"", ex));
-            }
-            writeln!(writer, "{}", filter_message)?;
+        if let Some(filter_message) =
+            non_interactive_render::describe_filters(size_threshold_fraction, filter_regex.as_ref())
+        {
+            writeln!(writer, "^ {filter_message}")?;
         }
 
+        non_interactive_render::render_scan_errors(&scan_errors, writer)?;
+
         writeln!(writer, "Done.")?;
 
         Ok(())
     }
 }
 
+impl ViewCommand {
+    /// Scans the target paths and returns the view state with the resulting items, filtered by the regex.
+    fn create_view_state(
+        &mut self,
+        size_display_format: SizeDisplayFormat,
+        size_threshold_fraction: f32,
+        skin: &Skin,
+    ) -> ViewState {
+        let items = self.get_directory_items();
+        let items = self.get_row_items(items, size_threshold_fraction);
+
+        let mut view_state = ViewState::new(
+            items,
+            size_display_format,
+            size_threshold_fraction,
+            self.filter_regex.take(),
+            skin,
+        );
+        view_state.size_mode = self.size_mode;
+        view_state.show_counts = self.show_counts;
+        view_state.scan_errors = std::mem::take(&mut self.scan_errors);
+
+        // TODO: Push any error into some sort of error stream and expose in UI.
+        let _ = view_state.read_config_file();
+
+        view_state.apply_regex_filter();
+
+        view_state
+    }
+}
+
 struct CancelThread {
     stop_flag: Arc<AtomicBool>,
     handle: Option<std::thread::JoinHandle<()>>,
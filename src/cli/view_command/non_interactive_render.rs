@@ -6,12 +6,12 @@ use crate::cli::{
     row_item::RowItem,
     skin::Skin,
     tui,
-    view_state::{table_rows, ViewState},
+    view_state::{table_rows, ViewState, COUNT_COLUMN_HEADERS, COUNT_COLUMN_WIDTH},
 };
 use anyhow::Context;
 use crossterm::{style::Print, QueueableCommand};
 use ratatui::prelude::*;
-use space_rs::SizeDisplayFormat;
+use space_rs::{ScanError, Size, SizeDisplayFormat, SizeMode};
 use std::{
     cell::RefCell,
     io::Write,
@@ -21,7 +21,56 @@ use std::{
 use unicode_segmentation::UnicodeSegmentation;
 
 /// How many rows to render between checks of the cancellation flag.
-const CANCEL_CHECK_INTERVAL: usize = 100;
+pub(crate) const CANCEL_CHECK_INTERVAL: usize = 100;
+/// The width of the metadata column, i.e. the modification time, owner and permissions.
+const METADATA_COLUMN_WIDTH: u16 = 42;
+
+/// Writes a summary of the errors that occurred while scanning, if any, so it is clear which parts of the
+/// tree were undercounted and why.
+pub(crate) fn render_scan_errors<W: Write>(
+    scan_errors: &[ScanError],
+    writer: &mut W,
+) -> anyhow::Result<()> {
+    if scan_errors.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        writer,
+        "! {} {} occurred while scanning, so the sizes of the items containing the following paths are \
+        undercounted:",
+        scan_errors.len(),
+        if scan_errors.len() == 1 { "error" } else { "errors" },
+    )?;
+    for error in scan_errors {
+        writeln!(writer, "  - {error}")?;
+    }
+    Ok(())
+}
+
+/// Describes which items are shown, e.g. `Only showing items that are at least 1% of the total size`, or returns
+/// `None` if all items are shown.
+pub(super) fn describe_filters(
+    size_threshold_fraction: f32,
+    filter_regex: Option<&regex::Regex>,
+) -> Option<String> {
+    if size_threshold_fraction <= 0f32 && filter_regex.is_none() {
+        return None;
+    }
+    let mut message = String::from("Only showing items that");
+    if size_threshold_fraction > 0f32 {
+        message.push_str(&format!(
+            " are at least {}% of the total size",
+            size_threshold_fraction * 100.0f32,
+        ));
+        if filter_regex.is_some() {
+            message.push_str(" and");
+        }
+    }
+    if let Some(regex) = filter_regex {
+        message.push_str(&format!(" match regex \"{regex}\""));
+    }
+    Some(message)
+}
 
 pub(crate) fn render_rows<W: Write>(
     view_state: ViewState,
@@ -36,22 +85,57 @@ pub(crate) fn render_rows<W: Write>(
 
     let width = crossterm::terminal::size().map(|(w, _)| w).unwrap_or(120);
 
+    // The metadata, if collected, is shown in an additional column after the table columns.
+    let show_metadata = view_state
+        .item_tree
+        .iter()
+        .any(|item| item.borrow().metadata.is_some());
+    let table_width = if show_metadata {
+        width.saturating_sub(METADATA_COLUMN_WIDTH + 1)
+    } else {
+        width
+    };
+
     // Exclude the expand column.  Use half the terminal width for the path
     // column since non-interactive mode doesn't have visible rows to measure.
-    let path_width = width / 2;
-    let constraints: Vec<Constraint> = tui::table_column_constraints(width, path_width)
-        .into_iter()
-        .enumerate()
-        .filter_map(
-            |(index, element)| {
-                if index != 1 {
-                    Some(element)
-                } else {
-                    None
-                }
-            },
-        )
-        .collect();
+    let path_width = table_width / 2;
+    let show_counts = view_state.show_counts;
+    let mut constraints: Vec<Constraint> =
+        tui::table_column_constraints(table_width, path_width, show_counts)
+            .into_iter()
+            .enumerate()
+            .filter_map(
+                |(index, element)| {
+                    if index != 1 {
+                        Some(element)
+                    } else {
+                        None
+                    }
+                },
+            )
+            .collect();
+    if show_metadata {
+        constraints.push(Constraint::Length(METADATA_COLUMN_WIDTH));
+    }
+
+    // The count columns cannot be told apart without a header.
+    if show_counts {
+        let mut headers = vec![
+            String::from("Size"),
+            String::from("Path"),
+            String::default(),
+            String::from("Incl"),
+        ];
+        headers.extend(
+            COUNT_COLUMN_HEADERS
+                .iter()
+                .map(|header| format!("{header:>0$}", COUNT_COLUMN_WIDTH as usize)),
+        );
+        if show_metadata {
+            headers.push(String::from("Modified"));
+        }
+        render_cells(&headers, &constraints, width, &mut backend)?;
+    }
 
     view_state
         .item_tree
@@ -63,6 +147,8 @@ pub(crate) fn render_rows<W: Write>(
                 &constraints,
                 width,
                 view_state.size_display_format,
+                view_state.size_mode,
+                show_counts,
                 &mut backend,
                 skin,
                 should_exit,
@@ -84,6 +170,8 @@ pub(crate) fn render_row<W: Write>(
     constraints: &Vec<Constraint>,
     terminal_width: u16,
     size_display_format: SizeDisplayFormat,
+    size_mode: SizeMode,
+    show_counts: bool,
     backend: &mut CrosstermBackend<W>,
     skin: &Skin,
     should_exit: &AtomicBool,
@@ -92,10 +180,7 @@ pub(crate) fn render_row<W: Write>(
     let mut rendered_count = 0;
     let item_ref = item.borrow();
 
-    if item_ref.incl_fraction < size_threshold_fraction {
-        return Ok(rendered_count);
-    }
-    if !item_ref.regex_visible {
+    if !is_rendered(&item_ref, size_threshold_fraction) {
         return Ok(rendered_count);
     }
 
@@ -108,8 +193,68 @@ pub(crate) fn render_row<W: Write>(
         }
     }
 
-    let cells = table_rows::get_row_cell_content_plain(item, size_display_format, skin, 0);
+    let mut cells = table_rows::get_row_cell_content_plain(
+        item,
+        size_display_format,
+        size_mode,
+        skin,
+        0,
+        show_counts,
+    );
+
+    if constraints.len() > cells.len() {
+        // The trailing metadata column, which is only included if the metadata was collected.
+        cells.push(item_ref.get_metadata_summary().unwrap_or_default());
+    }
+    render_cells(&cells, constraints, terminal_width, backend)?;
+
+    rendered_count += 1;
+
+    if item_ref.has_children {
+        for child in &item_ref.children {
+            rendered_count += render_row(
+                child,
+                size_threshold_fraction,
+                constraints,
+                terminal_width,
+                size_display_format,
+                size_mode,
+                show_counts,
+                backend,
+                skin,
+                should_exit,
+                rows_since_check,
+            )?;
+        }
+    }
+
+    Ok(rendered_count)
+}
+
+/// Returns `true` if the item is at least the size threshold and matches the regex filter, if any. The descendants
+/// of an item that is not rendered are not rendered either.
+pub(super) fn is_rendered(item: &RowItem, size_threshold_fraction: f32) -> bool {
+    item.incl_fraction >= size_threshold_fraction && item.regex_visible
+}
 
+/// Returns the total size of the root items. The view state only tracks the total while scanning interactively.
+pub(super) fn get_total_size(view_state: &ViewState) -> Size {
+    Size::new(
+        view_state
+            .item_tree
+            .iter()
+            .map(|item| item.borrow().size.get_value())
+            .sum(),
+    )
+}
+
+/// Writes a line with the given cells, each truncated or padded to the width of its column.
+fn render_cells<W: Write>(
+    cells: &[String],
+    constraints: &[Constraint],
+    terminal_width: u16,
+    backend: &mut CrosstermBackend<W>,
+) -> anyhow::Result<()> {
     let column_count = constraints.len();
     for col_index in 0..column_count {
         let mut max_len = constraints[col_index].apply(terminal_width) as usize;
@@ -133,24 +278,5 @@ pub(crate) fn render_row<W: Write>(
             backend.queue(Print('\n'))?;
         }
     }
-
-    rendered_count += 1;
-
-    if item_ref.has_children {
-        for child in &item_ref.children {
-            rendered_count += render_row(
-                child,
-                size_threshold_fraction,
-                constraints,
-                terminal_width,
-                size_display_format,
-                backend,
-                skin,
-                should_exit,
-                rows_since_check,
-            )?;
-        }
-    }
-
-    Ok(rendered_count)
+    Ok(())
 }
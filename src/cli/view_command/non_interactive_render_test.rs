@@ -1,15 +1,18 @@
-use super::{render_row, render_rows};
+use super::{describe_filters, render_row, render_rows, render_scan_errors};
 use crate::cli::{
     row_item::{RowItem, RowItemType},
     skin::Skin,
-    view_state_test_utils::make_test_view_state,
+    view_state_test_utils::{make_test_view_state, make_test_view_state_from_path_with_scanner},
 };
-use crate::test_directory_utils::delete_test_directory_tree;
+use crate::test_directory_utils::{create_test_directory_tree, delete_test_directory_tree};
 use crate::test_utils::TestOut;
 use ratatui::prelude::{Constraint, CrosstermBackend};
 use rstest::rstest;
-use space_rs::size::{Size, SizeDisplayFormat};
-use std::{cell::RefCell, rc::Rc};
+use space_rs::{
+    size::{Size, SizeDisplayFormat, SizeMode},
+    DescendantCounts, ScanError, ScanOptions,
+};
+use std::{cell::RefCell, io, path::Path, rc::Rc};
 
 #[test]
 fn render_row_with_size_smaller_than_threshold_does_not_output_anything() -> anyhow::Result<()> {
@@ -25,6 +28,7 @@ fn render_row_with_size_smaller_than_threshold_does_not_output_anything() -> any
     let mut backend = CrosstermBackend::new(writer);
     let item = Rc::new(RefCell::new(RowItem {
         size: Size::default(),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: false,
         tree_prefix: String::default(),
@@ -32,9 +36,12 @@ fn render_row_with_size_smaller_than_threshold_does_not_output_anything() -> any
         incl_fraction: 0.1f32,
         peer_fraction: 0.0,
         path_segment: "/some/path".to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 1,
@@ -53,6 +60,8 @@ fn render_row_with_size_smaller_than_threshold_does_not_output_anything() -> any
         &constraints,
         80,
         SizeDisplayFormat::Metric,
+        SizeMode::Apparent,
+        false,
         &mut backend,
         &Skin::default(),
         &should_exit,
@@ -108,6 +117,44 @@ fn render_rows_given_size_threshold_should_render_correct_rows(
     Ok(())
 }
 
+#[rstest]
+#[case(0, 1)]
+#[case(1, 2)]
+#[case(2, 14)]
+#[case(3, 24)]
+fn render_rows_given_max_depth_should_render_items_up_to_max_depth(
+    #[case] max_depth: usize,
+    #[case] expected_rendered_count: usize,
+) -> anyhow::Result<()> {
+    // Arrange
+    let mut output = TestOut::new();
+    let temp_dir_path = create_test_directory_tree()?;
+    let scanner = ScanOptions::default().max_depth(max_depth).build()?;
+    let view_state =
+        make_test_view_state_from_path_with_scanner(&temp_dir_path, 7, 0, 0f32, scanner)?;
+
+    // Act
+    let should_exit = std::sync::atomic::AtomicBool::new(false);
+    let rendered_count = render_rows(
+        view_state,
+        0f32,
+        &mut output,
+        &Skin::default(),
+        &should_exit,
+    )?;
+
+    // Assert
+    if expected_rendered_count != rendered_count {
+        println!("{}", output);
+    }
+    assert_eq!(expected_rendered_count, rendered_count);
+    output.expect("180 KB")?;
+
+    delete_test_directory_tree(&temp_dir_path);
+
+    Ok(())
+}
+
 #[test]
 fn render_row_with_regex_hidden_item_does_not_render() -> anyhow::Result<()> {
     // Arrange - item passes size threshold but is hidden by regex filter
@@ -122,6 +169,7 @@ fn render_row_with_regex_hidden_item_does_not_render() -> anyhow::Result<()> {
     let mut backend = CrosstermBackend::new(writer);
     let item = Rc::new(RefCell::new(RowItem {
         size: Size::default(),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: false,
         tree_prefix: String::default(),
@@ -129,9 +177,12 @@ fn render_row_with_regex_hidden_item_does_not_render() -> anyhow::Result<()> {
         incl_fraction: 1.0f32,
         peer_fraction: 0.0,
         path_segment: "/some/path".to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -150,6 +201,8 @@ fn render_row_with_regex_hidden_item_does_not_render() -> anyhow::Result<()> {
         &constraints,
         80,
         SizeDisplayFormat::Metric,
+        SizeMode::Apparent,
+        false,
         &mut backend,
         &Skin::default(),
         &should_exit,
@@ -161,3 +214,76 @@ fn render_row_with_regex_hidden_item_does_not_render() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn render_scan_errors_given_no_errors_does_not_output_anything() -> anyhow::Result<()> {
+    // Arrange
+    let mut writer = TestOut::new();
+
+    // Act
+    render_scan_errors(&[], &mut writer)?;
+
+    // Assert
+    assert_eq!("", writer.as_string());
+
+    Ok(())
+}
+
+#[test]
+fn render_scan_errors_given_errors_outputs_count_and_each_path() -> anyhow::Result<()> {
+    // Arrange
+    let mut writer = TestOut::new();
+    let errors = vec![
+        ScanError::new(
+            Path::new("/a/locked"),
+            &io::Error::from(io::ErrorKind::PermissionDenied),
+        ),
+        ScanError::new(
+            Path::new("/a/gone"),
+            &io::Error::from(io::ErrorKind::NotFound),
+        ),
+    ];
+
+    // Act
+    render_scan_errors(&errors, &mut writer)?;
+
+    // Assert
+    writer.expect("2 errors occurred while scanning")?;
+    writer.expect("  - /a/locked: permission denied")?;
+    writer.expect("  - /a/gone: entity not found")?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case(0f32, None, None)]
+#[case(
+    0.01f32,
+    None,
+    Some("Only showing items that are at least 1% of the total size")
+)]
+#[case(
+    0f32,
+    Some("\\.rs$"),
+    Some("Only showing items that match regex \"\\.rs$\"")
+)]
+#[case(
+    0.05f32,
+    Some("log"),
+    Some("Only showing items that are at least 5% of the total size and match regex \"log\"")
+)]
+fn describe_filters_describes_size_threshold_and_regex(
+    #[case] size_threshold_fraction: f32,
+    #[case] filter_regex: Option<&str>,
+    #[case] expected: Option<&str>,
+) -> anyhow::Result<()> {
+    // Arrange
+    let filter_regex = filter_regex.map(regex::Regex::new).transpose()?;
+
+    // Act
+    let description = describe_filters(size_threshold_fraction, filter_regex.as_ref());
+
+    // Assert
+    assert_eq!(expected, description.as_deref());
+    Ok(())
+}
@@ -1,6 +1,11 @@
-use crate::cli::{environment::MockEnvServiceTrait, view_command::ViewCommand};
+use crate::cli::{
+    environment::MockEnvServiceTrait,
+    output_format::OutputFormat,
+    view_command::{ItemSource, OutputSettings, ViewCommand},
+};
 use mockall::predicate::eq;
 use rstest::rstest;
+use space_rs::{Scanner, SizeMode};
 use std::{
     env::VarError,
     sync::{atomic::AtomicBool, Arc},
@@ -80,8 +85,16 @@ fn get_color_count_return_expected_color_count(
     let view_command = ViewCommand {
         target_paths: None,
         size_display_format: None,
+        size_mode: SizeMode::Apparent,
         size_threshold_percentage: 1,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
         filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
         total_size_in_bytes: 0,
         env_service: Box::new(env_service_mock),
         should_exit,
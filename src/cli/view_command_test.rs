@@ -1,12 +1,15 @@
 use crate::cli::{
-    cli_command::CliCommand, environment::MockEnvServiceTrait, view_command::ViewCommand,
+    cli_command::CliCommand,
+    environment::MockEnvServiceTrait,
+    output_format::OutputFormat,
+    view_command::{ItemSource, OutputSettings, ViewCommand},
 };
 use crate::test_directory_utils::{create_test_directory_tree, delete_test_directory_tree};
 use crate::test_utils::{env_service_mock_without_env_vars, TestOut};
 use rstest::rstest;
 use space_rs::{
-    size::{Size, SizeDisplayFormat},
-    DirectoryItem, DirectoryItemType,
+    size::{Size, SizeDisplayFormat, SizeMode},
+    DescendantCounts, DirectoryItem, DirectoryItemType, Scanner, SvgChart,
 };
 use std::sync::{
     atomic::{AtomicBool, Ordering},
@@ -22,9 +25,17 @@ fn single_target_path_that_does_not_exist_should_fail() -> anyhow::Result<()> {
     let mut view_command = ViewCommand {
         target_paths: Some(vec![std::env::temp_dir().join(Uuid::new_v4().to_string())]),
         size_display_format: None,
+        size_mode: SizeMode::Apparent,
         size_threshold_percentage: 1,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
         total_size_in_bytes: 0,
         filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
         env_service: Box::new(env_service_mock),
         should_exit,
     };
@@ -47,18 +58,33 @@ fn add_row_item_given_item_of_size_below_threshold_does_not_add_item() {
     let view_command = ViewCommand {
         target_paths: None,
         size_display_format: None,
+        size_mode: SizeMode::Apparent,
         size_threshold_percentage: 1,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
         total_size_in_bytes: 1000000,
         filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
         env_service: Box::new(env_service_mock),
         should_exit,
     };
     let item = DirectoryItem {
         path_segment: "/some/path".to_string(),
         size_in_bytes: Size::default(),
+        allocated_size_in_bytes: Size::default(),
         children: vec![],
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         item_type: DirectoryItemType::Unknown,
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     let mut rows = vec![];
 
@@ -82,9 +108,17 @@ fn analyze_space_given_no_target_paths_traces_current_dir() -> anyhow::Result<()
     let mut view_command = ViewCommand {
         target_paths: None,
         size_display_format: None,
+        size_mode: SizeMode::Apparent,
         size_threshold_percentage: 1,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
         total_size_in_bytes: 0,
         filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
         env_service: Box::new(env_service_mock),
         should_exit,
     };
@@ -121,9 +155,17 @@ fn run_given_no_size_display_format_defaults_to_metric() -> anyhow::Result<()> {
     let mut view_command = ViewCommand {
         target_paths: Some(vec![temp_dir.clone()]),
         size_display_format: None,
+        size_mode: SizeMode::Apparent,
         size_threshold_percentage: 1,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
         total_size_in_bytes: 0,
         filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
         env_service: Box::new(env_service_mock),
         should_exit,
     };
@@ -156,9 +198,17 @@ fn run_with_size_display_format_uses_that_format(
     let mut view_command = ViewCommand {
         target_paths: Some(vec![temp_dir.clone()]),
         size_display_format: Some(size_display_format),
+        size_mode: SizeMode::Apparent,
         size_threshold_percentage: 1,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
         total_size_in_bytes: 0,
         filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
         env_service: Box::new(env_service_mock),
         should_exit,
     };
@@ -185,9 +235,17 @@ fn run_given_single_target_path_shows_expected_duration_prompt() -> anyhow::Resu
     let mut view_command = ViewCommand {
         target_paths: Some(vec![temp_dir.clone()]),
         size_display_format: None,
+        size_mode: SizeMode::Apparent,
         size_threshold_percentage: 100,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
         total_size_in_bytes: 0,
         filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
         env_service: Box::new(env_service_mock),
         should_exit,
     };
@@ -215,9 +273,17 @@ fn run_given_multiple_target_paths_shows_expected_duration_prompt() -> anyhow::R
     let mut view_command = ViewCommand {
         target_paths: Some(vec![temp_dir1.clone(), temp_dir2.clone()]),
         size_display_format: None,
+        size_mode: SizeMode::Apparent,
         size_threshold_percentage: 100,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
         total_size_in_bytes: 0,
         filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
         env_service: Box::new(env_service_mock),
         should_exit,
     };
@@ -247,9 +313,17 @@ fn run_with_should_exit_equal_to_true_exits() -> anyhow::Result<()> {
     let mut view_command = ViewCommand {
         target_paths: Some(vec![temp_dir.clone()]),
         size_display_format: None,
+        size_mode: SizeMode::Apparent,
         size_threshold_percentage: 100,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
         total_size_in_bytes: 0,
         filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
         env_service: Box::new(env_service_mock),
         should_exit: should_exit.clone(),
     };
@@ -265,3 +339,257 @@ fn run_with_should_exit_equal_to_true_exits() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn analyze_space_given_allocated_size_mode_totals_allocated_size() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let should_exit = Arc::new(AtomicBool::new(false));
+    let mut view_command = ViewCommand {
+        target_paths: Some(vec![temp_dir.clone()]),
+        size_display_format: None,
+        size_mode: SizeMode::Allocated,
+        size_threshold_percentage: 1,
+        show_counts: false,
+        output_format: OutputFormat::Table,
+        output_settings: OutputSettings::default(),
+        total_size_in_bytes: 0,
+        filter_regex: None,
+        scanner: Arc::new(Scanner::default()),
+        item_source: ItemSource::Scan,
+        loaded_items: None,
+        scan_errors: vec![],
+        env_service: Box::new(MockEnvServiceTrait::new()),
+        should_exit,
+    };
+
+    // Act
+    let items = view_command.analyze_space();
+    let rows = view_command.get_row_items(items, 0f32);
+
+    // Assert
+    let root = rows[0].borrow();
+    assert_eq!(view_command.total_size_in_bytes, root.size.get_value());
+    let child_sizes: Vec<u64> = root.children[0]
+        .borrow()
+        .children
+        .iter()
+        .map(|c| c.borrow().size.get_value())
+        .collect();
+    let mut sorted_child_sizes = child_sizes.clone();
+    sorted_child_sizes.sort_by(|a, b| b.cmp(a));
+    assert_eq!(sorted_child_sizes, child_sizes);
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[test]
+fn prepare_given_ncdu_export_loads_items_rather_than_scanning() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let export_path = temp_dir.join("export.json");
+    std::fs::write(
+        &export_path,
+        r#"[1, 2, {}, [{"name": "/srv"}, {"name": "a", "asize": 10}, [{"name": "b", "read_error": true}]]]"#,
+    )?;
+    let mut view_command = ViewCommand::new(
+        None,
+        ItemSource::NcduExport(export_path),
+        None,
+        SizeMode::Apparent,
+        0,
+        false,
+        OutputFormat::Table,
+        OutputSettings::default(),
+        None,
+        Arc::new(Scanner::default()),
+        Box::new(MockEnvServiceTrait::new()),
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    // Act
+    view_command.prepare()?;
+    let items = view_command.analyze_space();
+
+    // Assert
+    assert_eq!(1, items.len());
+    assert_eq!("/srv", items[0].path_segment);
+    assert_eq!(2, items[0].descendant_count);
+    assert_eq!(10, view_command.total_size_in_bytes);
+    assert_eq!(1, view_command.scan_errors.len());
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[test]
+fn prepare_given_missing_ncdu_export_fails() {
+    // Arrange
+    let export_path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    let mut view_command = ViewCommand::new(
+        None,
+        ItemSource::NcduExport(export_path),
+        None,
+        SizeMode::Apparent,
+        0,
+        false,
+        OutputFormat::Table,
+        OutputSettings::default(),
+        None,
+        Arc::new(Scanner::default()),
+        Box::new(MockEnvServiceTrait::new()),
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    // Act
+    let result = view_command.prepare();
+
+    // Assert
+    assert!(result
+        .err()
+        .unwrap()
+        .to_string()
+        .contains("Could not open the ncdu export"));
+}
+
+#[test]
+fn run_given_ncdu_output_writes_full_tree_as_ncdu_export() -> anyhow::Result<()> {
+    // Arrange
+    let mut output = TestOut::new();
+    let temp_dir = create_test_directory_tree()?;
+    let mut view_command = ViewCommand::new(
+        Some(vec![temp_dir.clone()]),
+        ItemSource::Scan,
+        None,
+        SizeMode::Apparent,
+        100,
+        false,
+        OutputFormat::Ncdu,
+        OutputSettings::default(),
+        None,
+        Arc::new(Scanner::default()),
+        Box::new(env_service_mock_without_env_vars()),
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    // Act
+    view_command.prepare()?;
+    view_command.run(&mut output)?;
+
+    // Assert
+    let result = crate::cli::ncdu::read_ncdu(output.as_string().as_bytes())?;
+    assert_eq!(temp_dir.to_string_lossy(), result.items[0].path_segment);
+    // The size threshold is not applied to the export.
+    assert_eq!(28, result.items[0].descendant_count);
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[test]
+fn run_given_save_snapshot_saves_full_tree_that_loads_without_scanning() -> anyhow::Result<()> {
+    // Arrange
+    let mut output = TestOut::new();
+    let temp_dir = create_test_directory_tree()?;
+    let snapshot_path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    let mut view_command = ViewCommand::new(
+        Some(vec![temp_dir.clone()]),
+        ItemSource::Scan,
+        None,
+        SizeMode::Apparent,
+        100,
+        false,
+        OutputFormat::Table,
+        OutputSettings {
+            save_snapshot_path: Some(snapshot_path.clone()),
+            ..Default::default()
+        },
+        None,
+        Arc::new(Scanner::default()),
+        Box::new(env_service_mock_without_env_vars()),
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    // Act
+    view_command.prepare()?;
+    view_command.run(&mut output)?;
+    delete_test_directory_tree(&temp_dir);
+    let mut load_command = ViewCommand::new(
+        None,
+        ItemSource::Snapshot(snapshot_path.clone()),
+        None,
+        SizeMode::Apparent,
+        0,
+        false,
+        OutputFormat::Table,
+        OutputSettings::default(),
+        None,
+        Arc::new(Scanner::default()),
+        Box::new(MockEnvServiceTrait::new()),
+        Arc::new(AtomicBool::new(false)),
+    );
+    load_command.prepare()?;
+    let items = load_command.analyze_space();
+
+    // Assert
+    assert!(output.as_string().contains(&format!(
+        "Saved a snapshot of 29 items to {}",
+        snapshot_path.display()
+    )));
+    assert_eq!(1, items.len());
+    assert_eq!(temp_dir.to_string_lossy(), items[0].path_segment);
+    // The size threshold is not applied to the snapshot.
+    assert_eq!(28, items[0].descendant_count);
+    assert_eq!(180000, load_command.total_size_in_bytes);
+
+    std::fs::remove_file(&snapshot_path)?;
+
+    Ok(())
+}
+
+#[rstest]
+fn run_given_svg_output_draws_full_tree_and_labels_items_from_threshold() -> anyhow::Result<()> {
+    // Arrange
+    let mut output = TestOut::new();
+    let temp_dir = create_test_directory_tree()?;
+    let mut view_command = ViewCommand::new(
+        Some(vec![temp_dir.clone()]),
+        ItemSource::Scan,
+        None,
+        SizeMode::Apparent,
+        10,
+        false,
+        OutputFormat::Svg,
+        OutputSettings {
+            svg_chart: SvgChart::Icicle,
+            svg_dimensions: (3600, 600),
+            ..Default::default()
+        },
+        Some(regex::Regex::new("no match")?),
+        Arc::new(Scanner::default()),
+        Box::new(env_service_mock_without_env_vars()),
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    // Act
+    view_command.prepare()?;
+    view_command.run(&mut output)?;
+
+    // Assert
+    let svg = output.as_string();
+    assert!(
+        svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"3600\" height=\"600\"")
+    );
+    // The regex filter is not applied to the chart, but the items without a size, e.g. the symbolic links, are not
+    // drawn.
+    assert_eq!(25, svg.matches("<title>").count());
+    assert_eq!(8, svg.matches("<text ").count());
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
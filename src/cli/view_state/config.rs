@@ -7,8 +7,25 @@ use anyhow::bail;
 use std::{
     fs::File,
     io::{Read, Write},
+    path::{Path, PathBuf},
 };
 
+impl Config {
+    /// Returns the path of the config file for the user with the given home directory.
+    pub(crate) fn get_file_path(user_home_dir: &Path) -> PathBuf {
+        user_home_dir.join(".space").join(CONFIG_FILE_NAME)
+    }
+
+    /// Reads the config file at `file_path`.
+    pub(crate) fn read(file_path: &Path) -> anyhow::Result<Config> {
+        let mut file = File::open(file_path)?;
+        let mut yaml = String::new();
+        file.read_to_string(&mut yaml)?;
+
+        Ok(serde_yaml::from_str(&yaml)?)
+    }
+}
+
 impl ViewState {
     pub(crate) fn accept_license_terms(&mut self) {
         self.accepted_license_terms = true;
@@ -24,11 +41,7 @@ impl ViewState {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("The default config file path was not set!"))?;
 
-        let mut file = File::open(file_path)?;
-        let mut yaml = String::new();
-        file.read_to_string(&mut yaml)?;
-
-        let config: Config = serde_yaml::from_str(&yaml)?;
+        let config = Config::read(file_path)?;
 
         self.accepted_license_terms = config.accepted_license_terms;
 
@@ -43,8 +56,10 @@ impl ViewState {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("The default config file path was not set!"))?;
 
+        // Keep the settings that are not part of the view state, e.g. the exclude patterns.
         let config = Config {
             accepted_license_terms: self.accepted_license_terms,
+            ..Config::read(file_path).unwrap_or_default()
         };
 
         let yaml = serde_yaml::to_string(&config)?;
@@ -61,7 +76,7 @@ impl ViewState {
         }
 
         if let Some(user_home_dir) = dirs::home_dir() {
-            self.config_file_path = Some(user_home_dir.join(".space").join(CONFIG_FILE_NAME));
+            self.config_file_path = Some(Config::get_file_path(&user_home_dir));
             Ok(())
         } else {
             bail!("Could not determine the home directory in order to write the config file.");
@@ -1,4 +1,4 @@
-use crate::cli::view_state::ViewState;
+use crate::cli::view_state::{Config, ViewState};
 
 #[test]
 fn read_and_write_config_file_succeeds() -> anyhow::Result<()> {
@@ -44,3 +44,46 @@ fn accept_license_terms_updates_view_state_and_writes_config_file() -> anyhow::R
 
     Ok(())
 }
+
+#[test]
+fn read_config_with_exclude_lists_succeeds() -> anyhow::Result<()> {
+    // Arrange
+    let file_path = std::env::temp_dir().join(format!("space_test_{}", uuid::Uuid::new_v4()));
+    std::fs::write(
+        &file_path,
+        "exclude:\n  - .snapshot\n  - /proc\nexclude_regex:\n  - '\\.tmp$'\n",
+    )?;
+
+    // Act
+    let config = Config::read(&file_path)?;
+
+    // Assert
+    assert!(!config.accepted_license_terms);
+    assert_eq!(vec![".snapshot", "/proc"], config.exclude);
+    assert_eq!(vec![r"\.tmp$"], config.exclude_regex);
+
+    std::fs::remove_file(&file_path)?;
+    Ok(())
+}
+
+#[test]
+fn write_config_file_keeps_exclude_lists() -> anyhow::Result<()> {
+    // Arrange
+    let file_path = std::env::temp_dir().join(format!("space_test_{}", uuid::Uuid::new_v4()));
+    std::fs::write(&file_path, "exclude:\n  - .snapshot\n")?;
+    let mut view_state = ViewState {
+        config_file_path: Some(file_path.clone()),
+        ..Default::default()
+    };
+
+    // Act
+    view_state.accept_license_terms();
+
+    // Assert
+    let config = Config::read(&file_path)?;
+    assert!(config.accepted_license_terms);
+    assert_eq!(vec![".snapshot"], config.exclude);
+
+    std::fs::remove_file(&file_path)?;
+    Ok(())
+}
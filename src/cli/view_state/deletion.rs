@@ -5,6 +5,7 @@ mod deletion_test;
 use super::{DeletionResult, DeletionState, ViewState};
 use crate::cli::row_item::RowItem;
 use log::error;
+use space_rs::DescendantCounts;
 #[cfg(not(test))]
 use std::thread;
 use std::{
@@ -24,7 +25,8 @@ impl ViewState {
             return;
         };
         let path = selected_item.borrow().get_path();
-        let is_dir = path.is_dir();
+        // A followed link is removed on its own, without deleting the contents of its target.
+        let is_dir = !path.is_symlink() && path.is_dir();
 
         let cancel_flag = Arc::new(AtomicBool::new(false));
         let (sender, receiver) = crossfire::mpsc::unbounded_blocking();
@@ -38,7 +40,7 @@ impl ViewState {
                     Err(e) => DeletionResult::Error(e.to_string()),
                 }
             } else {
-                match fs::remove_file(&path) {
+                match remove_file_or_link(&path) {
                     Ok(()) => DeletionResult::Success,
                     Err(e) => DeletionResult::Error(e.to_string()),
                 }
@@ -116,10 +118,10 @@ impl ViewState {
             let selected_item_ref = selected_item.borrow();
             let path = selected_item_ref.get_path();
 
-            let remove_result = if path.is_dir() {
+            let remove_result = if !path.is_symlink() && path.is_dir() {
                 fs::remove_dir_all(&path)
             } else {
-                fs::remove_file(&path)
+                remove_file_or_link(&path)
             };
             match remove_result {
                 Ok(()) => {
@@ -166,7 +168,13 @@ impl ViewState {
                 self.visible_rows_dirty = true;
 
                 // Lastly remove the size of the deleted item from self and ancestors.
-                subtract_item_tree_size(&parent, selected_item.borrow().size.get_value());
+                let selected_item_ref = selected_item.borrow();
+                subtract_item_tree_size(
+                    &parent,
+                    selected_item_ref.size.get_value(),
+                    selected_item_ref.ignored_size.get_value(),
+                    &selected_item_ref.get_counts_including_self(),
+                );
             }
         }
     }
@@ -201,14 +209,7 @@ fn remove_dir_all_cancellable(path: &Path, cancel_flag: &AtomicBool) -> io::Resu
 
         if file_type.is_symlink() {
             // Remove the symlink itself, not its target contents.
-            // On Windows, directory symlinks must be removed with remove_dir
-            // while file symlinks use remove_file.  Since the target may
-            // already be deleted (broken symlink), we try remove_dir first
-            // and fall back to remove_file.
-            let entry_path = entry.path();
-            if fs::remove_dir(&entry_path).is_err() {
-                fs::remove_file(&entry_path)?;
-            }
+            remove_file_or_link(&entry.path())?;
         } else if file_type.is_dir() {
             if remove_dir_all_cancellable(&entry.path(), cancel_flag)? {
                 return Ok(true);
@@ -226,14 +227,34 @@ fn remove_dir_all_cancellable(path: &Path, cancel_flag: &AtomicBool) -> io::Resu
     Ok(false)
 }
 
-pub(crate) fn subtract_item_tree_size(item: &RefCell<RowItem>, size: u64) {
+/// Removes the file or symlink at `path`. A symlink is removed itself, not its target contents.
+/// On Windows, directory symlinks must be removed with remove_dir while file symlinks use
+/// remove_file.  Since the target may already be deleted (broken symlink), we try remove_dir
+/// first and fall back to remove_file.
+fn remove_file_or_link(path: &Path) -> io::Result<()> {
+    if path.is_symlink() && fs::remove_dir(path).is_ok() {
+        return Ok(());
+    }
+    fs::remove_file(path)
+}
+
+/// Subtracts the size and counts of a removed descendant from `item` and its ancestors.
+pub(crate) fn subtract_item_tree_size(
+    item: &RefCell<RowItem>,
+    size: u64,
+    ignored_size: u64,
+    counts: &DescendantCounts,
+) {
     let mut item_ref = item.borrow_mut();
     item_ref.size.subtract(size);
+    item_ref.ignored_size.subtract(ignored_size);
+    item_ref.descendant_count = item_ref.descendant_count.saturating_sub(counts.total());
+    item_ref.descendant_counts.subtract(counts);
 
     if let Some(parent) = &item_ref.parent {
         if let Some(parent) = parent.upgrade() {
             let parent = parent.as_ref();
-            subtract_item_tree_size(parent, size);
+            subtract_item_tree_size(parent, size, ignored_size, counts);
         }
     }
 }
@@ -6,6 +6,7 @@ use crate::cli::view_state_test_utils::{
 };
 use crate::test_directory_utils::delete_test_directory_tree;
 use rstest::rstest;
+use space_rs::DescendantCounts;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
@@ -16,7 +17,12 @@ fn subtract_item_tree_size_subtracts_value_from_self_and_ancestors() -> anyhow::
     let (view_state, temp_dir_path) = make_test_view_state_with_height(10, 0, 0f32)?;
 
     // Act
-    subtract_item_tree_size(&view_state.visible_row_items[4], value_to_subtract);
+    subtract_item_tree_size(
+        &view_state.visible_row_items[4],
+        value_to_subtract,
+        0,
+        &DescendantCounts::default(),
+    );
 
     // Assert
     assert_eq!(
@@ -138,6 +144,33 @@ fn start_async_deletion_and_check_complete_deletes_directory() -> anyhow::Result
     Ok(())
 }
 
+#[test]
+fn start_async_deletion_and_check_complete_deletes_link_but_not_its_target() -> anyhow::Result<()> {
+    let (mut view_state, temp_dir_path) = make_test_view_state(0f32)?;
+    // "1.11" is a symbolic link to the "1.5.3" directory, which contains files.
+    select_item_by_name("1.11", &mut view_state)?;
+    let selected_path = view_state.get_selected_item().unwrap().borrow().get_path();
+    let target_path = std::fs::canonicalize(&selected_path)?;
+    let target_entry_count = std::fs::read_dir(&target_path)?.count();
+    assert!(target_entry_count > 0, "Link target should not be empty");
+
+    view_state.start_async_deletion();
+    view_state.check_deletion_complete();
+
+    assert!(
+        !selected_path.is_symlink(),
+        "Link should be gone after async deletion completes"
+    );
+    assert_eq!(
+        target_entry_count,
+        std::fs::read_dir(&target_path)?.count(),
+        "Link target contents should not be deleted"
+    );
+
+    delete_test_directory_tree(&temp_dir_path);
+    Ok(())
+}
+
 #[test]
 fn cancel_deletion_sets_cancelling_state_and_sets_flag() {
     let cancel_flag = Arc::new(AtomicBool::new(false));
@@ -11,7 +11,7 @@ mod visible_rows;
 
 use super::{row_item::RowItem, skin::Skin};
 use serde::{Deserialize, Serialize};
-use space_rs::SizeDisplayFormat;
+use space_rs::{ScanError, Scanner, SizeDisplayFormat, SizeMode};
 use std::{
     cell::RefCell,
     path::PathBuf,
@@ -62,10 +62,21 @@ const CONFIG_FILE_NAME: &str = "config.yaml";
 pub(crate) const APPARENT_SIZE_COLUMN_WIDTH: u16 = 7;
 pub(crate) const EXPAND_INDICATOR_COLUMN_WIDTH: u16 = 1;
 pub(crate) const INCL_PERCENTAGE_COLUMN_WIDTH: u16 = 4;
+pub(crate) const COUNT_COLUMN_WIDTH: u16 = 7;
+/// The headers of the file, directory, symlink and other count columns, in order.
+pub(crate) const COUNT_COLUMN_HEADERS: [&str; 4] = ["Files", "Dirs", "Links", "Other"];
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-struct Config {
-    accepted_license_terms: bool,
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Config {
+    pub(crate) accepted_license_terms: bool,
+    /// The glob patterns of the items to exclude from every scan, in addition to any `--exclude` arguments.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) exclude: Vec<String>,
+    /// The regular expressions of the items to exclude from every scan, in addition to any `--exclude-regex`
+    /// arguments.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) exclude_regex: Vec<String>,
 }
 
 pub(crate) struct ViewState {
@@ -73,6 +84,7 @@ pub(crate) struct ViewState {
     pub item_tree: Vec<Rc<RefCell<RowItem>>>,
     pub total_items_in_tree: usize,
     pub size_display_format: SizeDisplayFormat,
+    pub size_mode: SizeMode,
     pub size_threshold_fraction: f32,
     pub visible_height: usize,
     pub visible_offset: usize,
@@ -85,11 +97,17 @@ pub(crate) struct ViewState {
     pub visible_row_items: Vec<Rc<RefCell<RowItem>>>,
     pub show_help: bool,
     pub show_delete_dialog: bool,
+    pub show_scan_errors: bool,
+    pub show_details: bool,
+    /// Whether to show the file, directory, symlink and other count columns.
+    pub show_counts: bool,
     pub accepted_license_terms: bool,
     pub table_width: u16,
     pub config_file_path: Option<PathBuf>,
     pub skin: Skin,
     pub is_scanning: bool,
+    /// If `true` then the items were loaded from a file rather than scanned, so they cannot be rescanned or deleted.
+    pub is_read_only: bool,
     pub total_size_in_bytes: u64,
     pub visible_rows_dirty: bool,
     pub auto_expand_done: bool,
@@ -99,6 +117,9 @@ pub(crate) struct ViewState {
     pub deletion_cancel_flag: Option<Arc<AtomicBool>>,
     pub deletion_receiver: Option<crossfire::Rx<crossfire::mpsc::List<DeletionResult>>>,
     pub rescan_request: Option<(PathBuf, Vec<String>)>,
+    pub scanner: Arc<Scanner>,
+    /// The errors that occurred while scanning, i.e. the reasons why some sizes may be undercounted.
+    pub scan_errors: Vec<ScanError>,
     pub filter_regex: Option<regex::Regex>,
     pub is_filter_input_active: bool,
     pub filter_input_buffer: String,
@@ -112,6 +133,7 @@ impl Default for ViewState {
             item_tree: vec![],
             total_items_in_tree: 0,
             size_display_format: SizeDisplayFormat::Metric,
+            size_mode: SizeMode::Apparent,
             size_threshold_fraction: 0f32,
             visible_height: 0,
             visible_offset: 0,
@@ -119,11 +141,15 @@ impl Default for ViewState {
             visible_row_items: vec![],
             show_help: false,
             show_delete_dialog: false,
+            show_scan_errors: false,
+            show_details: false,
+            show_counts: false,
             accepted_license_terms: false,
             table_width: 0,
             config_file_path: None,
             skin: Skin::default(),
             is_scanning: false,
+            is_read_only: false,
             total_size_in_bytes: 0,
             visible_rows_dirty: true,
             auto_expand_done: false,
@@ -133,6 +159,8 @@ impl Default for ViewState {
             deletion_cancel_flag: None,
             deletion_receiver: None,
             rescan_request: None,
+            scanner: Arc::new(Scanner::default()),
+            scan_errors: vec![],
             filter_regex: None,
             is_filter_input_active: false,
             filter_input_buffer: String::new(),
@@ -2,12 +2,13 @@ use crate::cli::row_item::{RowItem, RowItemType};
 use crate::cli::skin::Skin;
 use crate::cli::view_state::ViewState;
 use regex::RegexBuilder;
-use space_rs::{Size, SizeDisplayFormat};
+use space_rs::{DescendantCounts, Size, SizeDisplayFormat, SizeMode};
 use std::{cell::RefCell, rc::Rc};
 
 fn make_item(name: &str, fraction: f32) -> Rc<RefCell<RowItem>> {
     Rc::new(RefCell::new(RowItem {
         size: Size::new(1000),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: true,
         tree_prefix: String::default(),
@@ -15,9 +16,12 @@ fn make_item(name: &str, fraction: f32) -> Rc<RefCell<RowItem>> {
         incl_fraction: fraction,
         peer_fraction: 0.0,
         path_segment: name.to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -35,6 +39,7 @@ fn make_dir(
 ) -> Rc<RefCell<RowItem>> {
     let item = Rc::new(RefCell::new(RowItem {
         size: Size::new(5000),
+        ignored_size: Size::default(),
         has_children: !children.is_empty(),
         expanded: true,
         tree_prefix: String::default(),
@@ -42,9 +47,12 @@ fn make_dir(
         incl_fraction: fraction,
         peer_fraction: 0.0,
         path_segment: name.to_string(),
+        link_target: None,
+        metadata: None,
         children: children.clone(),
         parent: None,
         descendant_count: children.len(),
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -102,6 +110,7 @@ fn add_table_row_skips_regex_hidden_items() {
         &mut row_items,
         visible_item.clone(),
         SizeDisplayFormat::Metric,
+        SizeMode::Apparent,
         0.0,
         0,
         100,
@@ -112,12 +121,14 @@ fn add_table_row_skips_regex_hidden_items() {
         0,
         0,
         0,
+        false,
     );
     add_table_row(
         &mut rows,
         &mut row_items,
         hidden_item.clone(),
         SizeDisplayFormat::Metric,
+        SizeMode::Apparent,
         0.0,
         0,
         100,
@@ -128,6 +139,7 @@ fn add_table_row_skips_regex_hidden_items() {
         0,
         0,
         0,
+        false,
     );
 
     assert_eq!(
@@ -2,7 +2,7 @@ use crate::cli::row_item::{RowItem, RowItemType};
 use crate::cli::skin::Skin;
 use crate::cli::view_state::ViewState;
 use regex::RegexBuilder;
-use space_rs::{Size, SizeDisplayFormat};
+use space_rs::{DescendantCounts, Size, SizeDisplayFormat};
 use std::{cell::RefCell, rc::Rc};
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
@@ -10,6 +10,7 @@ use std::{cell::RefCell, rc::Rc};
 fn make_item(name: &str, fraction: f32) -> Rc<RefCell<RowItem>> {
     Rc::new(RefCell::new(RowItem {
         size: Size::new(1000),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: true,
         tree_prefix: String::default(),
@@ -17,9 +18,12 @@ fn make_item(name: &str, fraction: f32) -> Rc<RefCell<RowItem>> {
         incl_fraction: fraction,
         peer_fraction: 0.0,
         path_segment: name.to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -37,6 +41,7 @@ fn make_dir(
 ) -> Rc<RefCell<RowItem>> {
     let item = Rc::new(RefCell::new(RowItem {
         size: Size::new(5000),
+        ignored_size: Size::default(),
         has_children: !children.is_empty(),
         expanded: true,
         tree_prefix: String::default(),
@@ -44,9 +49,12 @@ fn make_dir(
         incl_fraction: fraction,
         peer_fraction: 0.0,
         path_segment: name.to_string(),
+        link_target: None,
+        metadata: None,
         children: children.clone(),
         parent: None,
         descendant_count: children.len(),
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -1,8 +1,12 @@
 use super::ViewState;
 use crate::cli::row_item::{RowItem, RowItemType};
-use space_rs::Size;
+use space_rs::{DescendantCounts, Size, SizeMode};
 use std::{cell::RefCell, rc::Rc};
 
+#[cfg(test)]
+#[path = "rescan_test.rs"]
+mod rescan_test;
+
 impl ViewState {
     /// Prepares a rescan of the currently selected directory.  Clears the
     /// item's children, subtracts its old size from ancestors and
@@ -22,11 +26,33 @@ impl ViewState {
 
         let path = selected.borrow().get_path();
         let ancestor_segments = get_ancestor_segments(&selected);
-        let old_size = selected.borrow().size.get_value();
+        // In count mode the item itself remains as one entry, as the rescan only sends its descendants.
+        let retained_size = if self.size_mode == SizeMode::Count {
+            1
+        } else {
+            0
+        };
+        let old_size = selected
+            .borrow()
+            .size
+            .get_value()
+            .saturating_sub(retained_size);
+        let old_ignored_size = selected.borrow().ignored_size.get_value();
         let old_descendants = selected.borrow().descendant_count;
+        let old_counts = selected.borrow().descendant_counts;
+
+        // Any errors within the item will be reported again by the rescan, if they still occur.
+        self.scan_errors
+            .retain(|error| !error.path.starts_with(&path));
 
         // Subtract old size from ancestors.
-        subtract_from_ancestors(&selected, old_size, old_descendants);
+        subtract_from_ancestors(
+            &selected,
+            old_size,
+            old_ignored_size,
+            old_descendants,
+            &old_counts,
+        );
 
         // Subtract from total.
         self.total_size_in_bytes = self.total_size_in_bytes.saturating_sub(old_size);
@@ -36,8 +62,10 @@ impl ViewState {
         {
             let mut item_ref = selected.borrow_mut();
             item_ref.children.clear();
-            item_ref.size = Size::new(0);
+            item_ref.size = Size::new(retained_size);
+            item_ref.ignored_size = Size::new(0);
             item_ref.descendant_count = 0;
+            item_ref.descendant_counts = DescendantCounts::default();
             item_ref.max_child_size = 0;
             item_ref.has_children = false;
             item_ref.expanded = false;
@@ -72,9 +100,15 @@ fn get_ancestor_segments(item: &Rc<RefCell<RowItem>>) -> Vec<String> {
     segments
 }
 
-/// Subtracts `size` and `descendants` from all ancestors of `item` up to the
-/// root.
-fn subtract_from_ancestors(item: &Rc<RefCell<RowItem>>, size: u64, descendants: usize) {
+/// Subtracts `size`, `ignored_size`, `descendants` and `counts` from all
+/// ancestors of `item` up to the root.
+fn subtract_from_ancestors(
+    item: &Rc<RefCell<RowItem>>,
+    size: u64,
+    ignored_size: u64,
+    descendants: usize,
+    counts: &DescendantCounts,
+) {
     let mut current = item.clone();
     loop {
         let parent = current.borrow().parent.as_ref().and_then(|p| p.upgrade());
@@ -83,7 +117,9 @@ fn subtract_from_ancestors(item: &Rc<RefCell<RowItem>>, size: u64, descendants:
             let mut p_ref = parent.borrow_mut();
             let old = p_ref.size.get_value();
             p_ref.size = Size::new(old.saturating_sub(size));
+            p_ref.ignored_size.subtract(ignored_size);
             p_ref.descendant_count = p_ref.descendant_count.saturating_sub(descendants);
+            p_ref.descendant_counts.subtract(counts);
         }
         current = parent;
     }
@@ -0,0 +1,62 @@
+use crate::cli::view_state::ViewState;
+use crate::cli::view_state_test_utils::select_item_by_name;
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, Size, SizeMode};
+
+fn make_item(path_segment: &str, item_type: DirectoryItemType) -> DirectoryItem {
+    DirectoryItem {
+        path_segment: path_segment.to_string(),
+        item_type,
+        size_in_bytes: Size::new(1000),
+        allocated_size_in_bytes: Size::default(),
+        descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
+        children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
+    }
+}
+
+fn make_children() -> Vec<DirectoryItem> {
+    vec![
+        make_item("a", DirectoryItemType::File),
+        make_item("b", DirectoryItemType::File),
+    ]
+}
+
+#[test]
+fn prepare_rescan_in_count_mode_keeps_entry_of_rescanned_directory() -> anyhow::Result<()> {
+    // Arrange
+    let mut view_state = ViewState {
+        size_mode: SizeMode::Count,
+        is_scanning: true,
+        visible_height: 10,
+        table_width: 80,
+        ..Default::default()
+    };
+    view_state.add_scanned_item(make_item("/root", DirectoryItemType::Directory));
+    view_state.add_scanned_child(make_item("dir", DirectoryItemType::Directory));
+    view_state.add_scanned_descendant_batch(&["dir".to_string()], make_children());
+    view_state.update_visible_rows();
+    let total_size = view_state.total_size_in_bytes;
+    let root_size = view_state.item_tree[0].borrow().size.get_value();
+
+    // Act
+    for _ in 0..2 {
+        select_item_by_name("dir", &mut view_state)?;
+        view_state.prepare_rescan();
+        view_state.add_scanned_descendant_batch(&["dir".to_string()], make_children());
+        view_state.update_visible_rows();
+    }
+
+    // Assert
+    let root = view_state.item_tree[0].borrow();
+    let dir = root.children[0].borrow();
+    assert_eq!(3, dir.size.get_value());
+    assert_eq!(4, root_size);
+    assert_eq!(root_size, root.size.get_value());
+    assert_eq!(total_size, view_state.total_size_in_bytes);
+    Ok(())
+}
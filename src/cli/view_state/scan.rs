@@ -41,15 +41,16 @@ use super::scan_helpers::{
 use super::scan_helpers::{insert_grandchild_into_parent, update_root_for_grandchild};
 use super::ViewState;
 use crate::cli::row_item::RowItem;
-use space_rs::{DirectoryItem, DirectoryItemType, Size};
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, ScanError, Size};
 use std::{cell::RefCell, rc::Rc};
 
 impl ViewState {
     pub(crate) fn add_scanned_item(&mut self, item: DirectoryItem) {
-        self.total_size_in_bytes += item.size_in_bytes.get_value();
+        self.total_size_in_bytes += item.get_size(self.size_mode).get_value();
 
         let descendant_count = item.descendant_count;
-        let row_item = RowItem::from_directory_item(&item, self.total_size_in_bytes, None, 0);
+        let row_item =
+            RowItem::from_directory_item(&item, self.size_mode, self.total_size_in_bytes, None, 0);
         row_item
             .borrow_mut()
             .update_tree_prefix(&String::default(), false);
@@ -59,7 +60,7 @@ impl ViewState {
     }
 
     pub(crate) fn add_scanned_child(&mut self, child_item: DirectoryItem) {
-        let child_size = child_item.size_in_bytes.get_value();
+        let child_size = child_item.get_size(self.size_mode).get_value();
         self.total_size_in_bytes += child_size;
 
         let Some(root) = self.item_tree.last().cloned() else {
@@ -67,7 +68,13 @@ impl ViewState {
         };
 
         let is_directory = child_item.item_type == DirectoryItemType::Directory;
-        let child_row = build_child_row(&child_item, self.total_size_in_bytes, &root, is_directory);
+        let child_row = build_child_row(
+            &child_item,
+            self.size_mode,
+            self.total_size_in_bytes,
+            &root,
+            is_directory,
+        );
         let child_descendant_count = child_item.descendant_count;
 
         insert_child_into_root(&root, child_row, child_size, child_descendant_count);
@@ -78,7 +85,7 @@ impl ViewState {
 
     #[cfg(test)]
     pub(crate) fn add_scanned_grandchild(&mut self, parent_name: &str, child_item: DirectoryItem) {
-        let child_size = child_item.size_in_bytes.get_value();
+        let child_size = child_item.get_size(self.size_mode).get_value();
         self.total_size_in_bytes += child_size;
 
         let Some(root) = self.item_tree.last().cloned() else {
@@ -91,6 +98,7 @@ impl ViewState {
         let is_directory = child_item.item_type == DirectoryItemType::Directory;
         let grandchild_row = RowItem::from_directory_item(
             &child_item,
+            self.size_mode,
             self.total_size_in_bytes,
             Some(Rc::downgrade(&parent_child)),
             0,
@@ -100,6 +108,7 @@ impl ViewState {
             grandchild_row.borrow_mut().is_scanning = true;
         }
         let child_descendant_count = child_item.descendant_count;
+        let child_counts = grandchild_row.borrow().get_counts_including_self();
 
         insert_grandchild_into_parent(
             &parent_child,
@@ -107,7 +116,13 @@ impl ViewState {
             child_size,
             child_descendant_count,
         );
-        update_root_for_grandchild(&root, parent_name, child_size, child_descendant_count);
+        update_root_for_grandchild(
+            &root,
+            parent_name,
+            child_size,
+            child_descendant_count,
+            &child_counts,
+        );
 
         self.total_items_in_tree += 1 + child_descendant_count;
         self.visible_rows_dirty = true;
@@ -132,6 +147,10 @@ impl ViewState {
         self.visible_rows_dirty = true;
     }
 
+    pub(crate) fn add_scan_errors(&mut self, errors: Vec<ScanError>) {
+        self.scan_errors.extend(errors);
+    }
+
     pub(crate) fn mark_child_scan_complete(&mut self, name: &str) {
         let Some(root) = self.item_tree.last().cloned() else {
             return;
@@ -176,20 +195,24 @@ impl ViewState {
         };
 
         let mut total_size: u64 = 0;
+        let mut total_ignored_size: u64 = 0;
         let mut total_descendants: usize = 0;
+        let mut total_counts = DescendantCounts::default();
 
         {
             let mut parent_ref = parent.borrow_mut();
             for child_item in &children {
-                let child_size = child_item.size_in_bytes.get_value();
+                let child_size = child_item.get_size(self.size_mode).get_value();
                 let child_descendant_count = child_item.descendant_count;
                 self.total_size_in_bytes += child_size;
                 total_size += child_size;
+                total_ignored_size += child_item.get_ignored_size(self.size_mode).get_value();
                 total_descendants += 1 + child_descendant_count;
 
                 let is_directory = child_item.item_type == DirectoryItemType::Directory;
                 let child_row = RowItem::from_directory_item(
                     child_item,
+                    self.size_mode,
                     self.total_size_in_bytes,
                     Some(Rc::downgrade(&parent)),
                     0,
@@ -197,6 +220,7 @@ impl ViewState {
                 if is_directory {
                     child_row.borrow_mut().is_scanning = true;
                 }
+                total_counts.add(&child_row.borrow().get_counts_including_self());
 
                 parent_ref.max_child_size = parent_ref.max_child_size.max(child_size);
                 // Append without sorting or prefix updates - rebuilt at scan end.
@@ -205,7 +229,10 @@ impl ViewState {
             parent_ref.has_children = true;
             let parent_size = parent_ref.size.get_value();
             parent_ref.size = Size::new(parent_size + total_size);
+            parent_ref.ignored_size =
+                Size::new(parent_ref.ignored_size.get_value() + total_ignored_size);
             parent_ref.descendant_count += total_descendants;
+            parent_ref.descendant_counts.add(&total_counts);
             // Batch received - clear scanning flag (derive_scanning_state
             // will re-set it if subdirectory children are pending).
             parent_ref.is_scanning = false;
@@ -222,7 +249,10 @@ impl ViewState {
                 let mut node_ref = next.borrow_mut();
                 let old_size = node_ref.size.get_value();
                 node_ref.size = Size::new(old_size + total_size);
+                node_ref.ignored_size =
+                    Size::new(node_ref.ignored_size.get_value() + total_ignored_size);
                 node_ref.descendant_count += total_descendants;
+                node_ref.descendant_counts.add(&total_counts);
             }
             // Update current's max_child_size with next's (possibly grown) size.
             let next_size = next.borrow().size.get_value();
@@ -236,7 +266,10 @@ impl ViewState {
             let mut root_ref = root.borrow_mut();
             let old_size = root_ref.size.get_value();
             root_ref.size = Size::new(old_size + total_size);
+            root_ref.ignored_size =
+                Size::new(root_ref.ignored_size.get_value() + total_ignored_size);
             root_ref.descendant_count += total_descendants;
+            root_ref.descendant_counts.add(&total_counts);
         }
 
         self.total_items_in_tree += total_descendants;
@@ -1,13 +1,20 @@
 use crate::cli::view_state::ViewState;
-use space_rs::{DirectoryItem, DirectoryItemType, Size};
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, Size};
 
 fn make_file_item(path_segment: &str, size: u64) -> DirectoryItem {
     DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::File,
         size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -16,8 +23,15 @@ fn make_empty_root(path_segment: &str) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::default(),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 #[test]
@@ -60,18 +74,36 @@ fn add_scanned_child_counts_nested_descendants() {
         path_segment: "subdir".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(5000),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 3,
+        descendant_counts: DescendantCounts {
+            files: 3,
+            ..Default::default()
+        },
         children: vec![
             make_file_item("f1", 2000),
             make_file_item("f2", 1500),
             make_file_item("f3", 1500),
         ],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     view_state.add_scanned_child(dir_child);
 
     let root = view_state.item_tree[0].borrow();
     // 1 (the subdir itself) + 3 (its descendants)
     assert_eq!(4, root.descendant_count);
+    assert_eq!(
+        DescendantCounts {
+            files: 3,
+            directories: 1,
+            ..Default::default()
+        },
+        root.descendant_counts
+    );
 }
 
 #[test]
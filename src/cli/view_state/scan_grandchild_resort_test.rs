@@ -1,5 +1,5 @@
 use crate::cli::view_state::ViewState;
-use space_rs::{DirectoryItem, DirectoryItemType, Size};
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, Size};
 
 // Helpers are duplicated from scan_grandchild_test.rs to keep this module independent.
 
@@ -8,8 +8,15 @@ fn make_file_item(path_segment: &str, size: u64) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::File,
         size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -18,8 +25,15 @@ fn make_empty_dir(path_segment: &str) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::default(),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -1,5 +1,5 @@
 use crate::cli::view_state::ViewState;
-use space_rs::{DirectoryItem, DirectoryItemType, Size};
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, Size};
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
@@ -8,8 +8,15 @@ fn make_file_item(path_segment: &str, size: u64) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::File,
         size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -18,8 +25,15 @@ fn make_empty_dir(path_segment: &str) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::default(),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -123,12 +137,19 @@ fn add_scanned_grandchild_counts_nested_descendants_from_grandchild_subtree() {
         path_segment: "subdir".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(6000),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 3,
+        descendant_counts: DescendantCounts::default(),
         children: vec![
             make_file_item("f1.txt", 2000),
             make_file_item("f2.txt", 2000),
             make_file_item("f3.txt", 2000),
         ],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     view_state.add_scanned_grandchild("parent_dir", deep_grandchild);
 
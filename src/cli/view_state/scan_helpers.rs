@@ -1,15 +1,19 @@
 use crate::cli::row_item::RowItem;
-use space_rs::{DirectoryItem, Size};
+#[cfg(test)]
+use space_rs::DescendantCounts;
+use space_rs::{DirectoryItem, Size, SizeMode};
 use std::{cell::RefCell, rc::Rc};
 
 pub(super) fn build_child_row(
     child_item: &DirectoryItem,
+    size_mode: SizeMode,
     total_size_in_bytes: u64,
     root: &Rc<RefCell<RowItem>>,
     is_directory: bool,
 ) -> Rc<RefCell<RowItem>> {
     let child_row = RowItem::from_directory_item(
         child_item,
+        size_mode,
         total_size_in_bytes,
         Some(Rc::downgrade(root)),
         0,
@@ -29,7 +33,12 @@ pub(super) fn insert_child_into_root(
     let mut root_ref = root.borrow_mut();
     let root_size = root_ref.size.get_value();
     root_ref.size = Size::new(root_size + child_size);
+    let child_ignored_size = child_row.borrow().ignored_size.get_value();
+    root_ref.ignored_size = Size::new(root_ref.ignored_size.get_value() + child_ignored_size);
     root_ref.descendant_count += 1 + child_descendant_count;
+    root_ref
+        .descendant_counts
+        .add(&child_row.borrow().get_counts_including_self());
     root_ref.has_children = true;
     // Only force expand on the first child; preserve user's collapse state after that.
     if root_ref.children.is_empty() {
@@ -88,6 +97,9 @@ pub(super) fn insert_grandchild_into_parent(
     let parent_size = parent_ref.size.get_value();
     parent_ref.size = Size::new(parent_size + child_size);
     parent_ref.descendant_count += 1 + child_descendant_count;
+    parent_ref
+        .descendant_counts
+        .add(&grandchild_row.borrow().get_counts_including_self());
     parent_ref.has_children = true;
 
     let gc_size_val = grandchild_row.borrow().size.get_value();
@@ -116,11 +128,13 @@ pub(super) fn update_root_for_grandchild(
     parent_name: &str,
     child_size: u64,
     child_descendant_count: usize,
+    child_counts: &DescendantCounts,
 ) {
     let mut root_ref = root.borrow_mut();
     let root_size = root_ref.size.get_value();
     root_ref.size = Size::new(root_size + child_size);
     root_ref.descendant_count += 1 + child_descendant_count;
+    root_ref.descendant_counts.add(child_counts);
     resort_child_in_parent(&mut root_ref, parent_name, " ");
 }
 
@@ -1,13 +1,20 @@
 use crate::cli::view_state::ViewState;
-use space_rs::{DirectoryItem, DirectoryItemType, Size};
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, Size};
 
 fn make_file_item(path_segment: &str, size: u64) -> DirectoryItem {
     DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::File,
         size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -16,8 +23,15 @@ fn make_empty_root(path_segment: &str) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::default(),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 #[test]
@@ -83,8 +97,15 @@ fn add_scanned_child_with_descendants_increments_total_items_by_one_plus_descend
         path_segment: "deep".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(6000),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 5,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     view_state.add_scanned_child(deep_child);
 
@@ -102,8 +123,15 @@ fn add_scanned_child_empty_directory_increments_total_items_by_one() {
         path_segment: "emptydir".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(0),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     view_state.add_scanned_child(empty_dir);
 
@@ -126,8 +154,15 @@ fn add_scanned_child_sequential_additions_accumulate_total_items_correctly() {
         path_segment: "subdir".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(2000),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 2,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     view_state.add_scanned_child(dir_with_children);
     // 2 + 1 (subdir) + 2 (its descendants) = 5
@@ -150,15 +185,29 @@ fn total_items_in_tree_accurate_after_all_children_added() {
         path_segment: "dir1".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(1000),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 10,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     });
     view_state.add_scanned_child(DirectoryItem {
         path_segment: "dir2".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(500),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 3,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     });
 
     // root(1) + a.txt(1) + b.txt(1) + dir1(1+10) + dir2(1+3) = 18
@@ -1,13 +1,20 @@
 use crate::cli::view_state::ViewState;
-use space_rs::{DirectoryItem, DirectoryItemType, Size};
+use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, Size};
 
 fn make_file_item(path_segment: &str, size: u64) -> DirectoryItem {
     DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::File,
         size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -16,8 +23,15 @@ fn make_empty_root(path_segment: &str) -> DirectoryItem {
         path_segment: path_segment.to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::default(),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     }
 }
 
@@ -58,12 +72,19 @@ fn add_scanned_item_with_directory_counts_descendants_in_total_items() {
         path_segment: "dir".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(5000),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 3,
+        descendant_counts: DescendantCounts::default(),
         children: vec![
             make_file_item("child1", 1000),
             make_file_item("child2", 2000),
             make_file_item("child3", 2000),
         ],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
 
     view_state.add_scanned_item(dir_item);
@@ -193,3 +214,40 @@ fn recalculate_fractions_sets_visible_rows_dirty() {
 
     assert!(view_state.visible_rows_dirty);
 }
+
+// ─── Tests for ignored sizes ─────────────────────────────────────────────────
+
+fn make_ignored_file_item(path_segment: &str, size: u64) -> DirectoryItem {
+    DirectoryItem {
+        is_ignored: true,
+        ignored_size_in_bytes: Size::new(size),
+        ..make_file_item(path_segment, size)
+    }
+}
+
+#[test]
+fn add_scanned_child_and_descendant_batch_propagate_ignored_size_to_ancestors() {
+    let mut view_state = ViewState::default();
+    view_state.add_scanned_item(make_empty_root("/root"));
+    view_state.add_scanned_child(make_empty_root("dir"));
+    view_state.add_scanned_child(make_ignored_file_item("ignored_child", 50));
+
+    view_state.add_scanned_descendant_batch(
+        &["dir".to_string()],
+        vec![
+            make_ignored_file_item("ignored", 300),
+            make_file_item("kept", 100),
+        ],
+    );
+
+    let root = view_state.item_tree[0].borrow();
+    assert_eq!(450, root.size.get_value());
+    assert_eq!(350, root.ignored_size.get_value());
+    let dir = root
+        .children
+        .iter()
+        .find(|c| c.borrow().path_segment == "dir")
+        .unwrap()
+        .borrow();
+    assert_eq!(300, dir.ignored_size.get_value());
+}
@@ -1,13 +1,13 @@
 use crate::cli::{
     row_item::{RowItem, RowItemType},
     skin::Skin,
-    view_state::{APPARENT_SIZE_COLUMN_WIDTH, INCL_PERCENTAGE_COLUMN_WIDTH},
+    view_state::{APPARENT_SIZE_COLUMN_WIDTH, COUNT_COLUMN_WIDTH, INCL_PERCENTAGE_COLUMN_WIDTH},
 };
 use ratatui::{
     style::Style,
     widgets::{Cell, Row},
 };
-use space_rs::SizeDisplayFormat;
+use space_rs::{SizeDisplayFormat, SizeMode};
 use std::{cell::RefCell, rc::Rc};
 
 #[cfg(test)]
@@ -26,6 +26,7 @@ pub(crate) fn add_table_row(
     row_items: &mut Vec<Rc<RefCell<RowItem>>>,
     item: Rc<RefCell<RowItem>>,
     size_display_format: SizeDisplayFormat,
+    size_mode: SizeMode,
     size_threshold_fraction: f32,
     visible_offset: usize,
     visible_height: usize,
@@ -36,6 +37,7 @@ pub(crate) fn add_table_row(
     spinner_tick: usize,
     selected_row_index: usize,
     depth: usize,
+    show_counts: bool,
 ) {
     let (item_incl_fraction, item_regex_visible) = {
         let item_ref = item.as_ref().borrow();
@@ -57,6 +59,7 @@ pub(crate) fn add_table_row(
         row_items,
         &item,
         size_display_format,
+        size_mode,
         visible_offset,
         visible_height,
         *row_index,
@@ -65,6 +68,7 @@ pub(crate) fn add_table_row(
         spinner_tick,
         selected_row_index,
         depth,
+        show_counts,
     );
 
     {
@@ -79,6 +83,7 @@ pub(crate) fn add_table_row(
         row_items,
         &item,
         size_display_format,
+        size_mode,
         size_threshold_fraction,
         visible_offset,
         visible_height,
@@ -89,6 +94,7 @@ pub(crate) fn add_table_row(
         spinner_tick,
         selected_row_index,
         depth + 1,
+        show_counts,
     );
 }
 
@@ -98,6 +104,7 @@ fn try_add_visible_row(
     row_items: &mut Vec<Rc<RefCell<RowItem>>>,
     item: &Rc<RefCell<RowItem>>,
     size_display_format: SizeDisplayFormat,
+    size_mode: SizeMode,
     visible_offset: usize,
     visible_height: usize,
     row_index: usize,
@@ -106,16 +113,19 @@ fn try_add_visible_row(
     spinner_tick: usize,
     selected_row_index: usize,
     depth: usize,
+    show_counts: bool,
 ) {
     if row_index >= visible_offset && *added_count <= visible_height {
         let is_selected = *added_count == selected_row_index;
         let cells = get_row_cell_content(
             item,
             size_display_format,
+            size_mode,
             skin,
             spinner_tick,
             is_selected,
             depth,
+            show_counts,
         );
         rows.push(Row::new(cells).height(1));
         row_items.push(item.clone());
@@ -129,6 +139,7 @@ fn add_children_table_rows(
     row_items: &mut Vec<Rc<RefCell<RowItem>>>,
     item: &Rc<RefCell<RowItem>>,
     size_display_format: SizeDisplayFormat,
+    size_mode: SizeMode,
     size_threshold_fraction: f32,
     visible_offset: usize,
     visible_height: usize,
@@ -139,6 +150,7 @@ fn add_children_table_rows(
     spinner_tick: usize,
     selected_row_index: usize,
     depth: usize,
+    show_counts: bool,
 ) {
     let item_ref = item.borrow();
     if item_ref.has_children && item_ref.expanded {
@@ -148,6 +160,7 @@ fn add_children_table_rows(
                 row_items,
                 child.clone(),
                 size_display_format,
+                size_mode,
                 size_threshold_fraction,
                 visible_offset,
                 visible_height,
@@ -158,6 +171,7 @@ fn add_children_table_rows(
                 spinner_tick,
                 selected_row_index,
                 depth,
+                show_counts,
             );
         }
     }
@@ -173,13 +187,16 @@ fn reset_item_tree_row_indices(item: &Rc<RefCell<RowItem>>) {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn get_row_cell_content<'a>(
     item: &Rc<RefCell<RowItem>>,
     size_display_format: SizeDisplayFormat,
+    size_mode: SizeMode,
     skin: &Skin,
     spinner_tick: usize,
     is_selected: bool,
     depth: usize,
+    show_counts: bool,
 ) -> Vec<Cell<'a>> {
     let item_ref = item.borrow();
     let level_fg = if depth % 2 == 0 {
@@ -194,42 +211,74 @@ pub(crate) fn get_row_cell_content<'a>(
     } else {
         (level_fg, skin.size_bar_bg_color)
     };
-    vec![
-        Cell::from(format_size_cell(&item_ref, size_display_format)),
+    let mut cells = vec![
+        Cell::from(format_size_cell(&item_ref, size_display_format, size_mode)),
         Cell::from(format_expand_indicator(&item_ref)),
         Cell::from(format_path_cell(&item_ref, skin, spinner_tick)),
         Cell::from(format_size_bar(item_ref.peer_fraction, MAX_SIZE_BAR_WIDTH))
             .style(Style::default().fg(bar_fg).bg(bar_bg)),
         Cell::from(format_incl_percentage(item_ref.incl_fraction)),
-    ]
+    ];
+    if show_counts {
+        cells.extend(format_count_cells(&item_ref).map(Cell::from));
+    }
+    cells
 }
 
 pub(crate) fn get_row_cell_content_plain(
     item: &Rc<RefCell<RowItem>>,
     size_display_format: SizeDisplayFormat,
+    size_mode: SizeMode,
     skin: &Skin,
     spinner_tick: usize,
+    show_counts: bool,
 ) -> Vec<String> {
     let item_ref = item.borrow();
-    vec![
-        format_size_cell(&item_ref, size_display_format),
+    let mut cells = vec![
+        format_size_cell(&item_ref, size_display_format, size_mode),
         format_path_cell(&item_ref, skin, spinner_tick),
         format_size_bar(item_ref.peer_fraction, MAX_SIZE_BAR_WIDTH),
         format_incl_percentage(item_ref.incl_fraction),
-    ]
+    ];
+    if show_counts {
+        cells.extend(format_count_cells(&item_ref));
+    }
+    cells
 }
 
 fn format_size_cell(
     item_ref: &std::cell::Ref<'_, RowItem>,
     size_display_format: SizeDisplayFormat,
+    size_mode: SizeMode,
 ) -> String {
     format!(
         "{:>1$}",
-        item_ref.size.to_string(size_display_format),
+        item_ref
+            .size
+            .to_string_for_mode(size_mode, size_display_format),
         APPARENT_SIZE_COLUMN_WIDTH as usize
     )
 }
 
+/// Formats the file, directory, symlink and other counts of the descendants of a directory. The cells of other
+/// items, and zero counts, are left empty to keep the columns easy to scan.
+fn format_count_cells(item_ref: &std::cell::Ref<'_, RowItem>) -> [String; 4] {
+    let counts = &item_ref.descendant_counts;
+    [
+        counts.files,
+        counts.directories,
+        counts.symlinks,
+        counts.other,
+    ]
+    .map(|count| {
+        if item_ref.item_type == RowItemType::Directory && count > 0 {
+            format!("{:>1$}", count, COUNT_COLUMN_WIDTH as usize)
+        } else {
+            String::default()
+        }
+    })
+}
+
 fn format_expand_indicator(item_ref: &std::cell::Ref<'_, RowItem>) -> String {
     if item_ref.has_children {
         if item_ref.expanded {
@@ -264,11 +313,18 @@ fn format_path_cell(
         }
         RowItemType::File => skin.item_type_file_symbol,
         RowItemType::SymbolicLink => skin.item_type_symbolic_link_symbol,
+        RowItemType::HardLink => skin.item_type_hard_link_symbol,
+        RowItemType::MountPoint => skin.item_type_mount_point_symbol,
         RowItemType::Unknown => skin.item_type_unknown_symbol,
     };
     format!(
-        "{}{}{}{}",
-        item_ref.tree_prefix, icon, item_ref.path_segment, descendant_count_suffix
+        "{}{}{}{}{}{}",
+        item_ref.tree_prefix,
+        icon,
+        item_ref.path_segment,
+        item_ref.get_link_target_suffix(),
+        descendant_count_suffix,
+        item_ref.get_ignored_share_suffix()
     )
 }
 
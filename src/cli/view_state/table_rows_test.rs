@@ -1,7 +1,7 @@
 use crate::cli::row_item::{RowItem, RowItemType};
 use crate::cli::skin::Skin;
 use crate::cli::view_state::table_rows::get_row_cell_content_plain;
-use space_rs::{Size, SizeDisplayFormat};
+use space_rs::{DescendantCounts, Size, SizeDisplayFormat, SizeMode};
 use std::{cell::RefCell, rc::Rc};
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
@@ -9,6 +9,7 @@ use std::{cell::RefCell, rc::Rc};
 fn make_dir_row_item(is_scanning: bool) -> Rc<RefCell<RowItem>> {
     Rc::new(RefCell::new(RowItem {
         size: Size::new(1000),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: false,
         tree_prefix: String::default(),
@@ -16,9 +17,12 @@ fn make_dir_row_item(is_scanning: bool) -> Rc<RefCell<RowItem>> {
         incl_fraction: 1.0,
         peer_fraction: 0.0,
         path_segment: "test_dir".to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -32,6 +36,7 @@ fn make_dir_row_item(is_scanning: bool) -> Rc<RefCell<RowItem>> {
 fn make_file_row_item() -> Rc<RefCell<RowItem>> {
     Rc::new(RefCell::new(RowItem {
         size: Size::new(500),
+        ignored_size: Size::default(),
         has_children: false,
         expanded: false,
         tree_prefix: String::default(),
@@ -39,9 +44,12 @@ fn make_file_row_item() -> Rc<RefCell<RowItem>> {
         incl_fraction: 0.5,
         peer_fraction: 0.0,
         path_segment: "test_file.txt".to_string(),
+        link_target: None,
+        metadata: None,
         children: vec![],
         parent: None,
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         depth: 0,
         max_child_size: 0,
         row_index: 0,
@@ -57,8 +65,10 @@ fn path_cell(item: &Rc<RefCell<RowItem>>, spinner_tick: usize) -> String {
     let cells = get_row_cell_content_plain(
         item,
         SizeDisplayFormat::Metric,
+        SizeMode::Apparent,
         &Skin::default(),
         spinner_tick,
+        false,
     );
     // cells: [size, path, bars, percentage]
     cells[1].clone()
@@ -156,3 +166,101 @@ fn view_state_default_spinner_tick_is_zero() {
     let view_state = ViewState::default();
     assert_eq!(0, view_state.spinner_tick);
 }
+
+// ─── Followed links ───────────────────────────────────────────────────────────
+
+#[test]
+fn format_path_cell_shows_link_target_when_link_was_followed() {
+    let item = make_dir_row_item(false);
+    item.borrow_mut().link_target = Some(std::path::PathBuf::from("/mnt/volume"));
+    let path = path_cell(&item, 0);
+    assert!(
+        path.ends_with("test_dir → /mnt/volume"),
+        "expected link target in path cell, got: {path:?}"
+    );
+}
+
+#[test]
+fn format_path_cell_does_not_show_link_target_when_not_followed() {
+    let item = make_dir_row_item(false);
+    let path = path_cell(&item, 0);
+    assert!(
+        !path.contains('→'),
+        "did not expect a link target in path cell, got: {path:?}"
+    );
+}
+
+// ─── Ignored share ────────────────────────────────────────────────────────────
+
+#[test]
+fn format_path_cell_shows_ignored_share_when_some_of_the_size_is_ignored() {
+    let item = make_dir_row_item(false);
+    item.borrow_mut().ignored_size = Size::new(250);
+    let path = path_cell(&item, 0);
+    assert!(
+        path.ends_with("test_dir (25% ignored)"),
+        "expected ignored share in path cell, got: {path:?}"
+    );
+}
+
+#[test]
+fn format_path_cell_does_not_show_ignored_share_when_nothing_is_ignored() {
+    let item = make_dir_row_item(false);
+    let path = path_cell(&item, 0);
+    assert!(
+        !path.contains("ignored"),
+        "did not expect an ignored share in path cell, got: {path:?}"
+    );
+}
+
+// ─── Count columns ────────────────────────────────────────────────────────────
+
+#[test]
+fn get_row_cell_content_plain_includes_count_cells_when_counts_are_shown() {
+    let item = make_dir_row_item(false);
+    item.borrow_mut().descendant_counts = DescendantCounts {
+        files: 12,
+        directories: 3,
+        symlinks: 0,
+        other: 1,
+    };
+    let cells = get_row_cell_content_plain(
+        &item,
+        SizeDisplayFormat::Metric,
+        SizeMode::Apparent,
+        &Skin::default(),
+        0,
+        true,
+    );
+    let counts: Vec<&str> = cells[4..].iter().map(|cell| cell.trim()).collect();
+    assert_eq!(vec!["12", "3", "", "1"], counts);
+}
+
+#[test]
+fn get_row_cell_content_plain_excludes_count_cells_when_counts_are_hidden() {
+    let item = make_dir_row_item(false);
+    item.borrow_mut().descendant_counts.files = 12;
+    let cells = get_row_cell_content_plain(
+        &item,
+        SizeDisplayFormat::Metric,
+        SizeMode::Apparent,
+        &Skin::default(),
+        0,
+        false,
+    );
+    assert_eq!(4, cells.len());
+}
+
+#[test]
+fn get_row_cell_content_plain_leaves_count_cells_empty_for_files() {
+    let item = make_file_row_item();
+    let cells = get_row_cell_content_plain(
+        &item,
+        SizeDisplayFormat::Metric,
+        SizeMode::Apparent,
+        &Skin::default(),
+        0,
+        true,
+    );
+    assert!(cells[4..].iter().all(|cell| cell.is_empty()));
+}
@@ -20,6 +20,7 @@ impl ViewState {
                 &mut self.visible_row_items,
                 item.clone(),
                 self.size_display_format,
+                self.size_mode,
                 self.size_threshold_fraction,
                 self.visible_offset,
                 self.visible_height,
@@ -30,6 +31,7 @@ impl ViewState {
                 self.spinner_tick,
                 self.table_selected_index,
                 0,
+                self.show_counts,
             );
         }
 
@@ -49,10 +51,12 @@ impl ViewState {
                 let cells = table_rows::get_row_cell_content(
                     item,
                     self.size_display_format,
+                    self.size_mode,
                     &self.skin,
                     self.spinner_tick,
                     is_selected,
                     depth,
+                    self.show_counts,
                 );
                 Row::new(cells).height(1)
             })
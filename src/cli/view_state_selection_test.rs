@@ -6,19 +6,33 @@ use rstest::rstest;
 // Note: make_test_view_state_with_expanded_dir is also defined in view_state_test.rs-
 // duplicated here to avoid a shared module dependency.
 fn make_test_view_state_with_expanded_dir() -> ViewState {
-    use space_rs::{DirectoryItem, DirectoryItemType, Size};
+    use space_rs::{DescendantCounts, DirectoryItem, DirectoryItemType, Size};
     let root = DirectoryItem {
         path_segment: "/root".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(1000),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 1,
+        descendant_counts: DescendantCounts::default(),
         children: vec![DirectoryItem {
             path_segment: "child".to_string(),
             item_type: DirectoryItemType::File,
             size_in_bytes: Size::new(1000),
+            allocated_size_in_bytes: Size::default(),
             descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
             children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
         }],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     let mut view_state = ViewState {
         visible_height: 10,
@@ -1,11 +1,13 @@
 use super::{
-    environment::MockEnvServiceTrait, row_item::RowItem, skin::Skin, view_state::ViewState,
+    environment::MockEnvServiceTrait, output_format::OutputFormat, row_item::RowItem, skin::Skin,
+    view_state::ViewState,
 };
 use crate::{
-    cli::view_command::ViewCommand, test_directory_utils::create_test_directory_tree,
+    cli::view_command::{ItemSource, OutputSettings, ViewCommand},
+    test_directory_utils::create_test_directory_tree,
     test_utils::TestOut,
 };
-use space_rs::SizeDisplayFormat;
+use space_rs::{Scanner, SizeDisplayFormat, SizeMode};
 use std::{
     cell::RefCell,
     path::{Path, PathBuf},
@@ -49,15 +51,37 @@ pub(crate) fn make_test_view_state_from_path(
     visible_height: usize,
     visible_offset: usize,
     size_threshold_fraction: f32,
+) -> Result<ViewState, anyhow::Error> {
+    make_test_view_state_from_path_with_scanner(
+        path,
+        visible_height,
+        visible_offset,
+        size_threshold_fraction,
+        Scanner::default(),
+    )
+}
+
+pub(crate) fn make_test_view_state_from_path_with_scanner(
+    path: &Path,
+    visible_height: usize,
+    visible_offset: usize,
+    size_threshold_fraction: f32,
+    scanner: Scanner,
 ) -> Result<ViewState, anyhow::Error> {
     let size_display_format = SizeDisplayFormat::Metric;
     let env_service_mock = MockEnvServiceTrait::new();
     let should_exit = Arc::new(AtomicBool::new(false));
     let mut view_command = ViewCommand::new(
         Some(vec![path.to_path_buf()]),
+        ItemSource::Scan,
         Some(size_display_format),
+        SizeMode::Apparent,
         (size_threshold_fraction * 100f32) as u8,
+        false,
+        OutputFormat::Table,
+        OutputSettings::default(),
         None,
+        Arc::new(scanner),
         Box::new(env_service_mock),
         should_exit,
     );
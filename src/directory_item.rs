@@ -1,10 +1,12 @@
 //! Provides functionality to analyze disk space usage.
 
-use crate::Size;
+use crate::{Size, SizeMode};
 use rayon::{
     prelude::{IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator},
     slice::ParallelSliceMut,
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     fs,
@@ -12,10 +14,42 @@ use std::{
     sync::{atomic::AtomicBool, Arc},
 };
 
+mod descendant_counts;
+mod item_metadata;
+mod progressive_scan;
+mod scan_context;
+mod scan_error;
+mod scan_options;
+mod scan_result;
+#[cfg(feature = "serde")]
+mod scan_snapshot;
+mod scan_visitor;
+mod scanner;
+mod tree_iter;
+
+pub use descendant_counts::DescendantCounts;
+pub use item_metadata::ItemMetadata;
+pub use progressive_scan::{
+    ProgressiveScan, ScanEvent, ScanEventReceiver, ScanEventReceiverDropped, ScanEventSender,
+};
+pub use scan_context::ScanContext;
+pub use scan_error::ScanError;
+pub use scan_options::{IgnoreFileMode, ScanOptions};
+pub use scan_result::ScanResult;
+#[cfg(feature = "serde")]
+pub use scan_snapshot::{ScanSnapshot, SCAN_SNAPSHOT_VERSION};
+pub use scan_visitor::ScanVisitor;
+pub use scanner::{Scanner, ScannerError};
+pub use tree_iter::{BreadthFirstIter, DepthFirstIter, TreeEntry};
+
 #[cfg(test)]
 #[path = "./directory_item_test.rs"]
 mod directory_item_test;
 
+#[cfg(all(test, feature = "serde"))]
+#[path = "./directory_item_serde_test.rs"]
+mod directory_item_serde_test;
+
 const FILE_NAME_ERROR_VALUE: &str = "!error!";
 
 /// Returns `true` if `path` is a Windows junction point (a reparse point that is not a regular
@@ -49,6 +83,11 @@ fn metadata_is_reparse_point(_metadata: &fs::Metadata) -> bool {
 
 /// The supported directory item types.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum DirectoryItemType {
     /// A directory.
     Directory,
@@ -56,57 +95,99 @@ pub enum DirectoryItemType {
     File,
     /// A symbolic link.
     SymbolicLink,
-    /// The type could not be determined or is not supported.
+    /// An additional hard link to a file that has already been counted elsewhere in the scan. Only reported
+    /// when hard links are deduplicated, in which case the size is zero.
+    HardLink,
+    /// A directory on a different file system to the root being scanned, which was not descended into. Only
+    /// reported when staying on one file system, in which case the size is zero.
+    MountPoint,
+    /// The type could not be determined or is not supported, e.g. a FIFO, socket or device node.
     Unknown,
 }
 
 /// A directory item.
 #[derive(Debug, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DirectoryItem {
     /// The last part of the path that ends with this item.
     pub path_segment: String,
     /// The item type.
     pub item_type: DirectoryItemType,
-    /// The size in bytes.
+    /// The apparent size in bytes, i.e. the size of the file content.
     pub size_in_bytes: Size,
+    /// The allocated size in bytes, i.e. the space used by the blocks allocated on disk.
+    pub allocated_size_in_bytes: Size,
     /// If the item is a directory it may also have descendants.
     pub descendant_count: usize,
+    /// The number of descendants per type. The total is equal to `descendant_count`.
+    pub descendant_counts: DescendantCounts,
     /// If the item is a directory, it may also have child items.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
     pub children: Vec<DirectoryItem>,
+    /// If the item was reached by following a symbolic link, the target of that link. The item type and size are
+    /// then those of the target. Only set when links are followed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub link_target: Option<PathBuf>,
+    /// If `true` then the item matches the rules in the ignore files, or is in a directory that does. Only set when
+    /// ignored items are tagged, see [`IgnoreFileMode::Tag`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_ignored: bool,
+    /// The apparent size in bytes of the ignored items, i.e. this item or, if it is a directory, its ignored
+    /// descendants.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ignored_size_in_bytes: Size,
+    /// The allocated size in bytes of the ignored items, i.e. this item or, if it is a directory, its ignored
+    /// descendants.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ignored_allocated_size_in_bytes: Size,
+    /// The timestamps, ownership and permissions of the item. Only set when metadata is collected, see
+    /// [`ScanOptions::collect_metadata`]. Boxed, so that it costs little memory when not collected.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub metadata: Option<Box<ItemMetadata>>,
 }
 
 impl DirectoryItem {
-    /// Builds one or more DirectoryItem trees.
+    /// Builds one or more DirectoryItem trees, using the default scan options. See [`Scanner`] to configure
+    /// the scan.
     #[inline(always)]
-    pub fn build(mut paths: Vec<PathBuf>, should_exit: &Arc<AtomicBool>) -> Vec<DirectoryItem> {
-        if !paths.is_empty() {
-            paths.sort();
-            paths.dedup();
-        }
-
-        let mut items = vec![];
-        for path in paths {
-            items.push(Self::from_root(&path, should_exit));
-        }
-
-        items
+    pub fn build(paths: Vec<PathBuf>, should_exit: &Arc<AtomicBool>) -> Vec<DirectoryItem> {
+        Scanner::default().scan(paths, should_exit).items
     }
 
     /// Builds a subtree from a path, keeping the filename-only `path_segment`.
     /// Unlike `from_root`, this does not override `path_segment` to the full path.
     #[inline(always)]
     pub fn build_subtree(path: &Path, should_exit: &Arc<AtomicBool>) -> DirectoryItem {
-        let mut item = if let Ok(metadata) = fs::symlink_metadata(path) {
-            if metadata.is_file() {
-                Self::from_file_size(path, metadata.len())
-            } else if metadata.is_symlink() || metadata_is_reparse_point(&metadata) {
-                Self::from_link(path)
-            } else {
-                Self::from_directory(path, should_exit)
-            }
-        } else {
-            Self::from_failure(path)
-        };
+        let context = Scanner::default().create_context(should_exit.clone());
+        Self::build_subtree_with_context(path, &context)
+    }
+
+    /// Builds a subtree from a path as part of the scan described by `context`, keeping the
+    /// filename-only `path_segment`. The item at `path` is at depth 0 with regard to the maximum depth.
+    #[inline(always)]
+    pub fn build_subtree_with_context(path: &Path, context: &ScanContext) -> DirectoryItem {
+        Self::build_subtree_at_depth(path, 0, context)
+    }
+
+    /// Builds a subtree from a path as part of the scan described by `context`, keeping the
+    /// filename-only `path_segment`. The item at `path` is at `depth` with regard to the maximum depth, so
+    /// that a scan which discovers the directories itself can build the items below a given depth folded.
+    #[inline(always)]
+    pub fn build_subtree_at_depth(
+        path: &Path,
+        depth: usize,
+        context: &ScanContext,
+    ) -> DirectoryItem {
+        let mut item = Self::from_path(path, depth, context);
 
         item.update_stats_from_descendant();
 
@@ -115,20 +196,17 @@ impl DirectoryItem {
 
     /// Builds a single DirectoryItem tree from a root path.
     #[inline(always)]
-    pub fn from_root(path: &PathBuf, should_exit: &Arc<AtomicBool>) -> DirectoryItem {
-        let mut item = if let Ok(metadata) = fs::symlink_metadata(path) {
-            if metadata.is_file() {
-                Self::from_file_size(path, metadata.len())
-            } else if metadata.is_symlink() || metadata_is_reparse_point(&metadata) {
-                Self::from_link(path)
-            } else {
-                Self::from_directory(path, should_exit)
-            }
-        } else {
-            Self::from_failure(path)
-        };
+    pub fn from_root(path: &Path, should_exit: &Arc<AtomicBool>) -> DirectoryItem {
+        let context = Scanner::default().create_context(should_exit.clone());
+        Self::from_root_with_context(path, &context)
+    }
 
-        item.update_stats_from_descendant();
+    /// Builds a single DirectoryItem tree from a root path, as part of the scan described by `context`.
+    #[inline(always)]
+    pub fn from_root_with_context(path: &Path, context: &ScanContext) -> DirectoryItem {
+        context.set_root(path);
+        context.read_root_ignore_files(path);
+        let mut item = Self::build_subtree_with_context(path, context);
 
         item.path_segment = path.to_string_lossy().to_string();
 
@@ -136,13 +214,141 @@ impl DirectoryItem {
     }
 
     #[inline(always)]
-    fn from_file_size(path: &Path, size_in_bytes: u64) -> DirectoryItem {
+    fn from_path(path: &Path, depth: usize, context: &ScanContext) -> DirectoryItem {
+        let mut item = Self::from_metadata(path, fs::symlink_metadata(path), depth, context);
+        if context.is_ignored(path) {
+            item.mark_ignored();
+        }
+        if let Some(visitor) = context.visitor() {
+            item.visit(path, depth, visitor);
+        }
+        item
+    }
+
+    /// Notifies the visitor of a streamed scan of this item. The descendants of a directory are then discarded, as
+    /// they have all been visited and only the stats are kept.
+    #[inline(always)]
+    fn visit(&mut self, path: &Path, depth: usize, visitor: &dyn ScanVisitor) {
+        if self.item_type == DirectoryItemType::Directory {
+            self.update_stats_from_descendant();
+            visitor.leave_dir(path, depth, self);
+            self.children = vec![];
+        } else {
+            visitor.file(path, depth, self);
+        }
+    }
+
+    #[inline(always)]
+    fn from_metadata(
+        path: &Path,
+        metadata: std::io::Result<fs::Metadata>,
+        depth: usize,
+        context: &ScanContext,
+    ) -> DirectoryItem {
+        let item_metadata = match &metadata {
+            Ok(metadata) if context.options().collect_metadata => {
+                Some(Box::new(ItemMetadata::from(metadata)))
+            }
+            _ => None,
+        };
+        let mut item = match metadata {
+            Ok(metadata) if metadata.is_file() => {
+                if context.is_duplicate_hard_link(&metadata) {
+                    Self::from_hard_link(path)
+                } else {
+                    Self::from_file_metadata(path, &metadata)
+                }
+            }
+            Ok(metadata) if metadata.is_symlink() || metadata_is_reparse_point(&metadata) => {
+                if context.options().follow_symlinks {
+                    Self::from_followed_link(path, depth, context)
+                } else {
+                    Self::from_link(path)
+                }
+            }
+            // FIFOs, sockets and device nodes cannot be read as directories, so they are leaf items.
+            Ok(metadata) if !metadata.is_dir() => Self::from_special_file(path, &metadata),
+            Ok(metadata) if context.is_mount_point(&metadata) => Self::from_mount_point(path),
+            Ok(metadata) => {
+                if context.enter_directory(&metadata) {
+                    Self::from_directory(path, depth, context)
+                } else {
+                    // Already counted via a followed link to a directory outside the roots, so don't count it again.
+                    Self::from_link(path)
+                }
+            }
+            Err(error) => {
+                context.report_error(path, &error);
+                Self::from_failure(path)
+            }
+        };
+        // The item of a followed link already has the metadata of the target, which it describes.
+        if item.metadata.is_none() {
+            item.metadata = item_metadata;
+        }
+        item
+    }
+
+    /// Builds the item for the target of a link, recording the target. A link with a target that does not exist,
+    /// that is a directory within one of the roots, which is counted when the root is scanned, or that is a directory
+    /// which has already been entered, is listed as a link. A root is always followed.
+    #[inline(always)]
+    fn from_followed_link(path: &Path, depth: usize, context: &ScanContext) -> DirectoryItem {
+        let mut item = match fs::metadata(path) {
+            Ok(metadata) if metadata.is_dir() && depth > 0 && context.is_link_into_roots(path) => {
+                Self::from_link(path)
+            }
+            Ok(metadata) if metadata.is_dir() || metadata.is_file() => {
+                Self::from_metadata(path, Ok(metadata), depth, context)
+            }
+            _ => Self::from_link(path),
+        };
+        item.link_target = fs::read_link(path).ok();
+        item
+    }
+
+    #[inline(always)]
+    fn from_file_metadata(path: &Path, metadata: &fs::Metadata) -> DirectoryItem {
         DirectoryItem {
             path_segment: get_file_name_from_path(path),
             item_type: DirectoryItemType::File,
-            size_in_bytes: Size::new(size_in_bytes),
+            size_in_bytes: Size::new(metadata.len()),
+            allocated_size_in_bytes: Size::new(get_allocated_size(metadata)),
+            descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
+            children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
+        }
+    }
+
+    /// Builds the item for a file that is neither a regular file, a directory nor a link, e.g. a FIFO.
+    #[inline(always)]
+    fn from_special_file(path: &Path, metadata: &fs::Metadata) -> DirectoryItem {
+        DirectoryItem {
+            item_type: DirectoryItemType::Unknown,
+            ..Self::from_file_metadata(path, metadata)
+        }
+    }
+
+    #[inline(always)]
+    fn from_hard_link(path: &Path) -> DirectoryItem {
+        DirectoryItem {
+            path_segment: get_file_name_from_path(path),
+            item_type: DirectoryItemType::HardLink,
+            size_in_bytes: Size::default(),
+            allocated_size_in_bytes: Size::default(),
             descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
             children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
         }
     }
 
@@ -152,8 +358,33 @@ impl DirectoryItem {
             path_segment: get_file_name_from_path(path),
             item_type: DirectoryItemType::SymbolicLink,
             size_in_bytes: Size::default(),
+            allocated_size_in_bytes: Size::default(),
             descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
             children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
+        }
+    }
+
+    #[inline(always)]
+    fn from_mount_point(path: &Path) -> DirectoryItem {
+        DirectoryItem {
+            path_segment: get_file_name_from_path(path),
+            item_type: DirectoryItemType::MountPoint,
+            size_in_bytes: Size::default(),
+            allocated_size_in_bytes: Size::default(),
+            descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
+            children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
         }
     }
 
@@ -163,73 +394,147 @@ impl DirectoryItem {
             path_segment: get_file_name_from_path(path),
             item_type: DirectoryItemType::Unknown,
             size_in_bytes: Size::default(),
+            allocated_size_in_bytes: Size::default(),
             descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
             children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
         }
     }
 
     #[inline(always)]
-    fn from_directory(path: &Path, should_exit: &Arc<AtomicBool>) -> DirectoryItem {
-        DirectoryItem {
+    fn from_directory(path: &Path, depth: usize, context: &ScanContext) -> DirectoryItem {
+        if let Some(visitor) = context.visitor() {
+            visitor.enter_dir(path, depth);
+        }
+        let mut item = DirectoryItem {
             path_segment: get_file_name_from_path(path),
             item_type: DirectoryItemType::Directory,
             size_in_bytes: Size::default(),
+            allocated_size_in_bytes: Size::default(),
             descendant_count: 0,
-            children: if should_exit.load(std::sync::atomic::Ordering::Relaxed) {
+            descendant_counts: DescendantCounts::default(),
+            children: if context.should_exit() {
                 vec![]
             } else {
-                Self::get_child_items(path, should_exit)
+                Self::get_child_items(path, depth + 1, context)
             },
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
+        };
+
+        // Beyond the maximum depth only the stats are kept, which also frees the memory used by the descendants.
+        if context
+            .options()
+            .max_depth
+            .is_some_and(|max_depth| depth >= max_depth)
+        {
+            item.update_stats_from_descendant();
+            item.children = vec![];
         }
+
+        item
     }
 
+    /// Marks this item as ignored. The ignored size of a directory is updated from its descendants, which are all
+    /// ignored as well.
     #[inline(always)]
-    fn get_child_items(path: &Path, should_exit: &Arc<AtomicBool>) -> Vec<DirectoryItem> {
-        let entries: Vec<_> = match fs::read_dir(path) {
-            Ok(entries) => entries,
-            Err(_) => return vec![Self::from_failure(path)], // TODO: report error
-        }
-        .filter_map(|result| result.ok())
-        .collect();
+    fn mark_ignored(&mut self) {
+        self.is_ignored = true;
+        self.ignored_size_in_bytes = self.size_in_bytes;
+        self.ignored_allocated_size_in_bytes = self.allocated_size_in_bytes;
+    }
 
-        match entries.len() {
-            0 => vec![],
-            1 => {
-                let path = &entries[0].path();
-                match fs::symlink_metadata(path) {
-                    Ok(metadata) if metadata.is_file() => {
-                        vec![Self::from_file_size(path, metadata.len())]
-                    }
-                    Ok(metadata)
-                        if metadata.is_symlink() || metadata_is_reparse_point(&metadata) =>
-                    {
-                        vec![Self::from_link(path)]
-                    }
-                    Ok(_) => vec![Self::from_directory(path, should_exit)],
-                    Err(_) => vec![Self::from_failure(path)],
-                }
+    #[inline(always)]
+    fn get_child_items(path: &Path, depth: usize, context: &ScanContext) -> Vec<DirectoryItem> {
+        let entries = match fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(error) => {
+                context.report_error(path, &error);
+                return vec![Self::from_failure(path)];
             }
-            _ => entries
+        };
+        context.read_ignore_files(path);
+        let paths: Vec<_> = entries
+            .filter_map(|result| {
+                result
+                    .map_err(|error| context.report_error(path, &error))
+                    .ok()
+            })
+            .map(|entry| entry.path())
+            .filter(|path| !context.is_excluded(path))
+            .collect();
+
+        match paths.len() {
+            0 => vec![],
+            1 => vec![Self::from_path(&paths[0], depth, context)],
+            _ => paths
                 .par_iter()
-                .map(|entry| {
-                    let path = &entry.path();
-                    match fs::symlink_metadata(path) {
-                        Ok(metadata) if metadata.is_file() => {
-                            Self::from_file_size(path, metadata.len())
-                        }
-                        Ok(metadata)
-                            if metadata.is_symlink() || metadata_is_reparse_point(&metadata) =>
-                        {
-                            Self::from_link(path)
-                        }
-                        Ok(_) => Self::from_directory(path, should_exit),
-                        Err(_) => Self::from_failure(path),
-                    }
-                })
+                .map(|path| Self::from_path(path, depth, context))
                 .collect(),
         }
     }
 
+    /// Returns the size of this item that corresponds to the specified size mode.
+    #[inline(always)]
+    pub fn get_size(&self, size_mode: SizeMode) -> Size {
+        match size_mode {
+            SizeMode::Apparent => self.size_in_bytes,
+            SizeMode::Allocated => self.allocated_size_in_bytes,
+            SizeMode::Count => Size::new(self.get_entry_count()),
+        }
+    }
+
+    /// Returns the number of entries in this item, i.e. the item itself and all its descendants.
+    #[inline(always)]
+    pub fn get_entry_count(&self) -> u64 {
+        self.descendant_count as u64 + 1
+    }
+
+    /// Compares this item to another by descending size, using the specified size mode, and then by ascending
+    /// path segment. This is the order of the children of each item.
+    #[inline(always)]
+    pub fn cmp_by_size(&self, other: &Self, size_mode: SizeMode) -> Ordering {
+        // We want the ordering to be descending by size, so we swap the operands.
+        match other.get_size(size_mode).cmp(&self.get_size(size_mode)) {
+            Ordering::Equal => self.path_segment.cmp(&other.path_segment),
+            ordering => ordering,
+        }
+    }
+
+    /// Recursively sorts the descendants of this item by descending size, using the specified size mode.
+    /// Items are sorted by apparent size when built, so this is only required for other size modes.
+    pub fn sort_by_size(&mut self, size_mode: SizeMode) {
+        if self.children.is_empty() {
+            return;
+        }
+
+        self.children
+            .par_iter_mut()
+            .for_each(|child| child.sort_by_size(size_mode));
+
+        self.children
+            .par_sort_by(|a, b| a.cmp_by_size(b, size_mode));
+    }
+
+    /// Returns the size of the ignored items within this item that corresponds to the specified size mode.
+    #[inline(always)]
+    pub fn get_ignored_size(&self, size_mode: SizeMode) -> Size {
+        match size_mode {
+            SizeMode::Apparent => self.ignored_size_in_bytes,
+            SizeMode::Allocated => self.ignored_allocated_size_in_bytes,
+            // The number of ignored entries is not tracked.
+            SizeMode::Count => Size::default(),
+        }
+    }
+
     /// Given the total size in bytes, returns the fraction of that total that his item uses.
     #[inline(always)]
     pub fn get_fraction(&self, total_size_in_bytes: u64) -> f32 {
@@ -262,29 +567,37 @@ impl DirectoryItem {
 
         // Update our own count and size from our descendants' stats.
         let mut size_in_bytes = 0;
+        let mut allocated_size_in_bytes = 0;
+        let mut ignored_size_in_bytes = 0;
+        let mut ignored_allocated_size_in_bytes = 0;
         let mut descendant_count = 0;
+        let mut descendant_counts = DescendantCounts::default();
         self.children.iter().for_each(|child| {
             if child.item_type == DirectoryItemType::Directory {
                 descendant_count += child.descendant_count;
+                descendant_counts.add(&child.descendant_counts);
             }
             descendant_count += 1;
+            descendant_counts.add_item_type(&child.item_type);
             size_in_bytes += child.size_in_bytes.get_value();
+            allocated_size_in_bytes += child.allocated_size_in_bytes.get_value();
+            ignored_size_in_bytes += child.ignored_size_in_bytes.get_value();
+            ignored_allocated_size_in_bytes += child.ignored_allocated_size_in_bytes.get_value();
         });
 
         self.descendant_count = descendant_count;
+        self.descendant_counts = descendant_counts;
         self.size_in_bytes = Size::new(size_in_bytes);
+        self.allocated_size_in_bytes = Size::new(allocated_size_in_bytes);
+        self.ignored_size_in_bytes = Size::new(ignored_size_in_bytes);
+        self.ignored_allocated_size_in_bytes = Size::new(ignored_allocated_size_in_bytes);
     }
 }
 
 impl Ord for DirectoryItem {
     #[inline(always)]
     fn cmp(&self, other: &Self) -> Ordering {
-        // We want the ordering to be descending by size, so we swap the operands.
-        if self.size_in_bytes == other.size_in_bytes {
-            self.path_segment.cmp(&other.path_segment)
-        } else {
-            other.size_in_bytes.cmp(&self.size_in_bytes)
-        }
+        self.cmp_by_size(other, SizeMode::Apparent)
     }
 }
 
@@ -302,6 +615,23 @@ impl PartialEq for DirectoryItem {
     }
 }
 
+/// Returns the number of bytes allocated on disk for the item with the given metadata.
+#[cfg(unix)]
+#[inline(always)]
+fn get_allocated_size(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    // st_blocks is always expressed in 512 byte units, regardless of the filesystem block size.
+    metadata.blocks() * 512
+}
+
+/// Returns the number of bytes allocated on disk for the item with the given metadata. The allocated size is
+/// not available via the standard library on this platform, so the apparent size is used instead.
+#[cfg(not(unix))]
+#[inline(always)]
+fn get_allocated_size(metadata: &fs::Metadata) -> u64 {
+    metadata.len()
+}
+
 #[inline(always)]
 fn get_file_name_from_path(path: &Path) -> String {
     match path.file_name() {
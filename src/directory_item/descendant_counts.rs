@@ -0,0 +1,60 @@
+//! Defines the number of descendants of a directory item, per type.
+
+use super::DirectoryItemType;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+#[path = "./descendant_counts_test.rs"]
+mod descendant_counts_test;
+
+/// The number of descendants of a directory item per type, e.g. to tell a directory with many files from one with
+/// many empty subdirectories, or to find the trees that use the most inodes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DescendantCounts {
+    /// The number of files.
+    pub files: usize,
+    /// The number of directories.
+    pub directories: usize,
+    /// The number of symbolic links that were not followed. Followed links are counted as their targets.
+    pub symlinks: usize,
+    /// The number of other items, i.e. additional hard links, mount points, special files such as FIFOs and sockets,
+    /// and items of an unknown type.
+    pub other: usize,
+}
+
+impl DescendantCounts {
+    /// Returns the total number of descendants.
+    pub fn total(&self) -> usize {
+        self.files + self.directories + self.symlinks + self.other
+    }
+
+    /// Adds the given counts to these counts.
+    pub fn add(&mut self, counts: &DescendantCounts) {
+        self.files += counts.files;
+        self.directories += counts.directories;
+        self.symlinks += counts.symlinks;
+        self.other += counts.other;
+    }
+
+    /// Subtracts the given counts from these counts, without going below zero.
+    pub fn subtract(&mut self, counts: &DescendantCounts) {
+        self.files = self.files.saturating_sub(counts.files);
+        self.directories = self.directories.saturating_sub(counts.directories);
+        self.symlinks = self.symlinks.saturating_sub(counts.symlinks);
+        self.other = self.other.saturating_sub(counts.other);
+    }
+
+    /// Counts one more item of the given type.
+    pub fn add_item_type(&mut self, item_type: &DirectoryItemType) {
+        match item_type {
+            DirectoryItemType::File => self.files += 1,
+            DirectoryItemType::Directory => self.directories += 1,
+            DirectoryItemType::SymbolicLink => self.symlinks += 1,
+            DirectoryItemType::HardLink
+            | DirectoryItemType::MountPoint
+            | DirectoryItemType::Unknown => self.other += 1,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::DescendantCounts;
+use crate::DirectoryItemType;
+use rstest::rstest;
+
+#[rstest]
+#[case(DirectoryItemType::File, DescendantCounts { files: 1, ..Default::default() })]
+#[case(DirectoryItemType::Directory, DescendantCounts { directories: 1, ..Default::default() })]
+#[case(DirectoryItemType::SymbolicLink, DescendantCounts { symlinks: 1, ..Default::default() })]
+#[case(DirectoryItemType::HardLink, DescendantCounts { other: 1, ..Default::default() })]
+#[case(DirectoryItemType::MountPoint, DescendantCounts { other: 1, ..Default::default() })]
+#[case(DirectoryItemType::Unknown, DescendantCounts { other: 1, ..Default::default() })]
+fn add_item_type_counts_item_by_type(
+    #[case] item_type: DirectoryItemType,
+    #[case] expected: DescendantCounts,
+) {
+    // Arrange
+    let mut counts = DescendantCounts::default();
+
+    // Act
+    counts.add_item_type(&item_type);
+
+    // Assert
+    assert_eq!(expected, counts);
+    assert_eq!(1, counts.total());
+}
+
+#[test]
+fn add_then_subtract_returns_original_counts() {
+    // Arrange
+    let original = DescendantCounts {
+        files: 5,
+        directories: 3,
+        symlinks: 2,
+        other: 1,
+    };
+    let other = DescendantCounts {
+        files: 2,
+        directories: 1,
+        symlinks: 1,
+        other: 1,
+    };
+    let mut counts = original;
+
+    // Act
+    counts.add(&other);
+    let total_after_add = counts.total();
+    counts.subtract(&other);
+
+    // Assert
+    assert_eq!(16, total_after_add);
+    assert_eq!(original, counts);
+}
+
+#[test]
+fn subtract_does_not_go_below_zero() {
+    // Arrange
+    let mut counts = DescendantCounts {
+        files: 1,
+        ..Default::default()
+    };
+
+    // Act
+    counts.subtract(&DescendantCounts {
+        files: 2,
+        directories: 1,
+        symlinks: 0,
+        other: 0,
+    });
+
+    // Assert
+    assert_eq!(DescendantCounts::default(), counts);
+}
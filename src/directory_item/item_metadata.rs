@@ -0,0 +1,102 @@
+//! Defines the optional metadata of directory items, i.e. timestamps, ownership and permissions.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{fs, time::SystemTime};
+
+#[cfg(test)]
+#[path = "./item_metadata_test.rs"]
+mod item_metadata_test;
+
+/// The timestamps, ownership and permissions of a directory item. Only collected when requested, see
+/// [`ScanOptions::collect_metadata`]. Each value is `None` if it is not supported by the platform or file system.
+///
+/// [`ScanOptions::collect_metadata`]: crate::ScanOptions::collect_metadata
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ItemMetadata {
+    /// The time the content of the item was last modified.
+    pub modified: Option<SystemTime>,
+    /// The time the item was last accessed. Many file systems only update this lazily, or not at all.
+    pub accessed: Option<SystemTime>,
+    /// The time the metadata of the item, e.g. its ownership or permissions, last changed. Only supported on Unix
+    /// platforms.
+    pub changed: Option<SystemTime>,
+    /// The user ID of the owner. Only supported on Unix platforms.
+    pub uid: Option<u32>,
+    /// The group ID of the owner. Only supported on Unix platforms.
+    pub gid: Option<u32>,
+    /// The file type and permission bits, as in `st_mode`. Only supported on Unix platforms.
+    pub mode: Option<u32>,
+}
+
+impl ItemMetadata {
+    /// Returns the permission bits of [`mode`](ItemMetadata::mode) in the symbolic notation used by `ls -l`, e.g.
+    /// `rwxr-xr-x`, including the setuid, setgid and sticky bits.
+    pub fn format_permissions(&self) -> Option<String> {
+        self.mode.map(|mode| {
+            const SYMBOLS: [char; 3] = ['r', 'w', 'x'];
+            let mut permissions: Vec<char> = (0..9)
+                .map(|bit| {
+                    if mode & (0o400 >> bit) != 0 {
+                        SYMBOLS[bit % 3]
+                    } else {
+                        '-'
+                    }
+                })
+                .collect();
+            for (special_bit, index, symbol) in
+                [(0o4000, 2, 's'), (0o2000, 5, 's'), (0o1000, 8, 't')]
+            {
+                if mode & special_bit != 0 {
+                    permissions[index] = if permissions[index] == 'x' {
+                        symbol
+                    } else {
+                        symbol.to_ascii_uppercase()
+                    };
+                }
+            }
+            permissions.into_iter().collect()
+        })
+    }
+}
+
+impl From<&fs::Metadata> for ItemMetadata {
+    #[cfg(unix)]
+    fn from(metadata: &fs::Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+        ItemMetadata {
+            modified: metadata.modified().ok(),
+            accessed: metadata.accessed().ok(),
+            changed: Some(system_time_from_unix_time(
+                metadata.ctime(),
+                metadata.ctime_nsec(),
+            )),
+            uid: Some(metadata.uid()),
+            gid: Some(metadata.gid()),
+            mode: Some(metadata.mode()),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn from(metadata: &fs::Metadata) -> Self {
+        ItemMetadata {
+            modified: metadata.modified().ok(),
+            accessed: metadata.accessed().ok(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Converts a Unix timestamp, which may be before the epoch, to a `SystemTime`.
+#[cfg(unix)]
+fn system_time_from_unix_time(seconds: i64, nanoseconds: i64) -> SystemTime {
+    use std::time::{Duration, UNIX_EPOCH};
+    let offset = Duration::new(seconds.unsigned_abs(), 0);
+    let time = if seconds < 0 {
+        UNIX_EPOCH - offset
+    } else {
+        UNIX_EPOCH + offset
+    };
+    time + Duration::from_nanos(nanoseconds.max(0) as u64)
+}
@@ -0,0 +1,54 @@
+use super::ItemMetadata;
+use rstest::rstest;
+
+#[rstest]
+#[case(0o100644, "rw-r--r--")]
+#[case(0o040755, "rwxr-xr-x")]
+#[case(0o100000, "---------")]
+#[case(0o104755, "rwsr-xr-x")]
+#[case(0o102644, "rw-r-Sr--")]
+#[case(0o041777, "rwxrwxrwt")]
+fn format_permissions_returns_symbolic_notation(#[case] mode: u32, #[case] expected: &str) {
+    // Arrange
+    let metadata = ItemMetadata {
+        mode: Some(mode),
+        ..Default::default()
+    };
+
+    // Act
+    let permissions = metadata.format_permissions();
+
+    // Assert
+    assert_eq!(Some(expected.to_string()), permissions);
+}
+
+#[test]
+fn format_permissions_given_no_mode_returns_none() {
+    assert_eq!(None, ItemMetadata::default().format_permissions());
+}
+
+#[cfg(unix)]
+#[test]
+fn from_metadata_captures_timestamps_ownership_and_mode() -> anyhow::Result<()> {
+    // Arrange
+    use std::os::unix::fs::MetadataExt;
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&temp_dir)?;
+    let path = temp_dir.join("file");
+    std::fs::write(&path, "content")?;
+    let fs_metadata = std::fs::symlink_metadata(&path)?;
+
+    // Act
+    let metadata = ItemMetadata::from(&fs_metadata);
+
+    // Assert
+    assert_eq!(fs_metadata.modified().ok(), metadata.modified);
+    assert!(metadata.accessed.is_some());
+    assert!(metadata.changed.is_some());
+    assert_eq!(Some(fs_metadata.uid()), metadata.uid);
+    assert_eq!(Some(fs_metadata.gid()), metadata.gid);
+    assert_eq!(Some(fs_metadata.mode()), metadata.mode);
+
+    std::fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
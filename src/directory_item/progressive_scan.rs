@@ -0,0 +1,538 @@
+//! Scans directory trees progressively in a background thread, streaming the items over a channel as they are
+//! discovered.
+
+use super::{
+    is_reparse_point, DescendantCounts, DirectoryItem, DirectoryItemType, ItemMetadata,
+    ScanContext, ScanError, Scanner,
+};
+use crate::Size;
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{RecvTimeoutError, TryRecvError},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+#[cfg(test)]
+#[path = "./progressive_scan_test.rs"]
+mod progressive_scan_test;
+
+/// The sending half of the channel that [`ScanEvent`]s are sent over. It may be cloned to share it between several
+/// scans.
+#[derive(Clone, Debug)]
+pub struct ScanEventSender(crossfire::MTx<crossfire::mpsc::List<ScanEvent>>);
+
+impl ScanEventSender {
+    /// Sends an event, which fails if the receiver has been dropped.
+    pub fn send(&self, event: ScanEvent) -> Result<(), ScanEventReceiverDropped> {
+        self.0.send(event).map_err(|_| ScanEventReceiverDropped)
+    }
+}
+
+/// The error returned when sending a [`ScanEvent`] after the [`ScanEventReceiver`] has been dropped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ScanEventReceiverDropped;
+
+impl Display for ScanEventReceiverDropped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The scan event receiver has been dropped")
+    }
+}
+
+impl Error for ScanEventReceiverDropped {}
+
+/// The receiving half of the channel that [`ScanEvent`]s are sent over.
+#[derive(Debug)]
+pub struct ScanEventReceiver(crossfire::Rx<crossfire::mpsc::List<ScanEvent>>);
+
+impl ScanEventReceiver {
+    /// Returns the next event if there is one, without blocking.
+    pub fn try_recv(&self) -> Result<ScanEvent, TryRecvError> {
+        self.0.try_recv().map_err(|error| match error {
+            crossfire::TryRecvError::Empty => TryRecvError::Empty,
+            crossfire::TryRecvError::Disconnected => TryRecvError::Disconnected,
+        })
+    }
+
+    /// Waits for the next event for at most `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<ScanEvent, RecvTimeoutError> {
+        self.0.recv_timeout(timeout).map_err(|error| match error {
+            crossfire::RecvTimeoutError::Timeout => RecvTimeoutError::Timeout,
+            crossfire::RecvTimeoutError::Disconnected => RecvTimeoutError::Disconnected,
+        })
+    }
+}
+
+/// The events sent by a [`ProgressiveScan`], from which the scanned trees can be assembled as they grow.
+///
+/// Each root directory is sent as an empty shell, followed by all of its children. The remaining directories are
+/// then read level by level, so that all the directories at one depth are sent before any deeper ones, and sizes
+/// grow uniformly across the tree. Directories are always sent as empty shells whose sizes and counts are still to
+/// be aggregated from their descendants, while all other items are sent fully built.
+#[derive(Debug)]
+pub enum ScanEvent {
+    /// A root item. Directories are sent as empty shells, anything else fully built.
+    Item(DirectoryItem),
+    /// A child of the most recent root item.
+    ChildItem(DirectoryItem),
+    /// All immediate children of a descendant directory, sent as a single batch. `ancestor_path` contains the path
+    /// segments of the directory below the root, e.g. `["child"]` or `["child", "grandchild"]`.
+    DescendantBatch {
+        /// The path segments of the directory the children belong to, relative to the root.
+        ancestor_path: Vec<String>,
+        /// The children of the directory.
+        children: Vec<DirectoryItem>,
+    },
+    /// The directory at the contained path segments, relative to the root, could not be read (e.g. access denied).
+    /// An empty path refers to the root itself.
+    AccessDenied(Vec<String>),
+    /// Errors that occurred while scanning, since the previous `Errors` event.
+    Errors(Vec<ScanError>),
+    /// All descendants of the named child of the root have been sent.
+    ChildScanComplete(String),
+    /// The scan has finished, or has been cancelled. This is always the last event of a scan.
+    Complete,
+}
+
+/// A scan running in a background thread, which sends [`ScanEvent`]s as the items are discovered rather than
+/// building the whole tree before returning it like [`Scanner::scan`].
+///
+/// Dropping a `ProgressiveScan` detaches the thread, which keeps running until the scan finishes, is cancelled, or
+/// the receiver is dropped.
+#[derive(Debug)]
+pub struct ProgressiveScan {
+    handle: JoinHandle<()>,
+    should_exit: Arc<AtomicBool>,
+}
+
+impl ProgressiveScan {
+    /// Creates an unbounded channel for the events of one or more scans.
+    pub fn channel() -> (ScanEventSender, ScanEventReceiver) {
+        let (sender, receiver) = crossfire::mpsc::unbounded_blocking();
+        (ScanEventSender(sender), ScanEventReceiver(receiver))
+    }
+
+    /// Starts scanning each path in a new thread, using the options and thread pool of `scanner`. Duplicate paths
+    /// are only scanned once. Sends `Complete` when done.
+    pub fn start(
+        mut paths: Vec<PathBuf>,
+        scanner: Arc<Scanner>,
+        should_exit: Arc<AtomicBool>,
+        sender: ScanEventSender,
+    ) -> Self {
+        paths.sort();
+        paths.dedup();
+
+        let context_should_exit = should_exit.clone();
+        let handle = thread::spawn(move || {
+            scanner.install(|| {
+                let context = Arc::new(scanner.create_context(context_should_exit));
+                context.add_roots(&paths);
+                scan_paths(paths, &context, &sender);
+            });
+        });
+        Self {
+            handle,
+            should_exit,
+        }
+    }
+
+    /// Starts a rescan of a single directory in a new thread, sending `DescendantBatch` events with
+    /// `ancestor_segments` prepended to each `ancestor_path`. Reuses the same BFS infrastructure as the initial
+    /// scan, and sends `Complete` when done. The rescan uses its own scan context, so hard links are only
+    /// deduplicated within the rescanned directory. The entries of a rescanned directory are always listed, even
+    /// if it is at the maximum depth, which allows the descendants of a folded directory to be revealed.
+    pub fn start_rescan(
+        ancestor_segments: Vec<String>,
+        path: PathBuf,
+        scanner: Arc<Scanner>,
+        should_exit: Arc<AtomicBool>,
+        sender: ScanEventSender,
+    ) -> Self {
+        let context_should_exit = should_exit.clone();
+        let handle = thread::spawn(move || {
+            scanner.install(|| {
+                let context = Arc::new(scanner.create_context(context_should_exit));
+                let _ = rescan_directory(&ancestor_segments, &path, &context, &sender);
+                let _ = send_errors(&context, &sender);
+                let _ = sender.send(ScanEvent::Complete);
+            });
+        });
+        Self {
+            handle,
+            should_exit,
+        }
+    }
+
+    /// Asks the scan to stop as soon as possible, after which it sends `Complete`. Note that this sets the
+    /// `should_exit` flag the scan was started with, so any other scans sharing the flag are cancelled too.
+    pub fn cancel(&self) {
+        self.should_exit.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the scan thread has finished.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Waits for the scan thread to finish. Returns an error if the thread panicked.
+    pub fn join(self) -> thread::Result<()> {
+        self.handle.join()
+    }
+}
+
+fn rescan_directory(
+    ancestor_segments: &[String],
+    path: &Path,
+    context: &Arc<ScanContext>,
+    sender: &ScanEventSender,
+) -> Result<(), ()> {
+    enter_root(path, context);
+    let mut bfs_queue: VecDeque<(Vec<String>, PathBuf)> = VecDeque::new();
+    bfs_queue.push_back((ancestor_segments.to_vec(), path.to_path_buf()));
+
+    while !bfs_queue.is_empty() {
+        if context.should_exit() {
+            break;
+        }
+
+        let current_level: Vec<_> = bfs_queue.drain(..).collect();
+        let next_level = Mutex::new(Vec::new());
+        let send_failed = AtomicBool::new(false);
+
+        rayon::scope(|s| {
+            for (ancestor_path, dir_path) in current_level {
+                let sender = sender.clone();
+                let context = context.clone();
+                let next_level = &next_level;
+                let send_failed = &send_failed;
+
+                s.spawn(move |_| {
+                    if context.should_exit() || send_failed.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    if process_directory_bfs(
+                        &ancestor_path,
+                        &dir_path,
+                        &context,
+                        &sender,
+                        next_level,
+                    )
+                    .is_err()
+                    {
+                        send_failed.store(true, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+
+        if send_failed.load(Ordering::Relaxed) {
+            return Err(());
+        }
+        send_errors(context, sender)?;
+
+        bfs_queue.extend(next_level.into_inner().unwrap());
+    }
+
+    Ok(())
+}
+
+fn scan_paths(paths: Vec<PathBuf>, context: &Arc<ScanContext>, sender: &ScanEventSender) {
+    for path in &paths {
+        if context.should_exit() {
+            break;
+        }
+
+        // A root at the maximum depth is built with its descendants folded, like any other directory.
+        if path.is_dir() && context.options().max_depth != Some(0) {
+            if send_directory_progressively(path, context, sender).is_err() {
+                return;
+            }
+        } else {
+            let item = DirectoryItem::from_root_with_context(path, context);
+            if sender.send(ScanEvent::Item(item)).is_err() {
+                return;
+            }
+        }
+    }
+
+    // Ignore send errors - receiver may have been dropped.
+    let _ = send_errors(context, sender);
+    let _ = sender.send(ScanEvent::Complete);
+}
+
+fn send_directory_progressively(
+    path: &PathBuf,
+    context: &Arc<ScanContext>,
+    sender: &ScanEventSender,
+) -> Result<(), ()> {
+    enter_root(path, context);
+
+    // Send the root as an empty shell.
+    let root_item = DirectoryItem {
+        path_segment: path.to_string_lossy().to_string(),
+        item_type: DirectoryItemType::Directory,
+        size_in_bytes: Size::default(),
+        allocated_size_in_bytes: Size::default(),
+        descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
+        children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: if context.options().collect_metadata {
+            fs::metadata(path)
+                .ok()
+                .map(|metadata| Box::new(ItemMetadata::from(&metadata)))
+        } else {
+            None
+        },
+    };
+    if sender.send(ScanEvent::Item(root_item)).is_err() {
+        return Err(());
+    }
+
+    let entries: Vec<_> = match fs::read_dir(path) {
+        Ok(entries) => read_entries(path, entries, context),
+        Err(error) => {
+            context.report_error(path, &error);
+            let _ = sender.send(ScanEvent::AccessDenied(vec![]));
+            return Ok(());
+        }
+    };
+
+    // Phase 1: Send all root children immediately (breadth-first).
+    // Files and symlinks are sent fully built; directories as empty shells.
+    let mut bfs_queue: VecDeque<(Vec<String>, PathBuf)> = VecDeque::new();
+    let mut root_child_names: Vec<String> = Vec::new();
+    for entry in &entries {
+        if context.should_exit() {
+            return Ok(());
+        }
+        let entry_path = entry.path();
+        if let Some(shell) = enter_directory_shell(&entry_path, 1, context) {
+            let name = shell.path_segment.clone();
+            if sender.send(ScanEvent::ChildItem(shell)).is_err() {
+                return Err(());
+            }
+            bfs_queue.push_back((vec![name.clone()], entry_path));
+            root_child_names.push(name);
+        } else {
+            let child = DirectoryItem::build_subtree_at_depth(&entry_path, 1, context);
+            if sender.send(ScanEvent::ChildItem(child)).is_err() {
+                return Err(());
+            }
+        }
+    }
+
+    // Phase 2: BFS - process directories level by level, each level in
+    // parallel via rayon.  All directories at the same depth are discovered
+    // before descending further, so sizes grow uniformly across the tree.
+    while !bfs_queue.is_empty() {
+        if context.should_exit() {
+            break;
+        }
+
+        let current_level: Vec<_> = bfs_queue.drain(..).collect();
+        let next_level = Mutex::new(Vec::new());
+        let send_failed = AtomicBool::new(false);
+
+        rayon::scope(|s| {
+            for (ancestor_path, dir_path) in current_level {
+                let sender = sender.clone();
+                let context = context.clone();
+                let next_level = &next_level;
+                let send_failed = &send_failed;
+
+                s.spawn(move |_| {
+                    if context.should_exit() || send_failed.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    if process_directory_bfs(
+                        &ancestor_path,
+                        &dir_path,
+                        &context,
+                        &sender,
+                        next_level,
+                    )
+                    .is_err()
+                    {
+                        send_failed.store(true, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+
+        if send_failed.load(Ordering::Relaxed) {
+            return Err(());
+        }
+        send_errors(context, sender)?;
+
+        bfs_queue.extend(next_level.into_inner().unwrap());
+    }
+
+    // Phase 3: Signal all root children as scan-complete.
+    for name in root_child_names {
+        sender
+            .send(ScanEvent::ChildScanComplete(name))
+            .map_err(|_| ())?;
+    }
+
+    Ok(())
+}
+
+/// Processes a single directory in the BFS: reads its entries, builds all
+/// children (files fully built, subdirectories as empty shells), and sends
+/// them as a single `DescendantBatch`.  Subdirectories are added to
+/// `next_level` for the next BFS iteration.
+fn process_directory_bfs(
+    ancestor_path: &[String],
+    dir_path: &Path,
+    context: &Arc<ScanContext>,
+    sender: &ScanEventSender,
+    next_level: &Mutex<Vec<(Vec<String>, PathBuf)>>,
+) -> Result<(), ()> {
+    let entries: Vec<_> = match fs::read_dir(dir_path) {
+        Ok(entries) => read_entries(dir_path, entries, context),
+        Err(error) => {
+            context.report_error(dir_path, &error);
+            let _ = sender.send(ScanEvent::AccessDenied(ancestor_path.to_vec()));
+            return Ok(());
+        }
+    };
+
+    let mut children = Vec::with_capacity(entries.len());
+    let mut subdirs = Vec::new();
+    let depth = ancestor_path.len() + 1;
+
+    for entry in entries {
+        if context.should_exit() {
+            break;
+        }
+        let path = entry.path();
+
+        if let Some(shell) = enter_directory_shell(&path, depth, context) {
+            let name = shell.path_segment.clone();
+            // Directory: add empty shell to the batch.
+            children.push(shell);
+            let mut child_ancestor = ancestor_path.to_vec();
+            child_ancestor.push(name);
+            subdirs.push((child_ancestor, path));
+        } else {
+            // File, symlink or directory at the maximum depth: build fully and add to the batch.
+            children.push(DirectoryItem::build_subtree_at_depth(&path, depth, context));
+        }
+    }
+
+    // Send all children as a single batch.
+    sender
+        .send(ScanEvent::DescendantBatch {
+            ancestor_path: ancestor_path.to_vec(),
+            children,
+        })
+        .map_err(|_| ())?;
+
+    // Add discovered subdirectories to the next BFS level.
+    next_level.lock().unwrap().extend(subdirs);
+
+    Ok(())
+}
+
+/// Returns an empty shell for `path` if it is a directory that the BFS should descend into, recording that the
+/// directory has been entered. Links are only followed if enabled, in which case a link into one of the roots, or to a
+/// directory that has already been entered, is built as a leaf item instead. When staying on one file system, mount
+/// points are also built as leaf items. Directories at the maximum `depth` are built in full with their descendants
+/// folded, so they are not descended into either.
+fn enter_directory_shell(
+    path: &Path,
+    depth: usize,
+    context: &ScanContext,
+) -> Option<DirectoryItem> {
+    if context
+        .options()
+        .max_depth
+        .is_some_and(|max_depth| depth >= max_depth)
+    {
+        return None;
+    }
+    let is_link = path.is_symlink() || is_reparse_point(path);
+    if is_link && !context.options().follow_symlinks {
+        return None;
+    }
+    let metadata = fs::metadata(path)
+        .ok()
+        .filter(|metadata| metadata.is_dir())?;
+    if is_link && context.is_link_into_roots(path) {
+        return None;
+    }
+    if context.is_mount_point(&metadata) || !context.enter_directory(&metadata) {
+        return None;
+    }
+    Some(DirectoryItem {
+        path_segment: path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+        item_type: DirectoryItemType::Directory,
+        size_in_bytes: Size::default(),
+        allocated_size_in_bytes: Size::default(),
+        descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
+        children: vec![],
+        link_target: if is_link {
+            fs::read_link(path).ok()
+        } else {
+            None
+        },
+        is_ignored: context.is_ignored(path),
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: context
+            .options()
+            .collect_metadata
+            .then(|| Box::new(ItemMetadata::from(&metadata))),
+    })
+}
+
+/// Prepares `context` for scanning the root directory at `path`, which is recorded as entered so that links back to
+/// it are not followed. The rules in the ignore files above the root also apply.
+fn enter_root(path: &Path, context: &ScanContext) {
+    context.set_root(path);
+    context.read_root_ignore_files(path);
+    if let Ok(metadata) = fs::metadata(path) {
+        context.enter_directory(&metadata);
+    }
+}
+
+/// Collects the readable entries of the directory at `path` that are not excluded, reporting any entries that
+/// could not be read. The ignore files in the directory are read first, so that they apply to the entries.
+fn read_entries(path: &Path, entries: fs::ReadDir, context: &ScanContext) -> Vec<fs::DirEntry> {
+    context.read_ignore_files(path);
+    entries
+        .filter_map(|result| {
+            result
+                .map_err(|error| context.report_error(path, &error))
+                .ok()
+        })
+        .filter(|entry| !context.is_excluded(&entry.path()))
+        .collect()
+}
+
+/// Sends any errors that have been reported since the previous call as a single `Errors` message.
+fn send_errors(context: &ScanContext, sender: &ScanEventSender) -> Result<(), ()> {
+    let errors = context.take_errors();
+    if errors.is_empty() {
+        return Ok(());
+    }
+    sender.send(ScanEvent::Errors(errors)).map_err(|_| ())
+}
@@ -0,0 +1,745 @@
+use super::{ProgressiveScan, ScanEvent, ScanEventReceiver};
+use crate::{
+    test_directory_utils::{create_test_directory_tree, delete_test_directory_tree},
+    DirectoryItemType, Scanner,
+};
+use std::{
+    fs,
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn test_start(paths: Vec<std::path::PathBuf>, should_exit: Arc<AtomicBool>) -> ScanEventReceiver {
+    let (sender, receiver) = ProgressiveScan::channel();
+    ProgressiveScan::start(paths, Arc::new(Scanner::default()), should_exit, sender);
+    receiver
+}
+
+#[test]
+fn start_with_empty_paths_sends_only_complete() {
+    let should_exit = Arc::new(AtomicBool::new(false));
+    let receiver = test_start(vec![], should_exit);
+
+    let msg = receiver
+        .recv_timeout(RECV_TIMEOUT)
+        .expect("Expected Complete message");
+    assert!(
+        matches!(msg, ScanEvent::Complete),
+        "Expected Complete, got an Item"
+    );
+    // Channel should be closed - no further messages.
+    assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn start_with_valid_directory_sends_item_then_children_then_complete() -> anyhow::Result<()> {
+    let temp_dir = create_test_directory_tree()?;
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    let receiver = test_start(vec![temp_dir.clone()], should_exit);
+
+    // First message: root Item (empty shell).
+    let msg = receiver
+        .recv_timeout(RECV_TIMEOUT)
+        .expect("Expected Item message");
+    assert!(
+        matches!(msg, ScanEvent::Item(_)),
+        "Expected Item, got something else"
+    );
+
+    // Then one or more ChildItem messages, followed by Complete.
+    let mut child_count = 0;
+    loop {
+        let msg = receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message");
+        match msg {
+            ScanEvent::ChildItem(_) => child_count += 1,
+            ScanEvent::DescendantBatch { .. }
+            | ScanEvent::AccessDenied(_)
+            | ScanEvent::Errors(_)
+            | ScanEvent::ChildScanComplete(_) => {}
+            ScanEvent::Complete => break,
+            ScanEvent::Item(_) => panic!("Unexpected second Item message"),
+        }
+    }
+
+    assert!(
+        child_count > 0,
+        "Expected at least one ChildItem for a non-empty directory"
+    );
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn start_with_should_exit_set_sends_only_complete() -> anyhow::Result<()> {
+    let temp_dir = create_test_directory_tree()?;
+    // should_exit already true - loop should break before scanning any path.
+    let should_exit = Arc::new(AtomicBool::new(true));
+
+    let receiver = test_start(vec![temp_dir.clone()], should_exit);
+
+    let msg = receiver
+        .recv_timeout(RECV_TIMEOUT)
+        .expect("Expected Complete message");
+    assert!(
+        matches!(msg, ScanEvent::Complete),
+        "Expected Complete when should_exit is set"
+    );
+    assert!(
+        receiver.recv_timeout(Duration::from_millis(200)).is_err(),
+        "Expected no more messages after Complete"
+    );
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn start_deduplicates_duplicate_paths() -> anyhow::Result<()> {
+    let temp_dir = create_test_directory_tree()?;
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    // Pass the same path twice - should be deduplicated to one scan.
+    let receiver = test_start(vec![temp_dir.clone(), temp_dir.clone()], should_exit);
+
+    // Exactly one Item (root shell).
+    let msg = receiver.recv_timeout(RECV_TIMEOUT).expect("Expected Item");
+    assert!(matches!(msg, ScanEvent::Item(_)));
+
+    // Drain ChildItems, then Complete.
+    loop {
+        let msg = receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message");
+        match msg {
+            ScanEvent::ChildItem(_)
+            | ScanEvent::DescendantBatch { .. }
+            | ScanEvent::AccessDenied(_)
+            | ScanEvent::Errors(_)
+            | ScanEvent::ChildScanComplete(_) => continue,
+            ScanEvent::Complete => break,
+            ScanEvent::Item(_) => panic!("Unexpected second Item - path should be deduplicated"),
+        }
+    }
+
+    // Nothing else.
+    assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn start_with_multiple_paths_sends_item_per_path_then_complete() -> anyhow::Result<()> {
+    let temp_dir1 = create_test_directory_tree()?;
+    let temp_dir2 = create_test_directory_tree()?;
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    let receiver = test_start(vec![temp_dir1.clone(), temp_dir2.clone()], should_exit);
+
+    let mut item_count = 0;
+    loop {
+        let msg = receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message");
+        match msg {
+            ScanEvent::Item(_) => item_count += 1,
+            ScanEvent::ChildItem(_)
+            | ScanEvent::DescendantBatch { .. }
+            | ScanEvent::AccessDenied(_)
+            | ScanEvent::Errors(_)
+            | ScanEvent::ChildScanComplete(_) => continue,
+            ScanEvent::Complete => break,
+        }
+    }
+
+    assert_eq!(
+        2, item_count,
+        "Expected exactly 2 root items for 2 distinct paths"
+    );
+
+    delete_test_directory_tree(&temp_dir1);
+    delete_test_directory_tree(&temp_dir2);
+    Ok(())
+}
+
+#[test]
+fn start_with_empty_directory_sends_item_then_complete_no_children() -> anyhow::Result<()> {
+    let temp_dir = std::env::temp_dir().join(format!("space_{}", uuid::Uuid::new_v4()));
+    fs::create_dir(&temp_dir)?;
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    let receiver = test_start(vec![temp_dir.clone()], should_exit);
+
+    // First message: root Item (empty shell for the empty directory).
+    let msg = receiver
+        .recv_timeout(RECV_TIMEOUT)
+        .expect("Expected Item message");
+    assert!(
+        matches!(msg, ScanEvent::Item(_)),
+        "Expected Item for empty directory root"
+    );
+
+    // Next message must be Complete - no ChildItems for an empty directory.
+    let msg = receiver
+        .recv_timeout(RECV_TIMEOUT)
+        .expect("Expected Complete message");
+    assert!(
+        matches!(msg, ScanEvent::Complete),
+        "Expected Complete with no ChildItems for an empty directory"
+    );
+
+    // Channel should now be closed.
+    assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn start_with_symlink_child_sends_child_item_with_symbolic_link_type_not_expanded(
+) -> anyhow::Result<()> {
+    // "1/1.11" is a symlink in the test tree. Scanning "1/" should emit a ChildItem
+    // for the symlink with SymbolicLink type - it must NOT be expanded into grandchildren.
+    let temp_dir = create_test_directory_tree()?;
+    let scan_dir = temp_dir.join("1");
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    let receiver = test_start(vec![scan_dir.clone()], should_exit);
+
+    // Drain all messages. Track whether "1.11" (a direct symlink child of the scan root)
+    // is sent as a ChildItem, and whether it is incorrectly expanded further.
+    let mut saw_1_11_as_child_item = false;
+    let mut saw_1_11_grandchild_messages = false;
+    let mut saw_1_11_child_scan_complete = false;
+    loop {
+        let msg = receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message");
+        match msg {
+            ScanEvent::ChildItem(item) => {
+                if item.path_segment == "1.11" {
+                    assert_eq!(
+                        DirectoryItemType::SymbolicLink,
+                        item.item_type,
+                        "'1.11' ChildItem should have SymbolicLink type"
+                    );
+                    saw_1_11_as_child_item = true;
+                }
+            }
+            ScanEvent::DescendantBatch {
+                ref ancestor_path,
+                ref children,
+            } => {
+                if ancestor_path.contains(&"1.11".to_string())
+                    || children.iter().any(|c| c.path_segment == "1.11")
+                {
+                    saw_1_11_grandchild_messages = true;
+                }
+            }
+            ScanEvent::ChildScanComplete(ref name) => {
+                if name == "1.11" {
+                    saw_1_11_child_scan_complete = true;
+                }
+            }
+            ScanEvent::Item(_) | ScanEvent::AccessDenied(_) | ScanEvent::Errors(_) => {}
+            ScanEvent::Complete => break,
+        }
+    }
+
+    assert!(
+        saw_1_11_as_child_item,
+        "symlink '1.11' should be sent as a ChildItem with SymbolicLink type"
+    );
+    assert!(
+        !saw_1_11_grandchild_messages,
+        "symlink '1.11' should not be expanded into grandchild messages"
+    );
+    assert!(
+        !saw_1_11_child_scan_complete,
+        "symlink '1.11' should not generate a ChildScanComplete (symlinks are not scanned)"
+    );
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn start_with_non_existent_path_sends_errors_before_complete() {
+    let path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    let receiver = test_start(vec![path.clone()], should_exit);
+
+    let mut errors = vec![];
+    loop {
+        match receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message")
+        {
+            ScanEvent::Errors(batch) => errors.extend(batch),
+            ScanEvent::Complete => break,
+            _ => {}
+        }
+    }
+
+    assert_eq!(1, errors.len());
+    assert_eq!(path, errors[0].path);
+    assert_eq!(std::io::ErrorKind::NotFound, errors[0].kind);
+}
+
+#[cfg(unix)]
+#[test]
+fn start_with_fifo_sends_leaf_item_without_errors() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    fs::create_dir_all(temp_dir.join("sub"))?;
+    assert!(std::process::Command::new("mkfifo")
+        .arg(temp_dir.join("pipe"))
+        .status()?
+        .success());
+    assert!(std::process::Command::new("mkfifo")
+        .arg(temp_dir.join("sub").join("pipe"))
+        .status()?
+        .success());
+
+    // Act
+    let receiver = test_start(vec![temp_dir.clone()], Arc::new(AtomicBool::new(false)));
+
+    // Assert
+    let mut pipes = vec![];
+    let mut errors = vec![];
+    loop {
+        match receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message")
+        {
+            ScanEvent::ChildItem(item) if item.path_segment == "pipe" => pipes.push(item),
+            ScanEvent::DescendantBatch { children, .. } => pipes.extend(
+                children
+                    .into_iter()
+                    .filter(|child| child.path_segment == "pipe"),
+            ),
+            ScanEvent::Errors(batch) => errors.extend(batch),
+            ScanEvent::Complete => break,
+            _ => {}
+        }
+    }
+
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(2, pipes.len());
+    for pipe in pipes {
+        assert_eq!(DirectoryItemType::Unknown, pipe.item_type);
+        assert!(pipe.children.is_empty());
+    }
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn start_with_follow_symlinks_and_link_sorting_before_its_target_sends_target_as_directory(
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    fs::create_dir_all(temp_dir.join("z_target"))?;
+    fs::write(temp_dir.join("z_target").join("f"), vec![0u8; 100])?;
+    std::os::unix::fs::symlink(temp_dir.join("z_target"), temp_dir.join("a_link"))?;
+    let scanner = crate::ScanOptions::default()
+        .follow_symlinks(true)
+        .build()?;
+    let (sender, receiver) = ProgressiveScan::channel();
+
+    // Act
+    ProgressiveScan::start(
+        vec![temp_dir.clone()],
+        Arc::new(scanner),
+        Arc::new(AtomicBool::new(false)),
+        sender,
+    );
+
+    // Assert
+    let mut items = vec![];
+    loop {
+        match receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message")
+        {
+            ScanEvent::ChildItem(item) => items.push(item),
+            ScanEvent::Complete => break,
+            _ => {}
+        }
+    }
+
+    let find = |name: &str| items.iter().find(|item| item.path_segment == name).unwrap();
+    assert_eq!(DirectoryItemType::Directory, find("z_target").item_type);
+    let link = find("a_link");
+    assert_eq!(DirectoryItemType::SymbolicLink, link.item_type);
+    assert_eq!(Some(temp_dir.join("z_target")), link.link_target);
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn start_with_follow_symlinks_descends_into_link_and_stops_at_cycle() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let volume_dir = temp_dir.join("volume");
+    let root_dir = temp_dir.join("root");
+    fs::create_dir_all(&volume_dir)?;
+    fs::create_dir_all(&root_dir)?;
+    fs::write(volume_dir.join("f"), vec![0u8; 100])?;
+    std::os::unix::fs::symlink(&volume_dir, root_dir.join("data"))?;
+    std::os::unix::fs::symlink(&root_dir, volume_dir.join("loop"))?;
+    let scanner = crate::ScanOptions::default()
+        .follow_symlinks(true)
+        .build()?;
+    let (sender, receiver) = ProgressiveScan::channel();
+
+    // Act
+    ProgressiveScan::start(
+        vec![root_dir.clone()],
+        Arc::new(scanner),
+        Arc::new(AtomicBool::new(false)),
+        sender,
+    );
+
+    // Assert
+    let mut data_item = None;
+    let mut data_children = vec![];
+    loop {
+        match receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message")
+        {
+            ScanEvent::ChildItem(item) if item.path_segment == "data" => data_item = Some(item),
+            ScanEvent::DescendantBatch {
+                ancestor_path,
+                children,
+            } if ancestor_path == vec!["data".to_string()] => data_children = children,
+            ScanEvent::Complete => break,
+            _ => {}
+        }
+    }
+
+    let data_item = data_item.expect("'data' should be sent as a ChildItem");
+    assert_eq!(DirectoryItemType::Directory, data_item.item_type);
+    assert_eq!(Some(volume_dir.clone()), data_item.link_target);
+
+    let file = data_children
+        .iter()
+        .find(|c| c.path_segment == "f")
+        .unwrap();
+    assert_eq!(100, file.size_in_bytes.get_value());
+    let link = data_children
+        .iter()
+        .find(|c| c.path_segment == "loop")
+        .unwrap();
+    assert_eq!(DirectoryItemType::SymbolicLink, link.item_type);
+    assert_eq!(Some(root_dir.clone()), link.link_target);
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn start_with_exclude_patterns_never_sends_excluded_items() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let scanner = crate::ScanOptions::default()
+        .exclude("1.5")
+        .exclude_regex(r"1\.3$")
+        .build()?;
+    let (sender, receiver) = ProgressiveScan::channel();
+
+    // Act
+    ProgressiveScan::start(
+        vec![temp_dir.clone()],
+        Arc::new(scanner),
+        Arc::new(AtomicBool::new(false)),
+        sender,
+    );
+
+    // Assert
+    let mut sent_names = vec![];
+    loop {
+        match receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message")
+        {
+            ScanEvent::ChildItem(item) => sent_names.push(item.path_segment),
+            ScanEvent::DescendantBatch {
+                ancestor_path,
+                children,
+            } => {
+                assert!(!ancestor_path.contains(&"1.5".to_string()));
+                sent_names.extend(children.into_iter().map(|c| c.path_segment));
+            }
+            ScanEvent::Complete => break,
+            _ => {}
+        }
+    }
+
+    assert!(sent_names.contains(&"1.4".to_string()));
+    assert!(!sent_names.contains(&"1.5".to_string()));
+    assert!(!sent_names.contains(&"1.3".to_string()));
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn start_with_tagged_ignore_files_tags_ignored_items() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    fs::create_dir_all(temp_dir.join("target").join("debug"))?;
+    fs::write(temp_dir.join(".gitignore"), "target/\n")?;
+    fs::write(
+        temp_dir.join("target").join("debug").join("bin"),
+        vec![0u8; 100],
+    )?;
+    fs::write(temp_dir.join("kept"), vec![0u8; 10])?;
+    let scanner = crate::ScanOptions::default()
+        .ignore_files(crate::IgnoreFileMode::Tag)
+        .build()?;
+    let (sender, receiver) = ProgressiveScan::channel();
+
+    // Act
+    ProgressiveScan::start(
+        vec![temp_dir.clone()],
+        Arc::new(scanner),
+        Arc::new(AtomicBool::new(false)),
+        sender,
+    );
+
+    // Assert
+    let mut items = vec![];
+    loop {
+        match receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message")
+        {
+            ScanEvent::ChildItem(item) => items.push(item),
+            ScanEvent::DescendantBatch { children, .. } => items.extend(children),
+            ScanEvent::Complete => break,
+            _ => {}
+        }
+    }
+
+    let is_ignored = |name: &str| {
+        items
+            .iter()
+            .find(|i| i.path_segment == name)
+            .unwrap()
+            .is_ignored
+    };
+    assert!(is_ignored("target"));
+    assert!(is_ignored("debug"));
+    assert!(is_ignored("bin"));
+    assert!(!is_ignored("kept"));
+    let bin = items.iter().find(|i| i.path_segment == "bin").unwrap();
+    assert_eq!(100, bin.ignored_size_in_bytes.get_value());
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn start_with_subdirectory_applies_ignore_files_of_ancestors() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    fs::create_dir_all(temp_dir.join("sub").join("logs"))?;
+    fs::write(temp_dir.join(".gitignore"), "*.log\n")?;
+    fs::write(temp_dir.join("sub").join("a.log"), vec![0u8; 1000])?;
+    fs::write(
+        temp_dir.join("sub").join("logs").join("b.log"),
+        vec![0u8; 1000],
+    )?;
+    fs::write(temp_dir.join("sub").join("a.rs"), vec![0u8; 500])?;
+    let scanner = crate::ScanOptions::default()
+        .ignore_files(crate::IgnoreFileMode::Skip)
+        .build()?;
+    let (sender, receiver) = ProgressiveScan::channel();
+
+    // Act
+    ProgressiveScan::start(
+        vec![temp_dir.join("sub")],
+        Arc::new(scanner),
+        Arc::new(AtomicBool::new(false)),
+        sender,
+    );
+
+    // Assert
+    let mut names = vec![];
+    loop {
+        match receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message")
+        {
+            ScanEvent::ChildItem(item) => names.push(item.path_segment),
+            ScanEvent::DescendantBatch { children, .. } => {
+                names.extend(children.into_iter().map(|child| child.path_segment))
+            }
+            ScanEvent::Complete => break,
+            _ => {}
+        }
+    }
+
+    names.sort();
+    assert_eq!(vec!["a.rs", "logs"], names);
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn start_with_max_depth_sends_directories_at_max_depth_folded() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let scanner = crate::ScanOptions::default().max_depth(2).build()?;
+    let (sender, receiver) = ProgressiveScan::channel();
+
+    // Act
+    ProgressiveScan::start(
+        vec![temp_dir.clone()],
+        Arc::new(scanner),
+        Arc::new(AtomicBool::new(false)),
+        sender,
+    );
+
+    // Assert
+    let mut items = vec![];
+    loop {
+        match receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message")
+        {
+            ScanEvent::DescendantBatch {
+                ancestor_path,
+                children,
+            } => {
+                assert_eq!(vec!["1".to_string()], ancestor_path);
+                items.extend(children);
+            }
+            ScanEvent::Complete => break,
+            _ => {}
+        }
+    }
+
+    let d1_5 = items.iter().find(|i| i.path_segment == "1.5").unwrap();
+    assert!(d1_5.children.is_empty());
+    assert_eq!(18000, d1_5.size_in_bytes.get_value());
+    assert_eq!(10, d1_5.descendant_count);
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn start_rescan_prefixes_ancestor_segments_and_sends_complete() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let (sender, receiver) = ProgressiveScan::channel();
+
+    // Act
+    let scan = ProgressiveScan::start_rescan(
+        vec!["1".to_string()],
+        temp_dir.join("1"),
+        Arc::new(Scanner::default()),
+        Arc::new(AtomicBool::new(false)),
+        sender,
+    );
+
+    // Assert
+    let mut ancestor_paths = vec![];
+    loop {
+        match receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected message")
+        {
+            ScanEvent::DescendantBatch { ancestor_path, .. } => ancestor_paths.push(ancestor_path),
+            ScanEvent::Complete => break,
+            ScanEvent::Errors(_) => {}
+            _ => panic!("Expected only DescendantBatch messages from a rescan"),
+        }
+    }
+    assert_eq!(vec!["1".to_string()], ancestor_paths[0]);
+    assert!(ancestor_paths.contains(&vec![
+        "1".to_string(),
+        "1.5".to_string(),
+        "1.5.3".to_string()
+    ]));
+    assert!(ancestor_paths.iter().all(|path| path[0] == "1"));
+    assert!(scan.join().is_ok());
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn join_waits_for_the_scan_to_finish() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let (sender, receiver) = ProgressiveScan::channel();
+    let scan = ProgressiveScan::start(
+        vec![temp_dir.clone()],
+        Arc::new(Scanner::default()),
+        Arc::new(AtomicBool::new(false)),
+        sender,
+    );
+
+    // Act
+    let result = scan.join();
+
+    // Assert
+    assert!(result.is_ok());
+    let mut last = None;
+    while let Ok(msg) = receiver.try_recv() {
+        last = Some(msg);
+    }
+    assert!(matches!(last, Some(ScanEvent::Complete)));
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn cancel_sets_should_exit_and_scan_sends_complete() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let should_exit = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = ProgressiveScan::channel();
+    let scan = ProgressiveScan::start(
+        vec![temp_dir.clone()],
+        Arc::new(Scanner::default()),
+        should_exit.clone(),
+        sender,
+    );
+
+    // Act
+    scan.cancel();
+
+    // Assert
+    assert!(should_exit.load(std::sync::atomic::Ordering::Relaxed));
+    loop {
+        if let ScanEvent::Complete = receiver
+            .recv_timeout(RECV_TIMEOUT)
+            .expect("Expected Complete message")
+        {
+            break;
+        }
+    }
+    assert!(scan.join().is_ok());
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
@@ -0,0 +1,435 @@
+//! Defines the state that is shared by all the threads involved in a single scan.
+
+use super::{IgnoreFileMode, ScanError, ScanOptions, ScanVisitor};
+use globset::GlobSet;
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    Match,
+};
+use regex::RegexSet;
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+
+#[cfg(test)]
+#[path = "./scan_context_test.rs"]
+mod scan_context_test;
+
+/// The names of the ignore files that are read from each directory, in increasing order of precedence.
+const IGNORE_FILE_NAMES: [&str; 3] = [".gitignore", ".ignore", ".spaceignore"];
+
+/// The state that is shared by all the threads involved in a single scan of one or more directory trees. Use
+/// [`Scanner::create_context`] to create an instance.
+///
+/// [`Scanner::create_context`]: crate::Scanner::create_context
+#[derive(Debug)]
+pub struct ScanContext {
+    options: ScanOptions,
+    exclude: GlobSet,
+    exclude_regex: RegexSet,
+    should_exit: Arc<AtomicBool>,
+    /// The (device, inode) pairs of the files with multiple hard links that have been counted so far.
+    counted_hard_links: Mutex<HashSet<(u64, u64)>>,
+    /// The (device, inode) pairs of the directories that have been entered so far, when following links.
+    entered_directories: Mutex<HashSet<(u64, u64)>>,
+    /// The canonical paths of the roots of the scan, when following links.
+    roots: RwLock<Vec<PathBuf>>,
+    /// The rules read from the ignore files of each directory that has any, keyed by the directory path.
+    ignore_rules: RwLock<HashMap<PathBuf, Gitignore>>,
+    /// The rules read from the ignore files above the root that is currently being scanned.
+    root_ignore_rules: RwLock<Option<RootIgnoreRules>>,
+    /// The device of the root that is currently being scanned, if it is known.
+    root_device: RwLock<Option<u64>>,
+    /// The errors that have occurred so far.
+    errors: Mutex<Vec<ScanError>>,
+    /// The visitor that is notified of each item, if the scan is streamed.
+    visitor: Option<Arc<dyn ScanVisitor>>,
+}
+
+impl ScanContext {
+    /// Creates a new instance, where `exclude` and `exclude_regex` have been compiled from the exclude patterns in
+    /// `options`.
+    pub(super) fn new(
+        options: ScanOptions,
+        exclude: GlobSet,
+        exclude_regex: RegexSet,
+        should_exit: Arc<AtomicBool>,
+        visitor: Option<Arc<dyn ScanVisitor>>,
+    ) -> Self {
+        ScanContext {
+            options,
+            exclude,
+            exclude_regex,
+            should_exit,
+            counted_hard_links: Mutex::new(HashSet::new()),
+            entered_directories: Mutex::new(HashSet::new()),
+            roots: RwLock::new(vec![]),
+            ignore_rules: RwLock::new(HashMap::new()),
+            root_ignore_rules: RwLock::new(None),
+            root_device: RwLock::new(None),
+            errors: Mutex::new(vec![]),
+            visitor,
+        }
+    }
+
+    /// The options for this scan.
+    #[inline(always)]
+    pub fn options(&self) -> &ScanOptions {
+        &self.options
+    }
+
+    /// Returns `true` if the scan should be stopped as soon as possible.
+    #[inline(always)]
+    pub fn should_exit(&self) -> bool {
+        self.should_exit.load(Ordering::Relaxed)
+    }
+
+    /// The visitor that is notified of each item, if the scan is streamed.
+    #[inline(always)]
+    pub(super) fn visitor(&self) -> Option<&dyn ScanVisitor> {
+        self.visitor.as_deref()
+    }
+
+    /// Records an error that occurred while reading `path`.
+    pub fn report_error(&self, path: &Path, error: &io::Error) {
+        if !self.options.collect_errors && self.visitor.is_none() {
+            return;
+        }
+        let error = ScanError::new(path, error);
+        if let Some(visitor) = self.visitor() {
+            visitor.error(&error);
+        }
+        if self.options.collect_errors {
+            self.errors.lock().unwrap().push(error);
+        }
+    }
+
+    /// Removes and returns the errors that have been reported since the last call.
+    pub fn take_errors(&self) -> Vec<ScanError> {
+        std::mem::take(&mut *self.errors.lock().unwrap())
+    }
+
+    /// Returns `true` if the item at `path` matches any of the exclude glob patterns, by full path or by file name,
+    /// or any of the exclude regular expressions, by full path. Ignored items are also excluded when they are
+    /// skipped, see [`IgnoreFileMode::Skip`].
+    #[inline(always)]
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        self.is_excluded_by_glob(path)
+            || self.is_excluded_by_regex(path)
+            || (self.options.ignore_files == IgnoreFileMode::Skip && self.is_ignored(path))
+    }
+
+    #[inline(always)]
+    fn is_excluded_by_glob(&self, path: &Path) -> bool {
+        if self.exclude.is_empty() {
+            return false;
+        }
+        self.exclude.is_match(path)
+            || path
+                .file_name()
+                .is_some_and(|file_name| self.exclude.is_match(file_name))
+    }
+
+    #[inline(always)]
+    fn is_excluded_by_regex(&self, path: &Path) -> bool {
+        !self.exclude_regex.is_empty() && self.exclude_regex.is_match(&path.to_string_lossy())
+    }
+
+    /// Reads the ignore files in the directory at `path`, if ignore files are used. Must be called before
+    /// checking whether any of the entries in the directory are ignored.
+    pub fn read_ignore_files(&self, path: &Path) {
+        if self.options.ignore_files == IgnoreFileMode::Off {
+            return;
+        }
+        if let Some(rules) = self.build_ignore_rules(path) {
+            self.ignore_rules
+                .write()
+                .unwrap()
+                .insert(path.to_path_buf(), rules);
+        }
+    }
+
+    /// Builds the rules in the ignore files in the directory at `path`, or returns `None` if it has none or none of
+    /// them could be built.
+    fn build_ignore_rules(&self, path: &Path) -> Option<Gitignore> {
+        let mut builder = GitignoreBuilder::new(path);
+        let mut has_ignore_files = false;
+        for file_name in IGNORE_FILE_NAMES {
+            let file_path = path.join(file_name);
+            if !file_path.is_file() {
+                continue;
+            }
+            has_ignore_files = true;
+            // Any valid rules in a file are still added if others are invalid.
+            if let Some(error) = builder.add(&file_path) {
+                self.report_ignore_file_error(&file_path, error);
+            }
+        }
+        if !has_ignore_files {
+            return None;
+        }
+
+        builder
+            .build()
+            .map_err(|error| self.report_ignore_file_error(path, error))
+            .ok()
+    }
+
+    /// Reads the ignore files in the directories from `root` down to the parent of `path`, which must be a
+    /// descendant of `root`. Used to scan part of a tree, e.g. when rescanning a directory.
+    pub fn read_ancestor_ignore_files(&self, root: &Path, path: &Path) {
+        let mut ancestors: Vec<_> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|ancestor| ancestor.starts_with(root))
+            .collect();
+        ancestors.reverse();
+        for ancestor in ancestors {
+            self.read_ignore_files(ancestor);
+        }
+    }
+
+    /// Reads the ignore files in the ancestors of the root at `path`, as their rules also apply to the items in the
+    /// root. Like git, the ancestors are read up to the root of the repository that contains the root, or up to the
+    /// top of the file system if there is none. They are found from the canonical path of the root, so a relative
+    /// root such as `.` has the same ancestors as its absolute path. Must be called before scanning each root,
+    /// including when rescanning a directory.
+    pub fn read_root_ignore_files(&self, path: &Path) {
+        *self.root_ignore_rules.write().unwrap() = None;
+        if self.options.ignore_files == IgnoreFileMode::Off {
+            return;
+        }
+        let Ok(canonical_root) = fs::canonicalize(path) else {
+            return;
+        };
+        if is_repository_root(&canonical_root) {
+            return;
+        }
+
+        let mut rules = vec![];
+        for ancestor in canonical_root.ancestors().skip(1) {
+            rules.extend(self.build_ignore_rules(ancestor));
+            if is_repository_root(ancestor) {
+                break;
+            }
+        }
+        if !rules.is_empty() {
+            *self.root_ignore_rules.write().unwrap() = Some(RootIgnoreRules {
+                root: path.to_path_buf(),
+                canonical_root,
+                rules,
+            });
+        }
+    }
+
+    /// Returns `true` if ignore files are used and the item at `path`, or the directory that contains it, matches
+    /// the rules in the ignore files that have been read. The rules of the closest directory take precedence.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        if self.options.ignore_files == IgnoreFileMode::Off {
+            return false;
+        }
+        let ignore_rules = self.ignore_rules.read().unwrap();
+        let root_ignore_rules = self.root_ignore_rules.read().unwrap();
+        // The rules above the root are matched against the canonical path, as they were read from its ancestors.
+        let canonical_path = root_ignore_rules
+            .as_ref()
+            .and_then(|root_rules| root_rules.to_canonical_path(path));
+        if ignore_rules.is_empty() && canonical_path.is_none() {
+            return false;
+        }
+
+        let rules = path
+            .ancestors()
+            .skip(1)
+            .filter_map(|ancestor| ignore_rules.get(ancestor))
+            .map(|rules| (rules, path));
+        let root_rules = canonical_path.iter().flat_map(|canonical_path| {
+            root_ignore_rules
+                .iter()
+                .flat_map(|root_rules| &root_rules.rules)
+                .map(move |rules| (rules, canonical_path.as_path()))
+        });
+        let mut is_dir = None;
+        for (rules, path) in rules.chain(root_rules) {
+            let is_dir = *is_dir.get_or_insert_with(|| {
+                fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_dir())
+            });
+            match rules.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    fn report_ignore_file_error(&self, path: &Path, error: ignore::Error) {
+        self.report_error(
+            path,
+            &io::Error::new(io::ErrorKind::InvalidData, error.to_string()),
+        );
+    }
+
+    /// Records that the directory with the given metadata is being entered. Returns `false` if links are being
+    /// followed and the directory has already been entered, in which case it should not be entered again.
+    #[inline(always)]
+    pub fn enter_directory(&self, metadata: &fs::Metadata) -> bool {
+        if !self.options.follow_symlinks {
+            return true;
+        }
+        match get_file_key(metadata) {
+            Some(key) => self.entered_directories.lock().unwrap().insert(key),
+            None => true,
+        }
+    }
+
+    /// Records the roots at `paths`, if links are being followed, so that links to the directories within any of them
+    /// are not followed, see [`is_link_into_roots`]. Should be called with all the roots before scanning any of them.
+    ///
+    /// [`is_link_into_roots`]: ScanContext::is_link_into_roots
+    pub fn add_roots(&self, paths: &[PathBuf]) {
+        if !self.options.follow_symlinks {
+            return;
+        }
+        let mut roots = self.roots.write().unwrap();
+        for path in paths {
+            if let Ok(root) = fs::canonicalize(path) {
+                if !roots.contains(&root) {
+                    roots.push(root);
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if links are being followed and the target of the link at `path` is within one of the roots.
+    /// Such a link is not followed, as its target is counted when the root is scanned, so that the result doesn't
+    /// depend on whether the link or its target is reached first.
+    pub fn is_link_into_roots(&self, path: &Path) -> bool {
+        if !self.options.follow_symlinks {
+            return false;
+        }
+        let roots = self.roots.read().unwrap();
+        !roots.is_empty()
+            && fs::canonicalize(path)
+                .is_ok_and(|target| roots.iter().any(|root| target.starts_with(root)))
+    }
+
+    /// Records the device of the root at `path`, which subsequent calls to `is_mount_point` compare against.
+    /// Must be called before scanning each root when staying on one file system. The root is also added to the
+    /// roots, see [`add_roots`].
+    ///
+    /// [`add_roots`]: ScanContext::add_roots
+    pub fn set_root(&self, path: &Path) {
+        self.add_roots(&[path.to_path_buf()]);
+        if self.options.one_file_system {
+            *self.root_device.write().unwrap() =
+                fs::metadata(path).ok().and_then(|m| get_device(&m));
+        }
+    }
+
+    /// Returns `true` if the scan is staying on one file system and the directory with the given metadata is on
+    /// a different device to the current root, in which case it should not be descended into.
+    #[inline(always)]
+    pub fn is_mount_point(&self, metadata: &fs::Metadata) -> bool {
+        if !self.options.one_file_system || !metadata.is_dir() {
+            return false;
+        }
+        match (*self.root_device.read().unwrap(), get_device(metadata)) {
+            (Some(root_device), Some(device)) => root_device != device,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if hard links are being deduplicated and the file with the given metadata is a hard link
+    /// to a file that has already been counted. The first call for any given file returns `false`.
+    #[inline(always)]
+    pub fn is_duplicate_hard_link(&self, metadata: &fs::Metadata) -> bool {
+        if !self.options.deduplicate_hard_links {
+            return false;
+        }
+        if get_link_count(metadata) < 2 {
+            return false;
+        }
+        match get_file_key(metadata) {
+            Some(key) => !self.counted_hard_links.lock().unwrap().insert(key),
+            None => false,
+        }
+    }
+}
+
+/// The rules in the ignore files above a root, see [`ScanContext::read_root_ignore_files`].
+#[derive(Debug)]
+struct RootIgnoreRules {
+    /// The path of the root, as it is scanned.
+    root: PathBuf,
+    canonical_root: PathBuf,
+    /// The rules of each ancestor of the root that has any, from the closest.
+    rules: Vec<Gitignore>,
+}
+
+impl RootIgnoreRules {
+    /// Returns the canonical path of the item at `path`, if it is within the root.
+    fn to_canonical_path(&self, path: &Path) -> Option<PathBuf> {
+        let relative_path = path.strip_prefix(&self.root).ok()?;
+        Some(self.canonical_root.join(relative_path))
+    }
+}
+
+/// Returns `true` if the directory at `path` is the root of a git repository, i.e. it contains `.git`, which is a
+/// file rather than a directory in a worktree or submodule.
+fn is_repository_root(path: &Path) -> bool {
+    path.join(".git").exists()
+}
+
+/// Returns the (device, inode) pair that identifies the file with the given metadata.
+#[cfg(unix)]
+#[inline(always)]
+fn get_file_key(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// The file identity is not available via the standard library on this platform, so hard links and
+/// directory cycles cannot be detected.
+#[cfg(not(unix))]
+#[inline(always)]
+fn get_file_key(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Returns the number of hard links to the file with the given metadata.
+#[cfg(unix)]
+#[inline(always)]
+fn get_link_count(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink()
+}
+
+/// The link count is not available via the standard library on this platform.
+#[cfg(not(unix))]
+#[inline(always)]
+fn get_link_count(_metadata: &fs::Metadata) -> u64 {
+    1
+}
+
+/// Returns the ID of the device that contains the file with the given metadata.
+#[cfg(unix)]
+#[inline(always)]
+fn get_device(metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev())
+}
+
+/// The device ID is not available via the standard library on this platform, so mount points cannot be
+/// detected.
+#[cfg(not(unix))]
+#[inline(always)]
+fn get_device(_metadata: &fs::Metadata) -> Option<u64> {
+    None
+}
@@ -0,0 +1,324 @@
+use super::ScanContext;
+use crate::{
+    test_directory_utils::{create_test_directory_tree, delete_test_directory_tree},
+    IgnoreFileMode, ScanOptions, Scanner,
+};
+use rstest::rstest;
+use std::{
+    fs,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+fn make_context(deduplicate_hard_links: bool) -> ScanContext {
+    make_context_with_options(ScanOptions::default().deduplicate_hard_links(deduplicate_hard_links))
+}
+
+fn make_context_with_options(options: ScanOptions) -> ScanContext {
+    Scanner::new(options)
+        .unwrap()
+        .create_context(Arc::new(AtomicBool::new(false)))
+}
+
+#[rstest]
+fn should_exit_reflects_flag() {
+    // Arrange
+    let should_exit = Arc::new(AtomicBool::new(false));
+    let context = Scanner::default().create_context(should_exit.clone());
+
+    // Act
+    should_exit.store(true, std::sync::atomic::Ordering::Relaxed);
+
+    // Assert
+    assert!(context.should_exit());
+}
+
+#[rstest]
+fn is_duplicate_hard_link_given_file_with_single_link_returns_false() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let metadata = fs::symlink_metadata(temp_dir.join("1").join("1.1"))?;
+    let context = make_context(true);
+
+    // Act
+    let first = context.is_duplicate_hard_link(&metadata);
+    let second = context.is_duplicate_hard_link(&metadata);
+
+    // Assert
+    assert!(!first);
+    assert!(!second);
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+#[case(true, true)]
+#[case(false, false)]
+fn is_duplicate_hard_link_given_second_link_to_file_returns_whether_deduplicating(
+    #[case] deduplicate_hard_links: bool,
+    #[case] expected_duplicate: bool,
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let original = temp_dir.join("1").join("1.1");
+    let link = temp_dir.join("1").join("1.1.link");
+    fs::hard_link(&original, &link)?;
+    let context = make_context(deduplicate_hard_links);
+
+    // Act
+    let first = context.is_duplicate_hard_link(&fs::symlink_metadata(&original)?);
+    let second = context.is_duplicate_hard_link(&fs::symlink_metadata(&link)?);
+
+    // Assert
+    assert!(!first);
+    assert_eq!(expected_duplicate, second);
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+#[case(false)]
+#[case(true)]
+fn is_mount_point_given_directory_on_same_device_returns_false(#[case] one_file_system: bool) {
+    // Arrange
+    let temp_dir = create_test_directory_tree().unwrap();
+    let context =
+        make_context_with_options(ScanOptions::default().one_file_system(one_file_system));
+    context.set_root(&temp_dir);
+
+    // Act
+    let is_mount_point = context.is_mount_point(&fs::metadata(temp_dir.join("1")).unwrap());
+
+    // Assert
+    assert!(!is_mount_point);
+
+    delete_test_directory_tree(&temp_dir);
+}
+
+#[test]
+fn take_errors_returns_reported_errors_once() {
+    // Arrange
+    let context = make_context(false);
+    let error = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+    context.report_error(std::path::Path::new("/some/dir"), &error);
+
+    // Act
+    let errors = context.take_errors();
+
+    // Assert
+    assert_eq!(1, errors.len());
+    assert_eq!(std::path::PathBuf::from("/some/dir"), errors[0].path);
+    assert_eq!(std::io::ErrorKind::PermissionDenied, errors[0].kind);
+    assert!(context.take_errors().is_empty());
+}
+
+#[rstest]
+#[case("*.log", "/some/dir/file.log", true)]
+#[case("*.log", "/some/dir/file.txt", false)]
+#[case("node_modules", "/some/node_modules", true)]
+#[case("**/dir/*.txt", "/some/dir/file.txt", true)]
+#[case("**/dir/*.txt", "/other/file.txt", false)]
+fn is_excluded_matches_full_path_or_file_name(
+    #[case] pattern: &str,
+    #[case] path: &str,
+    #[case] expected: bool,
+) {
+    // Arrange
+    let context = make_context_with_options(ScanOptions::default().exclude(pattern));
+
+    // Act
+    let is_excluded = context.is_excluded(std::path::Path::new(path));
+
+    // Assert
+    assert_eq!(expected, is_excluded);
+}
+
+#[rstest]
+#[case(r"\.log$", "/some/dir/file.log", true)]
+#[case(r"\.log$", "/some/dir/file.log.txt", false)]
+#[case("^/proc(/|$)", "/proc", true)]
+#[case("^/proc(/|$)", "/some/proc", false)]
+#[case("/dir/", "/some/dir/file.txt", true)]
+fn is_excluded_matches_regex_against_full_path(
+    #[case] pattern: &str,
+    #[case] path: &str,
+    #[case] expected: bool,
+) {
+    // Arrange
+    let context = make_context_with_options(ScanOptions::default().exclude_regex(pattern));
+
+    // Act
+    let is_excluded = context.is_excluded(std::path::Path::new(path));
+
+    // Assert
+    assert_eq!(expected, is_excluded);
+}
+
+#[rstest]
+#[case(false, true)]
+#[case(true, false)]
+fn enter_directory_given_directory_entered_before_returns_expected(
+    #[case] follow_symlinks: bool,
+    #[case] expected: bool,
+) {
+    // Arrange
+    let temp_dir = create_test_directory_tree().unwrap();
+    let metadata = fs::metadata(&temp_dir).unwrap();
+    let context =
+        make_context_with_options(ScanOptions::default().follow_symlinks(follow_symlinks));
+    assert!(context.enter_directory(&metadata));
+
+    // Act
+    let entered = context.enter_directory(&metadata);
+
+    // Assert
+    assert_eq!(expected, entered);
+
+    delete_test_directory_tree(&temp_dir);
+}
+
+#[rstest]
+#[case("", false)]
+#[case("*.log\n", true)]
+#[case("*.log\n!keep.log\n", false)]
+fn is_ignored_applies_rules_of_ignore_files_that_have_been_read(
+    #[case] gitignore: &str,
+    #[case] expected: bool,
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    fs::create_dir_all(&temp_dir)?;
+    fs::write(temp_dir.join(".gitignore"), gitignore)?;
+    let context =
+        make_context_with_options(ScanOptions::default().ignore_files(IgnoreFileMode::Tag));
+    let path = temp_dir.join("keep.log");
+    assert!(!context.is_ignored(&path));
+
+    // Act
+    context.read_ignore_files(&temp_dir);
+
+    // Assert
+    assert_eq!(expected, context.is_ignored(&path));
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
+
+#[rstest]
+#[case(IgnoreFileMode::Off, false)]
+#[case(IgnoreFileMode::Skip, true)]
+#[case(IgnoreFileMode::Tag, false)]
+fn is_excluded_only_excludes_ignored_items_when_skipped(
+    #[case] ignore_files: IgnoreFileMode,
+    #[case] expected: bool,
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    fs::create_dir_all(&temp_dir)?;
+    fs::write(temp_dir.join(".ignore"), "*.log\n")?;
+    let context = make_context_with_options(ScanOptions::default().ignore_files(ignore_files));
+    context.read_ignore_files(&temp_dir);
+
+    // Act
+    let is_excluded = context.is_excluded(&temp_dir.join("a.log"));
+
+    // Assert
+    assert_eq!(expected, is_excluded);
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
+
+#[rstest]
+fn is_ignored_gives_precedence_to_spaceignore_and_closest_directory() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let sub_dir = temp_dir.join("sub");
+    fs::create_dir_all(&sub_dir)?;
+    fs::write(temp_dir.join(".gitignore"), "*.log\n*.tmp\n")?;
+    fs::write(temp_dir.join(".spaceignore"), "!*.tmp\n")?;
+    fs::write(sub_dir.join(".gitignore"), "!keep.log\n")?;
+    let context =
+        make_context_with_options(ScanOptions::default().ignore_files(IgnoreFileMode::Tag));
+
+    // Act
+    context.read_ignore_files(&temp_dir);
+    context.read_ignore_files(&sub_dir);
+
+    // Assert
+    assert!(context.is_ignored(&sub_dir.join("other.log")));
+    assert!(!context.is_ignored(&sub_dir.join("keep.log")));
+    assert!(!context.is_ignored(&temp_dir.join("a.tmp")));
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
+
+#[rstest]
+fn read_ancestor_ignore_files_reads_files_from_root_down_to_parent() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let path = temp_dir.join("a").join("b");
+    fs::create_dir_all(&path)?;
+    fs::write(temp_dir.join(".gitignore"), "*.log\n")?;
+    let context =
+        make_context_with_options(ScanOptions::default().ignore_files(IgnoreFileMode::Tag));
+
+    // Act
+    context.read_ancestor_ignore_files(&temp_dir, &path);
+
+    // Assert
+    assert!(context.is_ignored(&path.join("c.log")));
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
+
+#[rstest]
+fn read_root_ignore_files_reads_files_up_to_repository_root() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let repository_dir = temp_dir.join("repository");
+    let path = repository_dir.join("a").join("b");
+    fs::create_dir_all(&path)?;
+    fs::create_dir_all(repository_dir.join(".git"))?;
+    fs::write(temp_dir.join(".gitignore"), "*.log\n")?;
+    fs::write(repository_dir.join(".gitignore"), "*.tmp\n")?;
+    let context =
+        make_context_with_options(ScanOptions::default().ignore_files(IgnoreFileMode::Tag));
+
+    // Act
+    context.read_root_ignore_files(&path);
+
+    // Assert
+    assert!(context.is_ignored(&path.join("c.tmp")));
+    assert!(!context.is_ignored(&path.join("c.log")));
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
+
+#[rstest]
+fn read_root_ignore_files_given_non_canonical_root_reads_files_of_canonical_ancestors(
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let path = temp_dir.join("a").join("b");
+    fs::create_dir_all(&path)?;
+    fs::write(temp_dir.join(".gitignore"), "/a/b/*.log\n")?;
+    let context =
+        make_context_with_options(ScanOptions::default().ignore_files(IgnoreFileMode::Tag));
+    let root = path.join("..").join("b");
+
+    // Act
+    context.read_root_ignore_files(&root);
+
+    // Assert
+    assert!(context.is_ignored(&root.join("c.log")));
+    assert!(!context.is_ignored(&root.join("c.tmp")));
+
+    fs::remove_dir_all(&temp_dir)?;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Defines the errors that are collected while scanning directory trees.
+
+use std::{
+    fmt::{self, Display},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// An error that occurred while scanning part of a directory tree. The size of the tree containing `path` will
+/// have been undercounted by the size of whatever could not be read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScanError {
+    /// The path of the file or directory that could not be read.
+    pub path: PathBuf,
+    /// The kind of I/O error that occurred.
+    pub kind: io::ErrorKind,
+    /// The description of the error.
+    pub message: String,
+}
+
+impl ScanError {
+    /// Creates a new instance from the I/O error that occurred while reading `path`.
+    pub fn new(path: &Path, error: &io::Error) -> Self {
+        ScanError {
+            path: path.to_path_buf(),
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
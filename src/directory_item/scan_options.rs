@@ -0,0 +1,171 @@
+//! Defines the options that control how directory trees are scanned.
+
+use super::{Scanner, ScannerError};
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// How the ignore files in the scanned directories, i.e. `.gitignore`, `.ignore` and `.spaceignore`, are used.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum IgnoreFileMode {
+    /// Ignore files are not read.
+    #[default]
+    Off,
+    /// Items that match the ignore files are skipped, as if they were excluded.
+    Skip,
+    /// Items that match the ignore files are scanned, but tagged as ignored, so that the ignored share of the
+    /// size of each directory is known.
+    Tag,
+}
+
+/// The options that control how directory trees are scanned. Use the builder methods to configure the options
+/// and then call [`build`] to create a [`Scanner`], e.g.
+///
+/// ```
+/// use space_rs::ScanOptions;
+///
+/// let scanner = ScanOptions::default()
+///     .max_depth(2)
+///     .one_file_system(true)
+///     .exclude("*.tmp")
+///     .threads(4)
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// [`build`]: ScanOptions::build
+#[derive(Clone, Debug)]
+pub struct ScanOptions {
+    /// If `true` then the size of a file with multiple hard links is only counted once, for the first link
+    /// encountered. Any further links to the same file are reported as [`DirectoryItemType::HardLink`] items
+    /// with a size of zero. Which of the links is encountered first depends on the order in which the tree is
+    /// traversed in parallel. Only supported on Unix platforms.
+    ///
+    /// [`DirectoryItemType::HardLink`]: crate::DirectoryItemType::HardLink
+    pub deduplicate_hard_links: bool,
+    /// If `true` then directories that are on a different file system (device) to the root being scanned are
+    /// not descended into. They are reported as [`DirectoryItemType::MountPoint`] items with a size of zero.
+    /// Only supported on Unix platforms.
+    ///
+    /// [`DirectoryItemType::MountPoint`]: crate::DirectoryItemType::MountPoint
+    pub one_file_system: bool,
+    /// The maximum depth of the items that are returned, where the root is at depth 0. Directories at the
+    /// maximum depth are still scanned in full, so their sizes are correct, but their descendants are
+    /// discarded. `None` means there is no limit.
+    pub max_depth: Option<usize>,
+    /// If `true` then symbolic links (and Windows junction points) are followed and the size of the target is
+    /// counted. Each directory is only descended into once, which prevents cycles. Links to directories within
+    /// the roots are not followed, as those directories are counted via their own paths.
+    pub follow_symlinks: bool,
+    /// The glob patterns of the items to exclude from the scan. A pattern is matched against both the full
+    /// path and the file name of each item, so `*.log` and `**/node_modules` both work as expected. Excluded
+    /// items are neither listed nor counted. The root paths are never excluded.
+    pub exclude_patterns: Vec<String>,
+    /// The regular expressions of the items to exclude from the scan, which are matched against the full path
+    /// of each item, e.g. `/\.snapshot$`. Excluded items are neither listed nor counted. The root paths are
+    /// never excluded.
+    pub exclude_regex_patterns: Vec<String>,
+    /// How the ignore files are used. The rules in the `.gitignore`, `.ignore` and `.spaceignore` files of each
+    /// directory, and of the ancestors of each root, are applied with gitignore semantics. Rules in a deeper
+    /// directory take precedence, as do the rules in `.ignore` over `.gitignore`, and in `.spaceignore` over
+    /// both. The root paths are never skipped.
+    pub ignore_files: IgnoreFileMode,
+    /// The number of threads used to scan. `None` uses the global thread pool, which has one thread per
+    /// logical CPU by default.
+    pub threads: Option<usize>,
+    /// If `true` then the errors that occur while scanning are collected and returned in
+    /// [`ScanResult::errors`].
+    ///
+    /// [`ScanResult::errors`]: crate::ScanResult::errors
+    pub collect_errors: bool,
+    /// If `true` then the timestamps, ownership and permissions of each item are collected, see
+    /// [`DirectoryItem::metadata`]. Off by default, as it uses more memory.
+    ///
+    /// [`DirectoryItem::metadata`]: crate::DirectoryItem::metadata
+    pub collect_metadata: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        ScanOptions {
+            deduplicate_hard_links: false,
+            one_file_system: false,
+            max_depth: None,
+            follow_symlinks: false,
+            exclude_patterns: vec![],
+            exclude_regex_patterns: vec![],
+            ignore_files: IgnoreFileMode::Off,
+            threads: None,
+            collect_errors: true,
+            collect_metadata: false,
+        }
+    }
+}
+
+impl ScanOptions {
+    /// Sets whether to count the size of a file with multiple hard links only once.
+    pub fn deduplicate_hard_links(mut self, deduplicate_hard_links: bool) -> Self {
+        self.deduplicate_hard_links = deduplicate_hard_links;
+        self
+    }
+
+    /// Sets whether to skip directories that are on a different file system to the root.
+    pub fn one_file_system(mut self, one_file_system: bool) -> Self {
+        self.one_file_system = one_file_system;
+        self
+    }
+
+    /// Sets the maximum depth of the items that are returned.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets whether to follow symbolic links.
+    pub fn follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Adds a glob pattern of items to exclude from the scan.
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude_patterns.push(pattern.into());
+        self
+    }
+
+    /// Adds a regular expression of items to exclude from the scan.
+    pub fn exclude_regex(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude_regex_patterns.push(pattern.into());
+        self
+    }
+
+    /// Sets how the ignore files are used.
+    pub fn ignore_files(mut self, ignore_files: IgnoreFileMode) -> Self {
+        self.ignore_files = ignore_files;
+        self
+    }
+
+    /// Sets the number of threads used to scan.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Sets whether to collect the errors that occur while scanning.
+    pub fn collect_errors(mut self, collect_errors: bool) -> Self {
+        self.collect_errors = collect_errors;
+        self
+    }
+
+    /// Sets whether to collect the timestamps, ownership and permissions of each item.
+    pub fn collect_metadata(mut self, collect_metadata: bool) -> Self {
+        self.collect_metadata = collect_metadata;
+        self
+    }
+
+    /// Creates a scanner that uses these options.
+    pub fn build(self) -> Result<Scanner, ScannerError> {
+        Scanner::new(self)
+    }
+}
@@ -0,0 +1,12 @@
+//! Defines the result of scanning one or more directory trees.
+
+use super::{DirectoryItem, ScanError};
+
+/// The result of scanning one or more directory trees.
+#[derive(Debug, Default)]
+pub struct ScanResult {
+    /// The root items, one per scanned path.
+    pub items: Vec<DirectoryItem>,
+    /// The errors that occurred while scanning, in no particular order.
+    pub errors: Vec<ScanError>,
+}
@@ -0,0 +1,41 @@
+//! Defines a versioned snapshot of scanned directory trees, so that they can be persisted and shipped between
+//! processes.
+
+use super::DirectoryItem;
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// The version of the schema of [`ScanSnapshot`], which is incremented whenever a change is made that older
+/// versions cannot read.
+pub const SCAN_SNAPSHOT_VERSION: u32 = 1;
+
+/// One or more scanned directory trees, along with the version of the schema they were serialized with. Use any
+/// serde data format to persist a snapshot. Deserializing a snapshot with a newer version than
+/// [`SCAN_SNAPSHOT_VERSION`] fails, rather than silently losing data.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ScanSnapshot {
+    /// The version of the schema, see [`SCAN_SNAPSHOT_VERSION`].
+    #[serde(deserialize_with = "deserialize_version")]
+    pub version: u32,
+    /// The root items of the scanned trees.
+    pub items: Vec<DirectoryItem>,
+}
+
+impl ScanSnapshot {
+    /// Creates a snapshot of the given trees, using the current schema version.
+    pub fn new(items: Vec<DirectoryItem>) -> Self {
+        ScanSnapshot {
+            version: SCAN_SNAPSHOT_VERSION,
+            items,
+        }
+    }
+}
+
+fn deserialize_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let version = u32::deserialize(deserializer)?;
+    if version == 0 || version > SCAN_SNAPSHOT_VERSION {
+        return Err(de::Error::custom(format!(
+            "unsupported snapshot version {version}, expected at most {SCAN_SNAPSHOT_VERSION}"
+        )));
+    }
+    Ok(version)
+}
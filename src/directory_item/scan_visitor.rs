@@ -0,0 +1,37 @@
+//! Defines the visitor that is notified of each item while scanning, to stream the results of a scan.
+
+use super::{DirectoryItem, ScanError};
+use std::{fmt, path::Path};
+
+/// Receives each item as it is scanned by [`Scanner::scan_with_visitor`], e.g. to compute custom aggregates over
+/// huge trees without keeping every item in memory.
+///
+/// The methods are called concurrently by the threads of the scan, in no particular order across directories.
+/// Within a directory, [`enter_dir`] is called before any of its entries are visited, and [`leave_dir`] after all
+/// of them have been. The root paths are at depth 0. All methods do nothing by default, so only the required
+/// ones need to be implemented.
+///
+/// [`Scanner::scan_with_visitor`]: crate::Scanner::scan_with_visitor
+/// [`enter_dir`]: ScanVisitor::enter_dir
+/// [`leave_dir`]: ScanVisitor::leave_dir
+pub trait ScanVisitor: Send + Sync {
+    /// Called when the directory at `path` is entered, before any of its entries are visited.
+    fn enter_dir(&self, _path: &Path, _depth: usize) {}
+
+    /// Called for each item that is not a directory that was entered, i.e. files, and also links, mount points and
+    /// items that could not be read. See the type of `item` to tell them apart.
+    fn file(&self, _path: &Path, _depth: usize, _item: &DirectoryItem) {}
+
+    /// Called when all the entries of the directory at `path` have been visited. The sizes and counts of `item`
+    /// are aggregated from all its descendants, and its children are included without their own descendants.
+    fn leave_dir(&self, _path: &Path, _depth: usize, _item: &DirectoryItem) {}
+
+    /// Called for each error that occurs while scanning, whether or not errors are collected.
+    fn error(&self, _error: &ScanError) {}
+}
+
+impl fmt::Debug for dyn ScanVisitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ScanVisitor")
+    }
+}
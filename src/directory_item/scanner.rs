@@ -0,0 +1,179 @@
+//! Defines the scanner, which builds directory trees using a set of scan options.
+
+use super::{DirectoryItem, ScanContext, ScanOptions, ScanResult, ScanVisitor};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use regex::RegexSet;
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+#[cfg(test)]
+#[path = "./scanner_test.rs"]
+mod scanner_test;
+
+/// Builds directory trees using a set of scan options. A scanner can be reused for any number of scans.
+#[derive(Debug)]
+pub struct Scanner {
+    options: ScanOptions,
+    exclude: GlobSet,
+    exclude_regex: RegexSet,
+    thread_pool: Option<ThreadPool>,
+}
+
+/// The errors that can occur when creating a [`Scanner`].
+#[derive(Debug)]
+pub enum ScannerError {
+    /// One of the exclude patterns is not a valid glob.
+    InvalidExcludePattern(globset::Error),
+    /// One of the exclude regular expressions is not valid.
+    InvalidExcludeRegex(regex::Error),
+    /// The thread pool could not be created.
+    ThreadPool(ThreadPoolBuildError),
+}
+
+impl Display for ScannerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScannerError::InvalidExcludePattern(error) => {
+                write!(f, "Invalid exclude pattern: {error}")
+            }
+            ScannerError::InvalidExcludeRegex(error) => {
+                write!(f, "Invalid exclude regex: {error}")
+            }
+            ScannerError::ThreadPool(error) => {
+                write!(f, "Unable to create the scan thread pool: {error}")
+            }
+        }
+    }
+}
+
+impl Error for ScannerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScannerError::InvalidExcludePattern(error) => Some(error),
+            ScannerError::InvalidExcludeRegex(error) => Some(error),
+            ScannerError::ThreadPool(error) => Some(error),
+        }
+    }
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Scanner {
+            options: ScanOptions::default(),
+            exclude: GlobSet::empty(),
+            exclude_regex: RegexSet::empty(),
+            thread_pool: None,
+        }
+    }
+}
+
+impl Scanner {
+    /// Creates a new instance that uses the specified options.
+    pub fn new(options: ScanOptions) -> Result<Self, ScannerError> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &options.exclude_patterns {
+            builder.add(Glob::new(pattern).map_err(ScannerError::InvalidExcludePattern)?);
+        }
+        let exclude = builder
+            .build()
+            .map_err(ScannerError::InvalidExcludePattern)?;
+        let exclude_regex = RegexSet::new(&options.exclude_regex_patterns)
+            .map_err(ScannerError::InvalidExcludeRegex)?;
+
+        let thread_pool = match options.threads {
+            Some(threads) => Some(
+                ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .map_err(ScannerError::ThreadPool)?,
+            ),
+            None => None,
+        };
+
+        Ok(Scanner {
+            options,
+            exclude,
+            exclude_regex,
+            thread_pool,
+        })
+    }
+
+    /// The options used by this scanner.
+    #[inline(always)]
+    pub fn options(&self) -> &ScanOptions {
+        &self.options
+    }
+
+    /// Builds one DirectoryItem tree per path. Duplicate paths are only scanned once.
+    pub fn scan(&self, paths: Vec<PathBuf>, should_exit: &Arc<AtomicBool>) -> ScanResult {
+        self.scan_in_context(paths, self.create_context(should_exit.clone()))
+    }
+
+    /// Scans each path and notifies `visitor` of every item, using the same parallel traversal as [`scan`]. Only
+    /// the items that are still being scanned are kept in memory, so the resulting items are the roots without
+    /// their descendants, with sizes and counts aggregated from all of them. Duplicate paths are only scanned once.
+    ///
+    /// [`scan`]: Scanner::scan
+    pub fn scan_with_visitor(
+        &self,
+        paths: Vec<PathBuf>,
+        visitor: Arc<dyn ScanVisitor>,
+        should_exit: &Arc<AtomicBool>,
+    ) -> ScanResult {
+        let context = ScanContext::new(
+            self.options.clone(),
+            self.exclude.clone(),
+            self.exclude_regex.clone(),
+            should_exit.clone(),
+            Some(visitor),
+        );
+        self.scan_in_context(paths, context)
+    }
+
+    fn scan_in_context(&self, mut paths: Vec<PathBuf>, context: ScanContext) -> ScanResult {
+        paths.sort();
+        paths.dedup();
+        context.add_roots(&paths);
+
+        self.install(|| {
+            let mut items = vec![];
+            for path in paths {
+                items.push(DirectoryItem::from_root_with_context(&path, &context));
+            }
+
+            ScanResult {
+                items,
+                errors: context.take_errors(),
+            }
+        })
+    }
+
+    /// Creates the context for a single scan, which may be used to build trees incrementally via
+    /// [`DirectoryItem::from_root_with_context`] and [`DirectoryItem::build_subtree_with_context`].
+    pub fn create_context(&self, should_exit: Arc<AtomicBool>) -> ScanContext {
+        ScanContext::new(
+            self.options.clone(),
+            self.exclude.clone(),
+            self.exclude_regex.clone(),
+            should_exit,
+            None,
+        )
+    }
+
+    /// Runs `op` in this scanner's thread pool, if it has one, or else in the current thread pool.
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        match &self.thread_pool {
+            Some(thread_pool) => thread_pool.install(op),
+            None => op(),
+        }
+    }
+}
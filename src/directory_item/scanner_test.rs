@@ -0,0 +1,768 @@
+use super::{Scanner, ScannerError};
+use crate::{
+    test_directory_utils::{create_test_directory_tree, delete_test_directory_tree},
+    DescendantCounts, DirectoryItem, DirectoryItemType, IgnoreFileMode, ScanError, ScanOptions,
+    ScanVisitor,
+};
+use rstest::rstest;
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc, Mutex},
+};
+use uuid::Uuid;
+
+const TEST_DIRECTORY_TREE_SIZE: u64 = 180000;
+
+fn scan(options: ScanOptions, path: &Path) -> DirectoryItem {
+    let should_exit = Arc::new(AtomicBool::new(false));
+    let mut result = options
+        .build()
+        .unwrap()
+        .scan(vec![path.to_path_buf()], &should_exit);
+    assert_eq!(1, result.items.len());
+    result.items.remove(0)
+}
+
+/// Creates a tree with a `.gitignore` in the root that ignores `target/` (6000 bytes), and a `.spaceignore` in
+/// `sub` that ignores `build` (1000 bytes). The total size is 11014 bytes, including the ignore files.
+fn create_ignore_test_tree() -> anyhow::Result<std::path::PathBuf> {
+    let temp_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    fs::create_dir_all(temp_dir.join("src"))?;
+    fs::create_dir_all(temp_dir.join("target"))?;
+    fs::create_dir_all(temp_dir.join("sub").join("build"))?;
+    fs::write(temp_dir.join(".gitignore"), "target/\n")?;
+    fs::write(temp_dir.join("src").join("a.rs"), vec![0u8; 3000])?;
+    fs::write(temp_dir.join("target").join("bin"), vec![0u8; 6000])?;
+    fs::write(temp_dir.join("sub").join(".spaceignore"), "build\n")?;
+    fs::write(
+        temp_dir.join("sub").join("build").join("x"),
+        vec![0u8; 1000],
+    )?;
+    fs::write(temp_dir.join("sub").join("keep"), vec![0u8; 1000])?;
+    Ok(temp_dir)
+}
+
+/// The calls made to a [`RecordingVisitor`].
+#[derive(Debug, PartialEq)]
+enum VisitorEvent {
+    EnterDir(PathBuf, usize),
+    File(PathBuf, usize, u64),
+    LeaveDir(PathBuf, usize, u64, usize),
+    Error(PathBuf),
+}
+
+/// Records every call made by a scan, with the size and, for directories, the descendant count of each item.
+#[derive(Debug, Default)]
+struct RecordingVisitor {
+    events: Mutex<Vec<VisitorEvent>>,
+}
+
+impl RecordingVisitor {
+    fn take_events(&self) -> Vec<VisitorEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+}
+
+impl ScanVisitor for RecordingVisitor {
+    fn enter_dir(&self, path: &Path, depth: usize) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(VisitorEvent::EnterDir(path.to_path_buf(), depth));
+    }
+
+    fn file(&self, path: &Path, depth: usize, item: &DirectoryItem) {
+        self.events.lock().unwrap().push(VisitorEvent::File(
+            path.to_path_buf(),
+            depth,
+            item.size_in_bytes.get_value(),
+        ));
+    }
+
+    fn leave_dir(&self, path: &Path, depth: usize, item: &DirectoryItem) {
+        self.events.lock().unwrap().push(VisitorEvent::LeaveDir(
+            path.to_path_buf(),
+            depth,
+            item.size_in_bytes.get_value(),
+            item.descendant_count,
+        ));
+    }
+
+    fn error(&self, error: &ScanError) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(VisitorEvent::Error(error.path.clone()));
+    }
+}
+
+fn find_child<'a>(item: &'a DirectoryItem, path_segment: &str) -> Option<&'a DirectoryItem> {
+    item.children
+        .iter()
+        .find(|c| c.path_segment == path_segment)
+}
+
+#[rstest]
+fn scan_given_duplicate_paths_returns_one_item_per_unique_path() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    // Act
+    let result = Scanner::default().scan(vec![temp_dir.clone(), temp_dir.clone()], &should_exit);
+
+    // Assert
+    assert_eq!(1, result.items.len());
+    assert_eq!(
+        TEST_DIRECTORY_TREE_SIZE,
+        result.items[0].size_in_bytes.get_value()
+    );
+    assert!(result.errors.is_empty());
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_given_max_depth_keeps_sizes_but_discards_deeper_items() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+
+    // Act
+    let item = scan(ScanOptions::default().max_depth(1), &temp_dir);
+
+    // Assert
+    assert_eq!(TEST_DIRECTORY_TREE_SIZE, item.size_in_bytes.get_value());
+    assert_eq!(28, item.descendant_count);
+    let d1 = find_child(&item, "1").unwrap();
+    assert!(d1.children.is_empty());
+    assert_eq!(TEST_DIRECTORY_TREE_SIZE, d1.size_in_bytes.get_value());
+    assert_eq!(27, d1.descendant_count);
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_given_max_depth_of_zero_returns_root_only() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+
+    // Act
+    let item = scan(ScanOptions::default().max_depth(0), &temp_dir);
+
+    // Assert
+    assert!(item.children.is_empty());
+    assert_eq!(TEST_DIRECTORY_TREE_SIZE, item.size_in_bytes.get_value());
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("1.5")]
+#[case("**/1/1.5")]
+fn scan_given_exclude_pattern_skips_matching_items(#[case] pattern: &str) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+
+    // Act
+    let item = scan(ScanOptions::default().exclude(pattern), &temp_dir);
+
+    // Assert
+    let d1 = find_child(&item, "1").unwrap();
+    assert!(find_child(d1, "1.5").is_none());
+    assert_eq!(
+        TEST_DIRECTORY_TREE_SIZE - 18000,
+        item.size_in_bytes.get_value()
+    );
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_given_exclude_regex_skips_matching_items() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+
+    // Act
+    let item = scan(
+        ScanOptions::default().exclude_regex(r"[/\\]1\.5$"),
+        &temp_dir,
+    );
+
+    // Assert
+    let d1 = find_child(&item, "1").unwrap();
+    assert!(find_child(d1, "1.5").is_none());
+    assert_eq!(
+        TEST_DIRECTORY_TREE_SIZE - 18000,
+        item.size_in_bytes.get_value()
+    );
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_given_exclude_pattern_that_matches_root_still_scans_root() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+
+    // Act
+    let item = scan(ScanOptions::default().exclude("space_*"), &temp_dir);
+
+    // Assert
+    assert_eq!(TEST_DIRECTORY_TREE_SIZE, item.size_in_bytes.get_value());
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn new_given_invalid_exclude_pattern_returns_error() {
+    // Act
+    let result = Scanner::new(ScanOptions::default().exclude("a[b"));
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(ScannerError::InvalidExcludePattern(_))
+    ));
+}
+
+#[rstest]
+fn new_given_invalid_exclude_regex_returns_error() {
+    // Act
+    let result = Scanner::new(ScanOptions::default().exclude_regex("a(b"));
+
+    // Assert
+    assert!(matches!(result, Err(ScannerError::InvalidExcludeRegex(_))));
+}
+
+#[rstest]
+fn scan_given_threads_returns_same_size() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+
+    // Act
+    let item = scan(ScanOptions::default().threads(2), &temp_dir);
+
+    // Assert
+    assert_eq!(TEST_DIRECTORY_TREE_SIZE, item.size_in_bytes.get_value());
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(true, 1)]
+#[case(false, 0)]
+fn scan_given_non_existent_path_returns_errors_if_collected(
+    #[case] collect_errors: bool,
+    #[case] expected_error_count: usize,
+) {
+    // Arrange
+    let path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    let should_exit = Arc::new(AtomicBool::new(false));
+    let scanner = Scanner::new(ScanOptions::default().collect_errors(collect_errors)).unwrap();
+
+    // Act
+    let result = scanner.scan(vec![path.clone()], &should_exit);
+
+    // Assert
+    assert_eq!(1, result.items.len());
+    assert_eq!(DirectoryItemType::Unknown, result.items[0].item_type);
+    assert_eq!(expected_error_count, result.errors.len());
+    if let Some(error) = result.errors.first() {
+        assert_eq!(path, error.path);
+        assert_eq!(ErrorKind::NotFound, error.kind);
+        assert!(!error.message.is_empty());
+    }
+}
+
+#[cfg(unix)]
+#[rstest]
+fn scan_given_fifo_builds_leaf_item_without_errors() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    fs::create_dir(&temp_dir)?;
+    fs::write(temp_dir.join("f"), vec![0u8; 100])?;
+    assert!(std::process::Command::new("mkfifo")
+        .arg(temp_dir.join("pipe"))
+        .status()?
+        .success());
+
+    // Act
+    let result = ScanOptions::default()
+        .build()?
+        .scan(vec![temp_dir.clone()], &Arc::new(AtomicBool::new(false)));
+
+    // Assert
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let item = &result.items[0];
+    assert_eq!(100, item.size_in_bytes.get_value());
+    let pipe = find_child(item, "pipe").unwrap();
+    assert_eq!(DirectoryItemType::Unknown, pipe.item_type);
+    assert!(pipe.children.is_empty());
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+fn scan_given_fifo_and_socket_counts_each_once_as_other() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    fs::create_dir_all(temp_dir.join("sub"))?;
+    fs::write(temp_dir.join("sub").join("f"), vec![0u8; 100])?;
+    assert!(std::process::Command::new("mkfifo")
+        .arg(temp_dir.join("pipe"))
+        .status()?
+        .success());
+    let _listener = std::os::unix::net::UnixListener::bind(temp_dir.join("socket"))?;
+
+    // Act
+    let item = scan(ScanOptions::default(), &temp_dir);
+
+    // Assert
+    assert_eq!(
+        DescendantCounts {
+            files: 1,
+            directories: 1,
+            symlinks: 0,
+            other: 2,
+        },
+        item.descendant_counts
+    );
+    assert_eq!(4, item.descendant_count);
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+fn scan_given_follow_symlinks_counts_each_directory_once() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+
+    // Act
+    let item = scan(ScanOptions::default().follow_symlinks(true), &temp_dir);
+
+    // Assert
+    assert_eq!(TEST_DIRECTORY_TREE_SIZE, item.size_in_bytes.get_value());
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+fn scan_given_follow_symlinks_and_link_sorting_before_its_target_counts_target_as_directory(
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    fs::create_dir_all(temp_dir.join("z_target"))?;
+    fs::write(temp_dir.join("z_target").join("f"), vec![0u8; 100])?;
+    std::os::unix::fs::symlink(temp_dir.join("z_target"), temp_dir.join("a_link"))?;
+
+    // Act
+    let item = scan(ScanOptions::default().follow_symlinks(true), &temp_dir);
+
+    // Assert
+    assert_eq!(100, item.size_in_bytes.get_value());
+    let target = find_child(&item, "z_target").unwrap();
+    assert_eq!(DirectoryItemType::Directory, target.item_type);
+    assert_eq!(100, target.size_in_bytes.get_value());
+    let link = find_child(&item, "a_link").unwrap();
+    assert_eq!(DirectoryItemType::SymbolicLink, link.item_type);
+    assert_eq!(Some(temp_dir.join("z_target")), link.link_target);
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+fn scan_given_follow_symlinks_and_link_to_ancestor_does_not_loop() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    fs::create_dir_all(temp_dir.join("a"))?;
+    fs::write(temp_dir.join("a").join("f"), vec![0u8; 100])?;
+    std::os::unix::fs::symlink(&temp_dir, temp_dir.join("a").join("loop"))?;
+
+    // Act
+    let item = scan(ScanOptions::default().follow_symlinks(true), &temp_dir);
+
+    // Assert
+    assert_eq!(100, item.size_in_bytes.get_value());
+    let link = find_child(find_child(&item, "a").unwrap(), "loop").unwrap();
+    assert_eq!(DirectoryItemType::SymbolicLink, link.item_type);
+    assert_eq!(Some(temp_dir.clone()), link.link_target);
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+#[case(false, 0, None)]
+#[case(true, 100, Some(DirectoryItemType::Directory))]
+fn scan_given_link_to_directory_outside_root_counts_target_only_when_following(
+    #[case] follow_symlinks: bool,
+    #[case] expected_size: u64,
+    #[case] expected_link_item_type: Option<DirectoryItemType>,
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    let volume_dir = temp_dir.join("volume");
+    let root_dir = temp_dir.join("root");
+    fs::create_dir_all(&volume_dir)?;
+    fs::create_dir_all(&root_dir)?;
+    fs::write(volume_dir.join("f"), vec![0u8; 100])?;
+    std::os::unix::fs::symlink(&volume_dir, root_dir.join("data"))?;
+
+    // Act
+    let item = scan(
+        ScanOptions::default().follow_symlinks(follow_symlinks),
+        &root_dir,
+    );
+
+    // Assert
+    assert_eq!(expected_size, item.size_in_bytes.get_value());
+    let link = find_child(&item, "data").unwrap();
+    match expected_link_item_type {
+        Some(item_type) => {
+            assert_eq!(item_type, link.item_type);
+            assert_eq!(Some(volume_dir.clone()), link.link_target);
+            assert_eq!(1, link.children.len());
+        }
+        None => {
+            assert_eq!(DirectoryItemType::SymbolicLink, link.item_type);
+            assert_eq!(None, link.link_target);
+        }
+    }
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+#[case(false, 0, 2 * 1024)]
+#[case(true, 1, 1024)]
+fn scan_given_hard_links_counts_size_as_expected(
+    #[case] deduplicate_hard_links: bool,
+    #[case] expected_hard_link_count: usize,
+    #[case] expected_size: u64,
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    fs::create_dir(&temp_dir)?;
+    fs::write(temp_dir.join("a"), vec![0u8; 1024])?;
+    fs::hard_link(temp_dir.join("a"), temp_dir.join("b"))?;
+
+    // Act
+    let item = scan(
+        ScanOptions::default().deduplicate_hard_links(deduplicate_hard_links),
+        &temp_dir,
+    );
+
+    // Assert
+    assert_eq!(expected_size, item.size_in_bytes.get_value());
+    let hard_link_count = item
+        .children
+        .iter()
+        .filter(|c| c.item_type == DirectoryItemType::HardLink)
+        .count();
+    assert_eq!(expected_hard_link_count, hard_link_count);
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case(IgnoreFileMode::Off, 11014, 0)]
+#[case(IgnoreFileMode::Skip, 4014, 0)]
+#[case(IgnoreFileMode::Tag, 11014, 7000)]
+fn scan_given_ignore_file_mode_returns_expected_sizes(
+    #[case] ignore_files: IgnoreFileMode,
+    #[case] expected_size: u64,
+    #[case] expected_ignored_size: u64,
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_ignore_test_tree()?;
+
+    // Act
+    let item = scan(ScanOptions::default().ignore_files(ignore_files), &temp_dir);
+
+    // Assert
+    assert_eq!(expected_size, item.size_in_bytes.get_value());
+    assert_eq!(
+        expected_ignored_size,
+        item.ignored_size_in_bytes.get_value()
+    );
+    assert_eq!(
+        ignore_files == IgnoreFileMode::Skip,
+        find_child(&item, "target").is_none()
+    );
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case(IgnoreFileMode::Skip, 500, 0)]
+#[case(IgnoreFileMode::Tag, 1500, 1000)]
+fn scan_given_subdirectory_applies_ignore_files_of_ancestors(
+    #[case] ignore_files: IgnoreFileMode,
+    #[case] expected_size: u64,
+    #[case] expected_ignored_size: u64,
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    fs::create_dir_all(temp_dir.join("sub"))?;
+    fs::write(temp_dir.join(".gitignore"), "*.log\n")?;
+    fs::write(temp_dir.join("sub").join("a.log"), vec![0u8; 1000])?;
+    fs::write(temp_dir.join("sub").join("a.rs"), vec![0u8; 500])?;
+
+    // Act
+    let item = scan(
+        ScanOptions::default().ignore_files(ignore_files),
+        &temp_dir.join("sub"),
+    );
+
+    // Assert
+    assert_eq!(expected_size, item.size_in_bytes.get_value());
+    assert_eq!(
+        expected_ignored_size,
+        item.ignored_size_in_bytes.get_value()
+    );
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_given_tag_ignore_file_mode_tags_ignored_items_and_their_descendants() -> anyhow::Result<()>
+{
+    // Arrange
+    let temp_dir = create_ignore_test_tree()?;
+
+    // Act
+    let item = scan(
+        ScanOptions::default().ignore_files(IgnoreFileMode::Tag),
+        &temp_dir,
+    );
+
+    // Assert
+    assert!(!item.is_ignored);
+    let target = find_child(&item, "target").unwrap();
+    assert!(target.is_ignored);
+    assert!(find_child(target, "bin").unwrap().is_ignored);
+    assert_eq!(6000, target.ignored_size_in_bytes.get_value());
+    let sub = find_child(&item, "sub").unwrap();
+    assert!(!sub.is_ignored);
+    assert_eq!(1000, sub.ignored_size_in_bytes.get_value());
+    assert!(find_child(sub, "build").unwrap().is_ignored);
+    assert!(!find_child(sub, "keep").unwrap().is_ignored);
+    assert!(!find_child(&item, "src").unwrap().is_ignored);
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_without_collect_metadata_does_not_set_metadata() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+
+    // Act
+    let item = scan(ScanOptions::default(), &temp_dir);
+
+    // Assert
+    assert!(item.metadata.is_none());
+    let d1 = find_child(&item, "1").unwrap();
+    assert!(d1.metadata.is_none());
+    assert!(find_child(d1, "1.1").unwrap().metadata.is_none());
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_given_collect_metadata_sets_metadata_of_all_items() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+
+    // Act
+    let item = scan(ScanOptions::default().collect_metadata(true), &temp_dir);
+
+    // Assert
+    fn assert_has_metadata(item: &DirectoryItem) {
+        let metadata = item
+            .metadata
+            .as_ref()
+            .unwrap_or_else(|| panic!("{} has no metadata", item.path_segment));
+        assert!(metadata.modified.is_some());
+        #[cfg(unix)]
+        assert!(metadata.uid.is_some() && metadata.gid.is_some() && metadata.mode.is_some());
+        item.children.iter().for_each(assert_has_metadata);
+    }
+    assert_has_metadata(&item);
+    let d1 = find_child(&item, "1").unwrap();
+    let file_metadata = find_child(d1, "1.1").unwrap().metadata.as_deref().unwrap();
+    assert_eq!(
+        fs::metadata(temp_dir.join("1").join("1.1"))?
+            .modified()
+            .ok(),
+        file_metadata.modified
+    );
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_with_visitor_visits_every_item_and_returns_roots_without_descendants() -> anyhow::Result<()>
+{
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let visitor = Arc::new(RecordingVisitor::default());
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    // Act
+    let result =
+        Scanner::default().scan_with_visitor(vec![temp_dir.clone()], visitor.clone(), &should_exit);
+
+    // Assert
+    let events = visitor.take_events();
+    let enter_count = events
+        .iter()
+        .filter(|e| matches!(e, VisitorEvent::EnterDir(..)))
+        .count();
+    let leave_count = events
+        .iter()
+        .filter(|e| matches!(e, VisitorEvent::LeaveDir(..)))
+        .count();
+    let file_sizes: u64 = events
+        .iter()
+        .filter_map(|e| match e {
+            VisitorEvent::File(_, _, size) => Some(*size),
+            _ => None,
+        })
+        .sum();
+    let file_count = events
+        .iter()
+        .filter(|e| matches!(e, VisitorEvent::File(..)))
+        .count();
+    assert_eq!(8, enter_count);
+    assert_eq!(8, leave_count);
+    assert_eq!(21, file_count);
+    assert_eq!(TEST_DIRECTORY_TREE_SIZE, file_sizes);
+    assert_eq!(
+        Some(&VisitorEvent::LeaveDir(
+            temp_dir.clone(),
+            0,
+            TEST_DIRECTORY_TREE_SIZE,
+            28
+        )),
+        events.last()
+    );
+
+    assert_eq!(1, result.items.len());
+    let root = &result.items[0];
+    assert!(root.children.is_empty());
+    assert_eq!(TEST_DIRECTORY_TREE_SIZE, root.size_in_bytes.get_value());
+    assert_eq!(28, root.descendant_count);
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_with_visitor_enters_each_directory_before_its_entries_and_leaves_it_after(
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let visitor = Arc::new(RecordingVisitor::default());
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    // Act
+    Scanner::default().scan_with_visitor(vec![temp_dir.clone()], visitor.clone(), &should_exit);
+
+    // Assert
+    let events = visitor.take_events();
+    let position = |predicate: &dyn Fn(&VisitorEvent) -> bool| {
+        events.iter().position(predicate).expect("event not found")
+    };
+    for event in &events {
+        let (path, depth) = match event {
+            VisitorEvent::File(path, depth, _) | VisitorEvent::LeaveDir(path, depth, _, _) => {
+                (path, *depth)
+            }
+            _ => continue,
+        };
+        if depth == 0 {
+            continue;
+        }
+        let parent = path.parent().unwrap();
+        let entered = position(&|e| matches!(e, VisitorEvent::EnterDir(p, _) if p == parent));
+        let left = position(&|e| matches!(e, VisitorEvent::LeaveDir(p, ..) if p == parent));
+        let visited = position(&|e| e == event);
+        assert!(
+            entered < visited && visited < left,
+            "{path:?} was not visited while its parent was entered"
+        );
+    }
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn scan_with_visitor_given_non_existent_path_visits_error_even_if_not_collected() {
+    // Arrange
+    let path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    let visitor = Arc::new(RecordingVisitor::default());
+    let should_exit = Arc::new(AtomicBool::new(false));
+    let scanner = Scanner::new(ScanOptions::default().collect_errors(false)).unwrap();
+
+    // Act
+    let result = scanner.scan_with_visitor(vec![path.clone()], visitor.clone(), &should_exit);
+
+    // Assert
+    assert_eq!(
+        vec![
+            VisitorEvent::Error(path.clone()),
+            VisitorEvent::File(path, 0, 0)
+        ],
+        visitor.take_events()
+    );
+    assert!(result.errors.is_empty());
+}
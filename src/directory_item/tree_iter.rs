@@ -0,0 +1,138 @@
+//! Provides iterators over the items in a directory tree, and lookup of items by path.
+
+use super::DirectoryItem;
+use rayon::iter::{walk_tree_prefix, ParallelIterator};
+use std::{
+    collections::VecDeque,
+    path::{Component, Path, PathBuf},
+};
+
+#[cfg(test)]
+#[path = "./tree_iter_test.rs"]
+mod tree_iter_test;
+
+/// An item in a directory tree, with its path and its depth below the item the iteration started from.
+pub type TreeEntry<'a> = (PathBuf, &'a DirectoryItem, usize);
+
+/// Iterates over a directory tree depth-first, yielding each item before its children. See
+/// [`DirectoryItem::iter_depth_first`].
+#[derive(Debug)]
+pub struct DepthFirstIter<'a> {
+    stack: Vec<TreeEntry<'a>>,
+}
+
+impl<'a> Iterator for DepthFirstIter<'a> {
+    type Item = TreeEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, item, depth) = self.stack.pop()?;
+        // Pushed in reverse, so that the children are yielded in order.
+        self.stack.extend(
+            item.children
+                .iter()
+                .rev()
+                .map(|child| (path.join(&child.path_segment), child, depth + 1)),
+        );
+        Some((path, item, depth))
+    }
+}
+
+/// Iterates over a directory tree breadth-first, yielding all the items at one depth before any deeper ones. See
+/// [`DirectoryItem::iter_breadth_first`].
+#[derive(Debug)]
+pub struct BreadthFirstIter<'a> {
+    queue: VecDeque<TreeEntry<'a>>,
+}
+
+impl<'a> Iterator for BreadthFirstIter<'a> {
+    type Item = TreeEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, item, depth) = self.queue.pop_front()?;
+        self.queue.extend(
+            item.children
+                .iter()
+                .map(|child| (path.join(&child.path_segment), child, depth + 1)),
+        );
+        Some((path, item, depth))
+    }
+}
+
+impl DirectoryItem {
+    /// Returns an iterator over this item and all its descendants, depth-first in the order of the children. Each
+    /// path is this item's path segment joined with those of the descendants, so the paths are full paths when
+    /// iterating from a root item. This item is at depth 0.
+    pub fn iter_depth_first(&self) -> DepthFirstIter<'_> {
+        DepthFirstIter {
+            stack: vec![(PathBuf::from(&self.path_segment), self, 0)],
+        }
+    }
+
+    /// Returns an iterator over this item and all its descendants, breadth-first in the order of the children. The
+    /// paths and depths are the same as for [`iter_depth_first`].
+    ///
+    /// [`iter_depth_first`]: DirectoryItem::iter_depth_first
+    pub fn iter_breadth_first(&self) -> BreadthFirstIter<'_> {
+        BreadthFirstIter {
+            queue: VecDeque::from([(PathBuf::from(&self.path_segment), self, 0)]),
+        }
+    }
+
+    /// Returns a parallel iterator over this item and all its descendants, in no particular order. The paths and
+    /// depths are the same as for [`iter_depth_first`].
+    ///
+    /// [`iter_depth_first`]: DirectoryItem::iter_depth_first
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = TreeEntry<'_>> {
+        walk_tree_prefix(
+            (PathBuf::from(&self.path_segment), self, 0),
+            |(path, item, depth)| {
+                item.children
+                    .iter()
+                    .map(|child| (path.join(&child.path_segment), child, depth + 1))
+                    .collect::<Vec<_>>()
+            },
+        )
+    }
+
+    /// Returns the item at `path`, which is relative to the parent of this item, i.e. it starts with this item's
+    /// path segment, like the paths yielded by [`iter_depth_first`]. Returns `None` if there is no such item.
+    ///
+    /// [`iter_depth_first`]: DirectoryItem::iter_depth_first
+    pub fn find(&self, path: &Path) -> Option<&DirectoryItem> {
+        let mut item = self;
+        for segment in relative_segments(&self.path_segment, path)? {
+            item = item
+                .children
+                .iter()
+                .find(|child| child.path_segment == segment)?;
+        }
+        Some(item)
+    }
+
+    /// Returns the item at `path` mutably. See [`find`].
+    ///
+    /// [`find`]: DirectoryItem::find
+    pub fn find_mut(&mut self, path: &Path) -> Option<&mut DirectoryItem> {
+        let segments = relative_segments(&self.path_segment, path)?;
+        let mut item = self;
+        for segment in segments {
+            item = item
+                .children
+                .iter_mut()
+                .find(|child| child.path_segment == segment)?;
+        }
+        Some(item)
+    }
+}
+
+/// Returns the path segments of `path` below `root_segment`, or `None` if `path` does not start with it.
+fn relative_segments(root_segment: &str, path: &Path) -> Option<Vec<String>> {
+    let relative_path = path.strip_prefix(root_segment).ok()?;
+    Some(
+        relative_path
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect(),
+    )
+}
@@ -0,0 +1,167 @@
+use crate::{
+    test_directory_utils::{create_test_directory_tree, delete_test_directory_tree},
+    DirectoryItem,
+};
+use rayon::iter::ParallelIterator;
+use rstest::rstest;
+use std::{
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc},
+};
+
+const TEST_DIRECTORY_TREE_ITEM_COUNT: usize = 29;
+
+fn build(path: &Path) -> DirectoryItem {
+    DirectoryItem::from_root(path, &Arc::new(AtomicBool::new(false)))
+}
+
+#[rstest]
+fn iter_depth_first_yields_each_item_before_its_descendants() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let item = build(&temp_dir);
+
+    // Act
+    let entries: Vec<_> = item.iter_depth_first().collect();
+
+    // Assert
+    assert_eq!(TEST_DIRECTORY_TREE_ITEM_COUNT, entries.len());
+    assert_eq!((temp_dir.clone(), 0), (entries[0].0.clone(), entries[0].2));
+    let d1_5 = entries
+        .iter()
+        .position(|(path, _, _)| *path == temp_dir.join("1").join("1.5"))
+        .unwrap();
+    let (_, d1_5_item, d1_5_depth) = entries[d1_5];
+    assert_eq!(2, d1_5_depth);
+    // All the descendants of a directory directly follow it.
+    for (path, item, depth) in &entries[d1_5 + 1..=d1_5 + d1_5_item.descendant_count] {
+        assert!(path.starts_with(temp_dir.join("1").join("1.5")));
+        assert!(*depth > d1_5_depth);
+        assert_eq!(
+            path.file_name().unwrap().to_string_lossy(),
+            item.path_segment
+        );
+    }
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+fn iter_breadth_first_yields_items_by_ascending_depth() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let item = build(&temp_dir);
+
+    // Act
+    let entries: Vec<_> = item.iter_breadth_first().collect();
+
+    // Assert
+    assert_eq!(TEST_DIRECTORY_TREE_ITEM_COUNT, entries.len());
+    assert!(entries.windows(2).all(|pair| pair[0].2 <= pair[1].2));
+    let children: Vec<_> = entries[1..=item.children.len()]
+        .iter()
+        .map(|(_, child, _)| &child.path_segment)
+        .collect();
+    let expected: Vec<_> = item
+        .children
+        .iter()
+        .map(|child| &child.path_segment)
+        .collect();
+    assert_eq!(expected, children);
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+fn par_iter_yields_the_same_items_as_iter_depth_first() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let item = build(&temp_dir);
+
+    // Act
+    let mut entries: Vec<_> = item
+        .par_iter()
+        .map(|(path, _, depth)| (path, depth))
+        .collect();
+
+    // Assert
+    let mut expected: Vec<_> = item
+        .iter_depth_first()
+        .map(|(path, _, depth)| (path, depth))
+        .collect();
+    entries.sort();
+    expected.sort();
+    assert_eq!(expected, entries);
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+#[case(&[], Some(""))]
+#[case(&["1"], Some("1"))]
+#[case(&["1", "1.5", "1.5.3", "1.5.3.5"], Some("1.5.3.5"))]
+#[case(&["1", "missing"], None)]
+fn find_given_path_below_root_returns_matching_item(
+    #[case] segments: &[&str],
+    #[case] expected_segment: Option<&str>,
+) -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let item = build(&temp_dir);
+    let path = segments
+        .iter()
+        .fold(temp_dir.clone(), |path, s| path.join(s));
+
+    // Act
+    let found = item.find(&path);
+
+    // Assert
+    let expected_segment = expected_segment.map(|segment| match segment {
+        "" => temp_dir.to_string_lossy().to_string(),
+        segment => segment.to_string(),
+    });
+    assert_eq!(
+        expected_segment,
+        found.map(|found| found.path_segment.clone())
+    );
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+fn find_given_path_outside_root_returns_none() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let item = build(&temp_dir);
+
+    // Act
+    let found = item.find(&PathBuf::from("elsewhere").join("1"));
+
+    // Assert
+    assert!(found.is_none());
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+fn find_mut_given_existing_path_allows_item_to_be_modified() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let mut item = build(&temp_dir);
+    let path = temp_dir.join("1").join("1.5");
+
+    // Act
+    item.find_mut(&path).unwrap().children.clear();
+
+    // Assert
+    assert!(item.find(&path).unwrap().children.is_empty());
+    assert!(item.find(&path.join("1.5.1")).is_none());
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
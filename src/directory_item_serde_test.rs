@@ -0,0 +1,218 @@
+use super::{
+    DescendantCounts, DirectoryItem, DirectoryItemType, ScanSnapshot, SCAN_SNAPSHOT_VERSION,
+};
+use crate::{
+    test_directory_utils::{create_test_directory_tree, delete_test_directory_tree},
+    ItemMetadata, ScanOptions, Size,
+};
+use rstest::rstest;
+use std::{
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc},
+    time::{Duration, SystemTime},
+};
+
+fn create_file_item(path_segment: &str, size: u64) -> DirectoryItem {
+    DirectoryItem {
+        path_segment: path_segment.to_string(),
+        item_type: DirectoryItemType::File,
+        size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::new(4096),
+        descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
+        children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
+    }
+}
+
+/// Asserts that all the fields of both trees are equal, since `DirectoryItem` equality only compares sizes.
+fn assert_trees_eq(expected: &DirectoryItem, actual: &DirectoryItem) {
+    let expected: Vec<_> = expected.iter_depth_first().collect();
+    let actual: Vec<_> = actual.iter_depth_first().collect();
+    assert_eq!(expected.len(), actual.len());
+    for ((expected_path, expected, _), (actual_path, actual, _)) in
+        expected.iter().zip(actual.iter())
+    {
+        assert_eq!(expected_path, actual_path);
+        assert_eq!(expected.item_type, actual.item_type);
+        assert_eq!(expected.size_in_bytes, actual.size_in_bytes);
+        assert_eq!(
+            expected.allocated_size_in_bytes,
+            actual.allocated_size_in_bytes
+        );
+        assert_eq!(expected.descendant_count, actual.descendant_count);
+        assert_eq!(expected.descendant_counts, actual.descendant_counts);
+        assert_eq!(expected.link_target, actual.link_target);
+        assert_eq!(expected.is_ignored, actual.is_ignored);
+        assert_eq!(expected.ignored_size_in_bytes, actual.ignored_size_in_bytes);
+        assert_eq!(
+            expected.ignored_allocated_size_in_bytes,
+            actual.ignored_allocated_size_in_bytes
+        );
+        assert_eq!(expected.metadata, actual.metadata);
+    }
+}
+
+#[rstest]
+fn serialize_file_item_uses_stable_field_names() -> anyhow::Result<()> {
+    // Arrange
+    let item = create_file_item("a.txt", 123);
+
+    // Act
+    let json = serde_json::to_value(&item)?;
+
+    // Assert
+    assert_eq!(
+        serde_json::json!({
+            "path_segment": "a.txt",
+            "item_type": "file",
+            "size_in_bytes": 123,
+            "allocated_size_in_bytes": 4096,
+            "descendant_count": 0,
+            "descendant_counts": { "files": 0, "directories": 0, "symlinks": 0, "other": 0 },
+            "is_ignored": false,
+            "ignored_size_in_bytes": 0,
+            "ignored_allocated_size_in_bytes": 0
+        }),
+        json
+    );
+    Ok(())
+}
+
+#[rstest]
+#[case(DirectoryItemType::Directory, "directory")]
+#[case(DirectoryItemType::File, "file")]
+#[case(DirectoryItemType::SymbolicLink, "symbolic_link")]
+#[case(DirectoryItemType::HardLink, "hard_link")]
+#[case(DirectoryItemType::MountPoint, "mount_point")]
+#[case(DirectoryItemType::Unknown, "unknown")]
+fn directory_item_type_round_trips_as_snake_case(
+    #[case] item_type: DirectoryItemType,
+    #[case] expected: &str,
+) -> anyhow::Result<()> {
+    // Act
+    let json = serde_json::to_string(&item_type)?;
+    let deserialized: DirectoryItemType = serde_json::from_str(&json)?;
+
+    // Assert
+    assert_eq!(format!("\"{expected}\""), json);
+    assert_eq!(item_type, deserialized);
+    Ok(())
+}
+
+#[rstest]
+fn deserialize_item_without_optional_fields_uses_defaults() -> anyhow::Result<()> {
+    // Arrange
+    let json = r#"{
+        "path_segment": "a.txt",
+        "item_type": "file",
+        "size_in_bytes": 123,
+        "allocated_size_in_bytes": 4096,
+        "descendant_count": 0,
+        "descendant_counts": { "files": 0, "directories": 0, "symlinks": 0, "other": 0 }
+    }"#;
+
+    // Act
+    let item: DirectoryItem = serde_json::from_str(json)?;
+
+    // Assert
+    assert_trees_eq(&create_file_item("a.txt", 123), &item);
+    Ok(())
+}
+
+#[rstest]
+fn scanned_tree_round_trips_through_json() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let item = DirectoryItem::from_root(&temp_dir, &Arc::new(AtomicBool::new(false)));
+
+    // Act
+    let json = serde_json::to_string(&item)?;
+    let deserialized: DirectoryItem = serde_json::from_str(&json)?;
+
+    // Assert
+    assert_trees_eq(&item, &deserialized);
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+fn scanned_tree_with_metadata_round_trips_through_json() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let mut result = ScanOptions::default()
+        .collect_metadata(true)
+        .build()?
+        .scan(vec![temp_dir.clone()], &Arc::new(AtomicBool::new(false)));
+    let item = result.items.remove(0);
+
+    // Act
+    let json = serde_json::to_string(&item)?;
+    let deserialized: DirectoryItem = serde_json::from_str(&json)?;
+
+    // Assert
+    assert!(deserialized.metadata.is_some());
+    assert_trees_eq(&item, &deserialized);
+
+    delete_test_directory_tree(&temp_dir);
+    Ok(())
+}
+
+#[rstest]
+fn item_with_link_target_and_metadata_round_trips_through_json() -> anyhow::Result<()> {
+    // Arrange
+    let mut item = create_file_item("link", 10);
+    item.link_target = Some(PathBuf::from("target"));
+    item.is_ignored = true;
+    item.ignored_size_in_bytes = Size::new(10);
+    item.metadata = Some(Box::new(ItemMetadata {
+        modified: Some(SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123)),
+        uid: Some(1000),
+        mode: Some(0o100644),
+        ..Default::default()
+    }));
+
+    // Act
+    let json = serde_json::to_string(&item)?;
+    let deserialized: DirectoryItem = serde_json::from_str(&json)?;
+
+    // Assert
+    assert_trees_eq(&item, &deserialized);
+    Ok(())
+}
+
+#[rstest]
+fn snapshot_round_trips_with_current_version() -> anyhow::Result<()> {
+    // Arrange
+    let snapshot = ScanSnapshot::new(vec![create_file_item("a", 1), create_file_item("b", 2)]);
+
+    // Act
+    let json = serde_json::to_string(&snapshot)?;
+    let deserialized: ScanSnapshot = serde_json::from_str(&json)?;
+
+    // Assert
+    assert_eq!(SCAN_SNAPSHOT_VERSION, deserialized.version);
+    assert_eq!(2, deserialized.items.len());
+    assert_trees_eq(&snapshot.items[1], &deserialized.items[1]);
+    Ok(())
+}
+
+#[rstest]
+#[case(0)]
+#[case(SCAN_SNAPSHOT_VERSION + 1)]
+fn snapshot_with_unsupported_version_fails_to_deserialize(#[case] version: u32) {
+    // Arrange
+    let json = format!(r#"{{ "version": {version}, "items": [] }}"#);
+
+    // Act
+    let result = serde_json::from_str::<ScanSnapshot>(&json);
+
+    // Assert
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains(&format!("unsupported snapshot version {version}")));
+}
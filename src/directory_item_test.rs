@@ -1,8 +1,8 @@
-use super::{is_reparse_point, DirectoryItem, DirectoryItemType};
+use super::{is_reparse_point, DescendantCounts, DirectoryItem, DirectoryItemType, Scanner};
 use crate::{
     directory_item::{get_file_name_from_path, FILE_NAME_ERROR_VALUE},
     test_directory_utils::{create_test_directory_tree, delete_test_directory_tree},
-    Size,
+    Size, SizeMode,
 };
 use rstest::rstest;
 use std::{
@@ -26,21 +26,42 @@ fn cmp_with_given_size_in_bytes_returns_descending_ordering(
         path_segment: "/1".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(size_in_bytes_1),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 1,
+        descendant_counts: DescendantCounts::default(),
         children: vec![DirectoryItem {
             path_segment: "1".to_string(),
             size_in_bytes: Size::new(size_in_bytes_1),
+            allocated_size_in_bytes: Size::default(),
             children: vec![],
             descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
             item_type: DirectoryItemType::File,
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
         }],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     let v2 = DirectoryItem {
         path_segment: "/2".to_string(),
         size_in_bytes: Size::new(size_in_bytes_2),
+        allocated_size_in_bytes: Size::default(),
         children: vec![],
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         item_type: DirectoryItemType::Directory,
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
 
     // Act
@@ -64,21 +85,42 @@ fn partial_cmp_with_given_size_in_bytes_returns_descending_ordering(
         path_segment: "/2".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(size_in_bytes_1),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 1,
+        descendant_counts: DescendantCounts::default(),
         children: vec![DirectoryItem {
             path_segment: "1".to_string(),
             item_type: DirectoryItemType::File,
             size_in_bytes: Size::new(size_in_bytes_1),
+            allocated_size_in_bytes: Size::default(),
             descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
             children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
         }],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     let v2 = DirectoryItem {
         path_segment: "/3".to_string(),
         item_type: DirectoryItemType::File,
         size_in_bytes: Size::new(size_in_bytes_2),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
 
     // Act
@@ -106,21 +148,42 @@ fn eq_with_given_size_in_bytes_returns_correct_result(
         path_segment: "/3".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(size_in_bytes_1),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 1,
+        descendant_counts: DescendantCounts::default(),
         children: vec![DirectoryItem {
             path_segment: "1".to_string(),
             item_type: DirectoryItemType::Directory,
             size_in_bytes: Size::new(size_in_bytes_1),
+            allocated_size_in_bytes: Size::default(),
             descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
             children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
         }],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
     let v2 = DirectoryItem {
         path_segment: "/4".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(size_in_bytes_2),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
 
     // Act
@@ -181,14 +244,28 @@ fn debug_succeeds() {
         path_segment: "/1".to_string(),
         item_type: DirectoryItemType::Directory,
         size_in_bytes: Size::new(777),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 1,
+        descendant_counts: DescendantCounts::default(),
         children: vec![DirectoryItem {
             path_segment: "2".to_string(),
             item_type: DirectoryItemType::Directory,
             size_in_bytes: Size::new(778),
+            allocated_size_in_bytes: Size::default(),
             descendant_count: 0,
+            descendant_counts: DescendantCounts::default(),
             children: vec![],
+            link_target: None,
+            is_ignored: false,
+            ignored_size_in_bytes: Size::default(),
+            ignored_allocated_size_in_bytes: Size::default(),
+            metadata: None,
         }],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
 
     // Act
@@ -220,10 +297,10 @@ fn from_root_given_non_existent_path_does_not_panic() {
 fn get_child_items_given_non_existent_path_does_not_panic() {
     // Arrange
     let path = std::env::temp_dir().join(Uuid::new_v4().to_string());
-    let should_exit = Arc::new(AtomicBool::new(false));
+    let context = Scanner::default().create_context(Arc::new(AtomicBool::new(false)));
 
     // Act
-    let children = DirectoryItem::get_child_items(&path, &should_exit);
+    let children = DirectoryItem::get_child_items(&path, 1, &context);
 
     // Assert
     assert_eq!(1, children.len());
@@ -236,8 +313,15 @@ fn get_fraction_given_total_size_in_bytes_of_0_should_return_0() {
         path_segment: "/1".to_string(),
         item_type: DirectoryItemType::File,
         size_in_bytes: Size::new(123),
+        allocated_size_in_bytes: Size::default(),
         descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
         children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
     };
 
     // Act
@@ -337,10 +421,10 @@ fn get_child_items_given_dir_with_single_symlink_returns_symbolic_link_item() ->
     // Arrange - "1/1.12" contains exactly one entry: a symlink "1.12.1"
     let temp_dir = create_test_directory_tree()?;
     let dir_with_single_symlink = temp_dir.join("1").join("1.12");
-    let should_exit = Arc::new(AtomicBool::new(false));
+    let context = Scanner::default().create_context(Arc::new(AtomicBool::new(false)));
 
     // Act
-    let children = DirectoryItem::get_child_items(&dir_with_single_symlink, &should_exit);
+    let children = DirectoryItem::get_child_items(&dir_with_single_symlink, 1, &context);
 
     // Assert - single-entry branch should detect symlink and not recurse
     assert_eq!(1, children.len());
@@ -414,3 +498,169 @@ fn is_reparse_point_given_nonexistent_path_returns_false() {
     // Assert
     assert!(!result, "a nonexistent path should not be a reparse point");
 }
+
+#[rstest]
+#[case(SizeMode::Apparent, 123)]
+#[case(SizeMode::Allocated, 4096)]
+#[case(SizeMode::Count, 1)]
+fn get_size_returns_size_for_size_mode(#[case] size_mode: SizeMode, #[case] expected_size: u64) {
+    // Arrange
+    let item = DirectoryItem {
+        path_segment: "1".to_string(),
+        item_type: DirectoryItemType::File,
+        size_in_bytes: Size::new(123),
+        allocated_size_in_bytes: Size::new(4096),
+        descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
+        children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
+    };
+
+    // Act
+    let size = item.get_size(size_mode);
+
+    // Assert
+    assert_eq!(expected_size, size.get_value());
+}
+
+#[rstest]
+fn sort_by_size_given_allocated_mode_sorts_children_by_descending_allocated_size() {
+    // Arrange
+    let make_file = |name: &str, size: u64, allocated_size: u64| DirectoryItem {
+        path_segment: name.to_string(),
+        item_type: DirectoryItemType::File,
+        size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::new(allocated_size),
+        descendant_count: 0,
+        descendant_counts: DescendantCounts::default(),
+        children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
+    };
+    let mut item = DirectoryItem {
+        path_segment: "/1".to_string(),
+        item_type: DirectoryItemType::Directory,
+        size_in_bytes: Size::new(3000),
+        allocated_size_in_bytes: Size::new(12288),
+        descendant_count: 3,
+        descendant_counts: DescendantCounts::default(),
+        children: vec![
+            make_file("a", 2000, 0),
+            make_file("b", 900, 8192),
+            make_file("c", 100, 4096),
+        ],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
+    };
+
+    // Act
+    item.sort_by_size(SizeMode::Allocated);
+
+    // Assert
+    let names: Vec<_> = item
+        .children
+        .iter()
+        .map(|c| c.path_segment.as_str())
+        .collect();
+    assert_eq!(vec!["b", "c", "a"], names);
+}
+
+#[rstest]
+fn sort_by_size_given_count_mode_sorts_children_by_descending_entry_count() {
+    // Arrange
+    let make_item = |name: &str, size: u64, descendant_count: usize| DirectoryItem {
+        path_segment: name.to_string(),
+        item_type: DirectoryItemType::Directory,
+        size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::default(),
+        descendant_count,
+        descendant_counts: DescendantCounts::default(),
+        children: vec![],
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
+    };
+    let mut item = make_item("/1", 3000, 13);
+    item.children = vec![
+        make_item("a", 2000, 1),
+        make_item("b", 900, 7),
+        make_item("c", 100, 2),
+    ];
+
+    // Act
+    item.sort_by_size(SizeMode::Count);
+
+    // Assert
+    let names: Vec<_> = item
+        .children
+        .iter()
+        .map(|c| c.path_segment.as_str())
+        .collect();
+    assert_eq!(vec!["b", "c", "a"], names);
+    assert_eq!(14, item.get_size(SizeMode::Count).get_value());
+}
+
+#[cfg(unix)]
+#[rstest]
+fn from_root_given_file_path_records_allocated_size_in_whole_blocks() -> anyhow::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let file_path = temp_dir.join("1").join("1.1");
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    // Act
+    let item = DirectoryItem::from_root(&file_path, &should_exit);
+
+    // Assert
+    let expected = std::fs::symlink_metadata(&file_path)?.blocks() * 512;
+    assert_eq!(expected, item.allocated_size_in_bytes.get_value());
+    assert_eq!(0, item.allocated_size_in_bytes.get_value() % 512);
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
+
+#[rstest]
+fn build_aggregates_allocated_size_from_descendants() -> anyhow::Result<()> {
+    // Arrange
+    let temp_dir = create_test_directory_tree()?;
+    let should_exit = Arc::new(AtomicBool::new(false));
+
+    // Act
+    let items = DirectoryItem::build(vec![temp_dir.clone()], &should_exit);
+
+    // Assert
+    fn assert_aggregated(item: &DirectoryItem) {
+        if item.children.is_empty() {
+            return;
+        }
+        let sum: u64 = item
+            .children
+            .iter()
+            .map(|c| c.allocated_size_in_bytes.get_value())
+            .sum();
+        assert_eq!(sum, item.allocated_size_in_bytes.get_value());
+        item.children.iter().for_each(assert_aggregated);
+    }
+    assert_eq!(1, items.len());
+    assert_aggregated(&items[0]);
+
+    delete_test_directory_tree(&temp_dir);
+
+    Ok(())
+}
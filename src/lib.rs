@@ -13,9 +13,24 @@
 //!
 //! > **NOTE:** The *apparent size* of a file is the size of the file content in bytes, which is typically
 //! > slighly less than the actual space based on allocated blocks on the disk. The larger the file the less
-//! > significant the difference.
+//! > significant the difference. The *allocated size*, i.e. the number of allocated blocks multiplied by the
+//! > block size, is recorded alongside the apparent size. Items may also be measured by the number of entries they
+//! > contain. See [`SizeMode`].
 //!
-//! > **NOTE:** Symbolic links will be listed but not followed.
+//! > **NOTE:** By default symbolic links will be listed but not followed.
+//!
+//! Use [`ScanOptions`] to create a [`Scanner`] that, for example, limits the depth, follows symbolic links,
+//! stays on one file system, excludes items by glob pattern or regular expression, respects ignore files,
+//! collects the timestamps, ownership and permissions of each item, or uses a dedicated thread pool. Use
+//! [`Scanner::scan_with_visitor`] with a [`ScanVisitor`] to stream the items of huge trees rather than building
+//! them in memory, or [`ProgressiveScan`] to receive [`ScanEvent`]s from a background thread as the trees are
+//! discovered level by level. A built tree can be walked depth-first, breadth-first or in parallel, and searched by
+//! path, see [`DirectoryItem::iter_depth_first`] and [`DirectoryItem::find`].
+//!
+//! Use [`render_svg`] to draw the trees as a squarified treemap or an icicle chart, e.g. to embed them in a report.
+//!
+//! Enable the `serde` feature to serialize the trees with serde, e.g. to persist them or to ship them between
+//! processes. A `ScanSnapshot` wraps one or more trees along with the version of the schema.
 //!
 //! [`DirectoryItem`]: directory_item/struct.DirectoryItem.html
 //!
@@ -30,9 +45,38 @@ mod test_directory_utils;
 
 pub mod directory_item;
 pub use directory_item::is_reparse_point;
+pub use directory_item::BreadthFirstIter;
+pub use directory_item::DepthFirstIter;
+pub use directory_item::DescendantCounts;
 pub use directory_item::DirectoryItem;
 pub use directory_item::DirectoryItemType;
+pub use directory_item::IgnoreFileMode;
+pub use directory_item::ItemMetadata;
+pub use directory_item::ProgressiveScan;
+pub use directory_item::ScanContext;
+pub use directory_item::ScanError;
+pub use directory_item::ScanEvent;
+pub use directory_item::ScanEventReceiver;
+pub use directory_item::ScanEventReceiverDropped;
+pub use directory_item::ScanEventSender;
+pub use directory_item::ScanOptions;
+pub use directory_item::ScanResult;
+#[cfg(feature = "serde")]
+pub use directory_item::ScanSnapshot;
+pub use directory_item::ScanVisitor;
+pub use directory_item::Scanner;
+pub use directory_item::ScannerError;
+pub use directory_item::TreeEntry;
+#[cfg(feature = "serde")]
+pub use directory_item::SCAN_SNAPSHOT_VERSION;
 pub mod rapid_arena;
 pub mod size;
 pub use size::Size;
 pub use size::SizeDisplayFormat;
+pub use size::SizeMode;
+pub mod svg;
+pub use svg::render_svg;
+pub use svg::SvgChart;
+pub use svg::SvgColor;
+pub use svg::SvgOptions;
+pub use svg::SvgPalette;
@@ -1,17 +1,20 @@
 #![forbid(unsafe_code)]
 
-use clap::{ColorChoice, Parser};
+use clap::{ColorChoice, Parser, Subcommand, ValueEnum};
 use cli::cli_command::CliCommand;
+use cli::diff_command::DiffCommand;
 use cli::environment::EnvServiceTrait;
-use cli::view_command::ViewCommand;
+use cli::output_format::OutputFormat;
+use cli::view_command::{ItemSourceArgs, OutputSettings, ViewCommand};
+use cli::view_state::Config;
 use log::error;
 use logging::configure_logger;
 use regex::RegexBuilder;
-use space_rs::SizeDisplayFormat;
+use space_rs::{IgnoreFileMode, ScanOptions, SizeDisplayFormat, SizeMode, SvgChart};
 #[cfg(not(test))]
 use std::env;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{atomic::AtomicBool, Arc};
 
 #[cfg(test)]
@@ -48,7 +51,25 @@ r#"EXAMPLES:
     $ space path/to/dir1,'path/to/dir 2'
     $ space --size-threshold-percentage 5
     $ space --size-format binary
-    $ space --non-interactive"#,
+    $ space --size-mode allocated
+    $ space --deduplicate-hard-links
+    $ space --one-file-system /
+    $ space --non-interactive
+    $ space --non-interactive --max-depth 2
+    $ space --metadata
+    $ space --counts
+    $ space --measure count
+    $ space --output json | jq
+    $ space --output csv > space.csv
+    $ space --output ncdu > export.json
+    $ space --import-ncdu export.json
+    $ space --save-snapshot home.snap ~
+    $ space --load-snapshot home.snap
+    $ space diff monday.snap friday.snap
+    $ space --output du --max-depth 1
+    $ space --output html > space.html
+    $ space --output markdown > space.md
+    $ space --output svg --chart icicle > space.svg"#,
     after_long_help =
 r#"EXAMPLES:
     Analyze and display current working directory in a Text User Interface (TUI):
@@ -66,9 +87,64 @@ r#"EXAMPLES:
     Display file and directory sizes using binary units rather than the default metric units:
     $ space --size-format binary
 
+    Display the space allocated on disk rather than the default apparent size of the file content:
+    $ space --size-mode allocated
+
+    Count the size of files with multiple hard links only once:
+    $ space --deduplicate-hard-links
+
+    Scan the root file system without descending into other mounted file systems:
+    $ space --one-file-system /
+
     Display non-interactive output then exit:
-    $ space --non-interactive"#,
+    $ space --non-interactive
+
+    Display a summary of all items up to two levels below the target path, similar to `du -d 2`:
+    $ space --non-interactive --max-depth 2
+
+    Collect the timestamps, ownership and permissions of each item, to show them in the details view:
+    $ space --metadata
+
+    Show the number of files, directories, symbolic links and other items in each directory:
+    $ space --counts
+
+    Rank the items by the number of entries they contain rather than their size, e.g. when running out of inodes:
+    $ space --measure count
+
+    Write the tree of items as JSON rather than a table, e.g. to process it with jq:
+    $ space --output json | jq '.items[].children[] | {path, size}'
+
+    Write one row per item with raw byte sizes, e.g. to sort them in a spreadsheet:
+    $ space --output csv > space.csv
+
+    Write the full tree in the JSON export format of ncdu, e.g. to browse it with `ncdu -f`:
+    $ space --output ncdu > export.json
+
+    View an ncdu export rather than scanning, e.g. one created with `ncdu -o export.json` on another machine:
+    $ space --import-ncdu export.json
+
+    Save a scan of the home directory to a compressed snapshot, then view it later without scanning again:
+    $ space --save-snapshot home.snap ~
+    $ space --load-snapshot home.snap
+
+    Compare two snapshots of the same tree, listing the items that grew or shrunk the most first:
+    $ space diff monday.snap friday.snap
+
+    Write the size of each directory up to one level below the target path like `du -h -d 1`, e.g. for scripts that
+    parse the output of du:
+    $ space --output du --max-depth 1
+
+    Write a single HTML page with a zoomable treemap and a collapsible tree, e.g. to share or view it offline:
+    $ space --output html > space.html
+
+    Write a summary and a nested list of the items that are at least 5% of the total size, e.g. to paste into an
+    issue:
+    $ space --output markdown -s 5 > space.md
+
+    Draw the tree as an icicle chart in an SVG image, with a label for each item of at least 2% of the total size:
+    $ space --output svg --chart icicle --svg-size 1600x900 -s 2 > space.svg"#,
     color = ColorChoice::Never,
+    args_conflicts_with_subcommands = true,
 )]
 struct CliArgs {
     /// The path(s) to the target files or directories to view. If not supplied the current directory
@@ -77,21 +153,180 @@ struct CliArgs {
     target_paths: Option<Vec<PathBuf>>,
 
     /// The size threshold as a percentage of the total. Only items with a relative size greater or
-    /// equal to this percentage will be displayed. [default: 0 for interactive, 1 for non-interactive]
+    /// equal to this percentage will be displayed. [default: 0 for interactive, 1 for non-interactive
+    /// without --max-depth]
     #[arg(value_name = "PERCENTAGE", short = 's', long, value_parser = clap::value_parser!(u8).range(0..=100))]
     size_threshold_percentage: Option<u8>,
 
-    /// The format to use when a size value is displayed.
-    #[arg(short = 'f', long, value_enum, default_value_t = SizeDisplayFormat::Metric)]
-    size_format: SizeDisplayFormat,
+    /// The format to use when a size value is displayed. [default: binary for --output du, like `du -h`, metric
+    /// otherwise]
+    #[arg(short = 'f', long, value_enum)]
+    size_format: Option<SizeDisplayFormat>,
+
+    /// The size to analyze, i.e. the apparent size of the file content or the space allocated on disk. [default:
+    /// allocated for --output du, like du, apparent otherwise]
+    #[arg(short = 'm', long, value_enum)]
+    size_mode: Option<SizeMode>,
+
+    /// What to measure and rank the items by. `count` uses the number of entries, i.e. each item and all its
+    /// descendants, for every size, percentage, size bar, sort order and threshold instead of bytes, to show which
+    /// trees use the most inodes.
+    #[arg(long, value_enum, value_name = "MEASURE", default_value_t = Measure::Bytes, conflicts_with = "size_mode")]
+    measure: Measure,
 
     /// If specified then only non-interactive output will be rendered.
     #[arg(short = 'n', long)]
     non_interactive: bool,
 
+    /// The format of the non-interactive output. Any format other than `table` implies --non-interactive, and
+    /// only writes the items that pass the size threshold and regex filter, without any status messages. `ncdu`
+    /// writes the full tree, which requires a single target path. `svg` draws the full tree, regardless of the
+    /// regex filter.
+    #[arg(short = 'o', long, value_enum, value_name = "FORMAT", default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+
+    /// If specified then `--output du` also writes a line for each file, rather than only for the directories,
+    /// like `du -a`.
+    #[arg(short = 'a', long)]
+    all: bool,
+
+    /// The kind of chart that `--output svg` draws.
+    #[arg(long, value_enum, default_value_t = SvgChart::Treemap)]
+    chart: SvgChart,
+
+    /// The width and height in pixels of the image that `--output svg` writes.
+    #[arg(long, value_name = "WIDTHxHEIGHT", value_parser = parse_svg_size, default_value = "1200x800")]
+    svg_size: (u32, u32),
+
+    #[command(flatten)]
+    item_source: ItemSourceArgs,
+
+    /// Saves the completed scan to a compressed snapshot FILE, along with the roots, the time and the host, rather
+    /// than viewing it. View it later, or on another machine, with `--load-snapshot FILE`.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["load_snapshot", "output", "non_interactive"])]
+    save_snapshot: Option<PathBuf>,
+
     /// Filter displayed items to those whose path matches this regex pattern (case-insensitive).
     #[arg(short = 'r', long, value_name = "PATTERN")]
     filter_regex: Option<String>,
+
+    /// If specified then the size of a file with multiple hard links is only counted once. Additional links
+    /// to the same file are listed with a size of zero. Only supported on Unix platforms.
+    #[arg(long)]
+    deduplicate_hard_links: bool,
+
+    /// If specified then directories on a different file system to the target path are not scanned, and are
+    /// shown as mount points with a size of zero. Only supported on Unix platforms.
+    #[arg(short = 'x', long)]
+    one_file_system: bool,
+
+    /// If specified then symbolic links are followed and the size of their targets is included. Each directory
+    /// is only scanned once, so links that form a cycle, that lead to a directory within the target paths, or
+    /// that lead to a directory that has already been scanned, are listed as links with a size of zero.
+    #[arg(short = 'L', long)]
+    follow_symlinks: bool,
+
+    /// Excludes the items that match a glob pattern from the scan, so they are never read, e.g. `.snapshot`
+    /// or `/proc`. The pattern is matched against both the full path and the file name of each item. May be
+    /// specified multiple times, and is combined with the `exclude` list in the config file.
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Excludes the items with a full path that matches a regular expression from the scan, so they are never
+    /// read. May be specified multiple times, and is combined with the `exclude_regex` list in the config file.
+    #[arg(long, value_name = "PATTERN")]
+    exclude_regex: Vec<String>,
+
+    /// How the `.gitignore`, `.ignore` and `.spaceignore` files in the scanned directories are used. `skip`
+    /// leaves out the items that match them, as if they were excluded. `tag` includes those items, but shows the
+    /// share of each directory's size that is ignored.
+    #[arg(long, value_enum, value_name = "MODE", default_value_t = IgnoreFileMode::Off)]
+    ignore_files: IgnoreFileMode,
+
+    /// The maximum depth of the items to display, where the target paths are at depth 0. Directories at the
+    /// maximum depth still include the size of all their descendants, but the descendants are not kept or
+    /// listed. When specified, all items up to the maximum depth are displayed by default in non-interactive
+    /// mode, similar to `du -d`.
+    #[arg(short = 'd', long, value_name = "DEPTH")]
+    max_depth: Option<usize>,
+
+    /// If specified then the timestamps, ownership and permissions of each item are collected. They are shown in
+    /// the details view of the selected item, and as additional columns in non-interactive output. Uses more
+    /// memory.
+    #[arg(long)]
+    metadata: bool,
+
+    /// If specified then the number of files, directories, symbolic links and other items below each directory
+    /// are shown in additional columns. The columns can also be toggled in the TUI.
+    #[arg(long)]
+    counts: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum Command {
+    /// Compares two snapshots saved with `--save-snapshot`, e.g. of the same tree on different days. The trees are
+    /// aligned by path, and each item is listed with the change in size, the percent change and whether it was
+    /// added (+) or removed (-), sorted by the absolute size of the change.
+    Diff(DiffArgs),
+}
+
+#[derive(Clone, Debug, clap::Args)]
+struct DiffArgs {
+    /// The snapshot of the old tree.
+    #[arg(value_name = "OLD")]
+    old_snapshot: PathBuf,
+
+    /// The snapshot of the new tree.
+    #[arg(value_name = "NEW")]
+    new_snapshot: PathBuf,
+
+    /// The format to display sizes in.
+    #[arg(short = 'f', long, value_enum, default_value_t = SizeDisplayFormat::Metric)]
+    size_format: SizeDisplayFormat,
+
+    /// The size to compare, i.e. the apparent size of the file content or the space allocated on disk.
+    #[arg(short = 'm', long, value_enum, default_value_t = SizeMode::Apparent)]
+    size_mode: SizeMode,
+
+    /// What to compare. `count` compares the number of entries, i.e. each item and all its descendants, instead of
+    /// bytes, to show which trees gained or lost the most inodes.
+    #[arg(long, value_enum, value_name = "MEASURE", default_value_t = Measure::Bytes, conflicts_with = "size_mode")]
+    measure: Measure,
+
+    /// If specified then only non-interactive output will be rendered.
+    #[arg(short = 'n', long)]
+    non_interactive: bool,
+
+    /// If specified then the items that did not change are also shown.
+    #[arg(short = 'a', long)]
+    all: bool,
+
+    /// The maximum depth of the items to show, where the roots are at depth 0.
+    #[arg(short = 'd', long, value_name = "DEPTH")]
+    max_depth: Option<usize>,
+}
+
+/// What the items are measured and ranked by.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+enum Measure {
+    /// The size in bytes, as specified by the size mode.
+    #[default]
+    Bytes,
+    /// The number of entries.
+    Count,
+}
+
+impl Measure {
+    /// Returns the size mode to analyze, where `size_mode` is the one specified for bytes.
+    fn size_mode(self, size_mode: SizeMode) -> SizeMode {
+        match self {
+            Measure::Bytes => size_mode,
+            Measure::Count => SizeMode::Count,
+        }
+    }
 }
 
 #[cfg(not(test))]
@@ -126,8 +361,8 @@ pub(crate) fn run<W: Write>(
     env_service: Box<dyn EnvServiceTrait>,
     should_exit: Arc<AtomicBool>,
 ) -> anyhow::Result<()> {
-    configure_logger(user_home_dir, &env_service);
-    if let Err(e) = run_command(args, writer, env_service, should_exit) {
+    configure_logger(user_home_dir.clone(), &env_service);
+    if let Err(e) = run_command(args, writer, user_home_dir, env_service, should_exit) {
         error!("{}", e);
         Err(e)
     } else {
@@ -138,10 +373,18 @@ pub(crate) fn run<W: Write>(
 fn run_command<W: Write>(
     args: &[String],
     writer: &mut W,
+    user_home_dir: Option<PathBuf>,
     env_service: Box<dyn EnvServiceTrait>,
     should_exit: Arc<AtomicBool>,
 ) -> anyhow::Result<()> {
-    let args = parse_args(args)?;
+    let mut args = parse_args(args)?;
+    if let Some(Command::Diff(diff_args)) = args.command.take() {
+        prepare_diff_command(diff_args, env_service, should_exit)?.run(writer)?;
+        return Ok(());
+    }
+    if let Some(user_home_dir) = user_home_dir {
+        add_config_exclude_patterns(&mut args, &Config::get_file_path(&user_home_dir));
+    }
     prepare_command(args, env_service, should_exit)?.run(writer)?;
     Ok(())
 }
@@ -150,26 +393,56 @@ fn parse_args(args: &[String]) -> Result<CliArgs, anyhow::Error> {
     Ok(CliArgs::parse_from(args))
 }
 
+/// Adds the exclude patterns from the config file at `config_file_path`, if any, to those specified via the
+/// arguments. A missing or invalid config file is ignored, as it is elsewhere.
+fn add_config_exclude_patterns(args: &mut CliArgs, config_file_path: &Path) {
+    if let Ok(config) = Config::read(config_file_path) {
+        args.exclude.extend(config.exclude);
+        args.exclude_regex.extend(config.exclude_regex);
+    }
+}
+
 fn prepare_command(
     args: CliArgs,
     env_service: Box<dyn EnvServiceTrait>,
     should_exit: Arc<AtomicBool>,
 ) -> anyhow::Result<ViewCommand> {
-    let size_threshold = args
-        .size_threshold_percentage
-        .unwrap_or(if args.non_interactive {
-            DEFAULT_NON_INTERACTIVE_SIZE_THRESHOLD_PERCENTAGE
-        } else {
-            DEFAULT_SIZE_THRESHOLD_PERCENTAGE
-        });
+    let size_threshold = get_size_threshold_percentage(&args);
+    let size_format = get_size_format(&args);
+    let size_mode = get_size_mode(&args);
+    #[cfg(not(test))]
+    let non_interactive = is_non_interactive(&args);
     let filter_regex = compile_filter_regex(args.filter_regex.as_deref())?;
+    let scanner = ScanOptions {
+        exclude_patterns: args.exclude,
+        exclude_regex_patterns: args.exclude_regex,
+        max_depth: args.max_depth,
+        ..Default::default()
+    }
+    .deduplicate_hard_links(args.deduplicate_hard_links)
+    .one_file_system(args.one_file_system)
+    .follow_symlinks(args.follow_symlinks)
+    .ignore_files(args.ignore_files)
+    .collect_metadata(args.metadata)
+    .build()?;
     let mut command = ViewCommand::new(
         args.target_paths,
-        Some(args.size_format),
+        args.item_source.into(),
+        Some(size_format),
+        size_mode,
         size_threshold,
+        args.counts,
+        args.output,
+        OutputSettings {
+            du_all_files: args.all,
+            svg_chart: args.chart,
+            svg_dimensions: args.svg_size,
+            save_snapshot_path: args.save_snapshot,
+        },
         #[cfg(not(test))]
-        args.non_interactive,
+        non_interactive,
         filter_regex,
+        Arc::new(scanner),
         env_service,
         should_exit,
     );
@@ -177,6 +450,91 @@ fn prepare_command(
     Ok(command)
 }
 
+fn prepare_diff_command(
+    args: DiffArgs,
+    env_service: Box<dyn EnvServiceTrait>,
+    should_exit: Arc<AtomicBool>,
+) -> anyhow::Result<DiffCommand> {
+    let mut command = DiffCommand::new(
+        args.old_snapshot,
+        args.new_snapshot,
+        args.size_format,
+        args.measure.size_mode(args.size_mode),
+        args.all,
+        args.max_depth,
+        #[cfg(not(test))]
+        args.non_interactive,
+        env_service,
+        should_exit,
+    );
+    command.prepare()?;
+    Ok(command)
+}
+
+/// Returns the size display format specified via the arguments, or else the default, which is binary for the du
+/// output like `du -h`.
+fn get_size_format(args: &CliArgs) -> SizeDisplayFormat {
+    args.size_format
+        .unwrap_or(if args.output == OutputFormat::Du {
+            SizeDisplayFormat::Binary
+        } else {
+            SizeDisplayFormat::Metric
+        })
+}
+
+/// Returns the size mode to analyze, taking into account what is measured. The default is the allocated size for
+/// the du output, like du, and the apparent size otherwise.
+fn get_size_mode(args: &CliArgs) -> SizeMode {
+    let size_mode = args
+        .size_mode
+        .unwrap_or(if args.output == OutputFormat::Du {
+            SizeMode::Allocated
+        } else {
+            SizeMode::Apparent
+        });
+    args.measure.size_mode(size_mode)
+}
+
+/// Returns the size threshold percentage specified via the arguments, or else the default. A limited depth is
+/// already a way to limit the non-interactive output, so all items up to that depth are then shown by default.
+fn get_size_threshold_percentage(args: &CliArgs) -> u8 {
+    args.size_threshold_percentage.unwrap_or(
+        if is_non_interactive(args) && args.max_depth.is_none() {
+            DEFAULT_NON_INTERACTIVE_SIZE_THRESHOLD_PERCENTAGE
+        } else {
+            DEFAULT_SIZE_THRESHOLD_PERCENTAGE
+        },
+    )
+}
+
+/// Returns `true` if only non-interactive output should be rendered, which is implied by the output formats
+/// other than the table.
+fn is_non_interactive(args: &CliArgs) -> bool {
+    args.non_interactive || args.output.is_machine_readable()
+}
+
+/// Parses the dimensions of the SVG output, e.g. `1200x800`.
+fn parse_svg_size(value: &str) -> Result<(u32, u32), String> {
+    let parse = |dimension: &str| {
+        dimension
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|pixels| *pixels > 0)
+    };
+    match value.split_once(['x', 'X']) {
+        Some((width, height)) => match (parse(width), parse(height)) {
+            (Some(width), Some(height)) => Ok((width, height)),
+            _ => Err(format!(
+                "'{value}' does not have a positive width and height"
+            )),
+        },
+        None => Err(format!(
+            "'{value}' is not in the form WIDTHxHEIGHT, e.g. 1200x800"
+        )),
+    }
+}
+
 fn compile_filter_regex(pattern: Option<&str>) -> anyhow::Result<Option<regex::Regex>> {
     match pattern {
         None => Ok(None),
@@ -1,14 +1,20 @@
+use clap::Parser;
+use space_rs::{IgnoreFileMode, SizeDisplayFormat, SizeMode, SvgChart};
 use std::{
     env::{self, VarError},
+    path::PathBuf,
     sync::{atomic::AtomicBool, Arc},
 };
 
 use crate::{
-    cli::environment::MockEnvServiceTrait,
+    add_config_exclude_patterns,
+    cli::{environment::MockEnvServiceTrait, output_format::OutputFormat},
+    get_size_format, get_size_mode, get_size_threshold_percentage,
     logging::SPACE_LOG_LEVEL_ENV_VAR_NAME,
     parse_args, prepare_command, run,
     test_directory_utils::{create_test_directory_tree, delete_test_directory_tree},
     test_utils::{env_service_mock_without_env_vars, TestOut},
+    CliArgs, Command,
 };
 
 const BINARY_PATH: &str = "./space";
@@ -225,3 +231,684 @@ fn run_given_valid_path_succeeds() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+// ─── --size-format arg tests ─────────────────────────────────────────────────
+
+#[test]
+fn get_size_format_is_metric_by_default() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert!(matches!(
+        get_size_format(&cli_args),
+        SizeDisplayFormat::Metric
+    ));
+    Ok(())
+}
+
+#[test]
+fn get_size_format_given_du_output_is_binary_by_default() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "du".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert!(matches!(
+        get_size_format(&cli_args),
+        SizeDisplayFormat::Binary
+    ));
+    Ok(())
+}
+
+#[test]
+fn get_size_format_given_du_output_and_size_format_returns_size_format() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "du".to_string(),
+        "--size-format".to_string(),
+        "metric".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert!(matches!(
+        get_size_format(&cli_args),
+        SizeDisplayFormat::Metric
+    ));
+    Ok(())
+}
+
+// ─── --size-mode arg tests ───────────────────────────────────────────────────
+
+#[test]
+fn parse_args_size_mode_is_apparent_by_default() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(SizeMode::Apparent, get_size_mode(&cli_args));
+    Ok(())
+}
+
+#[test]
+fn get_size_mode_given_du_output_is_allocated_by_default() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "du".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(SizeMode::Allocated, get_size_mode(&cli_args));
+    Ok(())
+}
+
+#[test]
+fn get_size_mode_given_du_output_and_size_mode_returns_size_mode() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "du".to_string(),
+        "--size-mode".to_string(),
+        "apparent".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(SizeMode::Apparent, get_size_mode(&cli_args));
+    Ok(())
+}
+
+#[test]
+fn parse_args_size_mode_allocated_long_flag() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--size-mode".to_string(),
+        "allocated".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(Some(SizeMode::Allocated), cli_args.size_mode);
+    Ok(())
+}
+
+#[test]
+fn parse_args_deduplicate_hard_links_is_false_by_default() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert!(!cli_args.deduplicate_hard_links);
+    Ok(())
+}
+
+#[test]
+fn parse_args_deduplicate_hard_links_long_flag() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--deduplicate-hard-links".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert!(cli_args.deduplicate_hard_links);
+    Ok(())
+}
+
+#[test]
+fn parse_args_one_file_system_short_flag() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string(), "-x".to_string()];
+    let cli_args = parse_args(&args)?;
+    assert!(cli_args.one_file_system);
+    Ok(())
+}
+
+#[test]
+fn parse_args_follow_symlinks_is_false_by_default() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert!(!cli_args.follow_symlinks);
+    Ok(())
+}
+
+#[test]
+fn parse_args_follow_symlinks_long_flag() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string(), "--follow-symlinks".to_string()];
+    let cli_args = parse_args(&args)?;
+    assert!(cli_args.follow_symlinks);
+    Ok(())
+}
+
+#[test]
+fn parse_args_exclude_can_be_specified_multiple_times() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--exclude".to_string(),
+        ".snapshot".to_string(),
+        "--exclude".to_string(),
+        "/proc".to_string(),
+        "--exclude-regex".to_string(),
+        r"\.tmp$".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(vec![".snapshot", "/proc"], cli_args.exclude);
+    assert_eq!(vec![r"\.tmp$"], cli_args.exclude_regex);
+    Ok(())
+}
+
+#[test]
+fn add_config_exclude_patterns_appends_config_lists_to_args() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--exclude".to_string(),
+        ".snapshot".to_string(),
+    ];
+    let mut cli_args = parse_args(&args)?;
+    let config_file_path =
+        std::env::temp_dir().join(format!("space_test_{}", uuid::Uuid::new_v4()));
+    std::fs::write(
+        &config_file_path,
+        "exclude:\n  - /proc\nexclude_regex:\n  - '\\.tmp$'\n",
+    )?;
+
+    add_config_exclude_patterns(&mut cli_args, &config_file_path);
+
+    assert_eq!(vec![".snapshot", "/proc"], cli_args.exclude);
+    assert_eq!(vec![r"\.tmp$"], cli_args.exclude_regex);
+    std::fs::remove_file(&config_file_path)?;
+    Ok(())
+}
+
+#[test]
+fn add_config_exclude_patterns_ignores_missing_config_file() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--exclude".to_string(),
+        ".snapshot".to_string(),
+    ];
+    let mut cli_args = parse_args(&args)?;
+    let config_file_path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+
+    add_config_exclude_patterns(&mut cli_args, &config_file_path);
+
+    assert_eq!(vec![".snapshot"], cli_args.exclude);
+    assert!(cli_args.exclude_regex.is_empty());
+    Ok(())
+}
+
+#[test]
+fn prepare_command_with_invalid_exclude_regex_returns_error() {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--exclude-regex".to_string(),
+        "(invalid".to_string(),
+    ];
+    let cli_args = parse_args(&args).unwrap();
+    let mut env_service_mock = MockEnvServiceTrait::new();
+    env_service_mock
+        .expect_current_dir()
+        .returning(env::current_dir);
+    let should_exit = Arc::new(AtomicBool::new(false));
+    let result = prepare_command(cli_args, Box::new(env_service_mock), should_exit);
+    let msg = format!("{}", result.err().expect("Expected an error"));
+    assert!(
+        msg.contains("exclude regex"),
+        "Error message should mention the exclude regex, got: {msg}"
+    );
+}
+
+#[test]
+fn parse_args_ignore_files_is_off_by_default() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(IgnoreFileMode::Off, cli_args.ignore_files);
+    Ok(())
+}
+
+#[test]
+fn parse_args_ignore_files_tag() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--ignore-files".to_string(),
+        "tag".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(IgnoreFileMode::Tag, cli_args.ignore_files);
+    Ok(())
+}
+
+// ─── --max-depth arg tests ───────────────────────────────────────────────────
+
+#[test]
+fn parse_args_max_depth_is_none_by_default() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(None, cli_args.max_depth);
+    Ok(())
+}
+
+#[test]
+fn parse_args_max_depth_short_flag() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string(), "-d".to_string(), "2".to_string()];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(Some(2), cli_args.max_depth);
+    Ok(())
+}
+
+#[test]
+fn get_size_threshold_percentage_given_non_interactive_returns_non_interactive_default(
+) -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string(), "--non-interactive".to_string()];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(1, get_size_threshold_percentage(&cli_args));
+    Ok(())
+}
+
+#[test]
+fn get_size_threshold_percentage_given_non_interactive_with_max_depth_returns_zero(
+) -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--non-interactive".to_string(),
+        "--max-depth".to_string(),
+        "1".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(0, get_size_threshold_percentage(&cli_args));
+    Ok(())
+}
+
+#[test]
+fn get_size_threshold_percentage_given_explicit_percentage_with_max_depth_returns_percentage(
+) -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--non-interactive".to_string(),
+        "--max-depth".to_string(),
+        "1".to_string(),
+        "-s".to_string(),
+        "5".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(5, get_size_threshold_percentage(&cli_args));
+    Ok(())
+}
+
+// ─── --counts arg tests ──────────────────────────────────────────────────────
+
+#[test]
+fn parse_args_counts_is_false_by_default() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert!(!cli_args.counts);
+    Ok(())
+}
+
+#[test]
+fn parse_args_counts_flag() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string(), "--counts".to_string()];
+    let cli_args = parse_args(&args)?;
+    assert!(cli_args.counts);
+    Ok(())
+}
+
+// ─── --measure arg tests ─────────────────────────────────────────────────────
+
+#[test]
+fn get_size_mode_given_default_measure_returns_size_mode() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--size-mode".to_string(),
+        "allocated".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(SizeMode::Allocated, get_size_mode(&cli_args));
+    Ok(())
+}
+
+#[test]
+fn get_size_mode_given_count_measure_returns_count() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--measure".to_string(),
+        "count".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(SizeMode::Count, get_size_mode(&cli_args));
+    Ok(())
+}
+
+#[test]
+fn parse_args_given_measure_and_size_mode_returns_error() {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--measure".to_string(),
+        "count".to_string(),
+        "--size-mode".to_string(),
+        "allocated".to_string(),
+    ];
+    assert!(CliArgs::try_parse_from(&args).is_err());
+}
+
+#[test]
+fn parse_args_given_count_size_mode_returns_error() {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--size-mode".to_string(),
+        "count".to_string(),
+    ];
+    assert!(CliArgs::try_parse_from(&args).is_err());
+}
+
+// ─── --output arg tests ──────────────────────────────────────────────────────
+
+#[test]
+fn parse_args_output_is_table_by_default() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(OutputFormat::Table, cli_args.output);
+    Ok(())
+}
+
+#[test]
+fn parse_args_output_json() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(OutputFormat::Json, cli_args.output);
+    Ok(())
+}
+
+#[test]
+fn parse_args_output_csv() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "csv".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(OutputFormat::Csv, cli_args.output);
+    Ok(())
+}
+
+#[test]
+fn parse_args_output_html() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "html".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(OutputFormat::Html, cli_args.output);
+    Ok(())
+}
+
+#[test]
+fn parse_args_output_svg_with_chart_and_size() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "svg".to_string(),
+        "--chart".to_string(),
+        "icicle".to_string(),
+        "--svg-size".to_string(),
+        "1600x900".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(OutputFormat::Svg, cli_args.output);
+    assert_eq!(SvgChart::Icicle, cli_args.chart);
+    assert_eq!((1600, 900), cli_args.svg_size);
+    Ok(())
+}
+
+#[test]
+fn parse_args_svg_chart_and_size_have_defaults() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(SvgChart::Treemap, cli_args.chart);
+    assert_eq!((1200, 800), cli_args.svg_size);
+    Ok(())
+}
+
+#[test]
+fn parse_args_invalid_svg_size_fails() {
+    for svg_size in ["1200", "0x800", "wide x tall"] {
+        let args = vec![
+            BINARY_PATH.to_string(),
+            "--svg-size".to_string(),
+            svg_size.to_string(),
+        ];
+        assert!(CliArgs::try_parse_from(&args).is_err(), "{svg_size}");
+    }
+}
+
+#[test]
+fn parse_args_output_markdown() -> anyhow::Result<()> {
+    for output in ["markdown", "md"] {
+        let args = vec![
+            BINARY_PATH.to_string(),
+            "--output".to_string(),
+            output.to_string(),
+        ];
+        let cli_args = parse_args(&args)?;
+        assert_eq!(OutputFormat::Markdown, cli_args.output);
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_args_output_tsv() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "tsv".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(OutputFormat::Tsv, cli_args.output);
+    Ok(())
+}
+
+#[test]
+fn get_size_threshold_percentage_given_json_output_returns_non_interactive_default(
+) -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "-o".to_string(),
+        "json".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(1, get_size_threshold_percentage(&cli_args));
+    Ok(())
+}
+
+// ─── --import-ncdu arg tests ─────────────────────────────────────────────────
+
+#[test]
+fn parse_args_import_ncdu() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--import-ncdu".to_string(),
+        "export.json".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(
+        Some(PathBuf::from("export.json")),
+        cli_args.item_source.import_ncdu
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_args_given_import_ncdu_and_target_path_returns_error() {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--import-ncdu".to_string(),
+        "export.json".to_string(),
+        "some/path".to_string(),
+    ];
+    assert!(CliArgs::try_parse_from(&args).is_err());
+}
+
+#[test]
+fn parse_args_save_snapshot() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--save-snapshot".to_string(),
+        "home.snap".to_string(),
+        "some/path".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(Some(PathBuf::from("home.snap")), cli_args.save_snapshot);
+    assert_eq!(
+        Some(vec![PathBuf::from("some/path")]),
+        cli_args.target_paths
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_args_given_save_snapshot_and_output_returns_error() {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--save-snapshot".to_string(),
+        "home.snap".to_string(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+    assert!(CliArgs::try_parse_from(&args).is_err());
+}
+
+#[test]
+fn parse_args_load_snapshot() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--load-snapshot".to_string(),
+        "home.snap".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(
+        Some(PathBuf::from("home.snap")),
+        cli_args.item_source.load_snapshot
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_args_given_load_snapshot_and_target_path_returns_error() {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--load-snapshot".to_string(),
+        "home.snap".to_string(),
+        "some/path".to_string(),
+    ];
+    assert!(CliArgs::try_parse_from(&args).is_err());
+}
+
+#[test]
+fn parse_args_given_load_snapshot_and_import_ncdu_returns_error() {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--load-snapshot".to_string(),
+        "home.snap".to_string(),
+        "--import-ncdu".to_string(),
+        "export.json".to_string(),
+    ];
+    assert!(CliArgs::try_parse_from(&args).is_err());
+}
+
+#[test]
+fn parse_args_diff() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "diff".to_string(),
+        "monday.snap".to_string(),
+        "friday.snap".to_string(),
+        "--all".to_string(),
+        "--max-depth".to_string(),
+        "2".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    let Some(Command::Diff(diff_args)) = cli_args.command else {
+        anyhow::bail!("Expected the diff command");
+    };
+    assert_eq!(PathBuf::from("monday.snap"), diff_args.old_snapshot);
+    assert_eq!(PathBuf::from("friday.snap"), diff_args.new_snapshot);
+    assert!(diff_args.all);
+    assert_eq!(Some(2), diff_args.max_depth);
+    assert_eq!(SizeMode::Apparent, diff_args.size_mode);
+    Ok(())
+}
+
+#[test]
+fn parse_args_diff_measure_count() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "diff".to_string(),
+        "--measure".to_string(),
+        "count".to_string(),
+        "monday.snap".to_string(),
+        "friday.snap".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    let Some(Command::Diff(diff_args)) = cli_args.command else {
+        anyhow::bail!("Expected the diff command");
+    };
+    assert_eq!(
+        SizeMode::Count,
+        diff_args.measure.size_mode(diff_args.size_mode)
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_args_given_diff_with_measure_count_and_size_mode_returns_error() {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "diff".to_string(),
+        "--measure".to_string(),
+        "count".to_string(),
+        "--size-mode".to_string(),
+        "allocated".to_string(),
+        "monday.snap".to_string(),
+        "friday.snap".to_string(),
+    ];
+    assert!(CliArgs::try_parse_from(&args).is_err());
+}
+
+#[test]
+fn parse_args_given_diff_with_one_snapshot_returns_error() {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "diff".to_string(),
+        "monday.snap".to_string(),
+    ];
+    assert!(CliArgs::try_parse_from(&args).is_err());
+}
+
+#[test]
+fn parse_args_output_ncdu() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "ncdu".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(OutputFormat::Ncdu, cli_args.output);
+    Ok(())
+}
+
+// ─── --output du arg tests ───────────────────────────────────────────────────
+
+#[test]
+fn parse_args_output_du_with_all() -> anyhow::Result<()> {
+    let args = vec![
+        BINARY_PATH.to_string(),
+        "--output".to_string(),
+        "du".to_string(),
+        "-a".to_string(),
+    ];
+    let cli_args = parse_args(&args)?;
+    assert_eq!(OutputFormat::Du, cli_args.output);
+    assert!(cli_args.all);
+    Ok(())
+}
+
+#[test]
+fn parse_args_all_is_false_by_default() -> anyhow::Result<()> {
+    let args = vec![BINARY_PATH.to_string()];
+    let cli_args = parse_args(&args)?;
+    assert!(!cli_args.all);
+    Ok(())
+}
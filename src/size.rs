@@ -8,6 +8,8 @@ use std::fmt::Display;
 
 #[cfg(feature = "cli")]
 use clap::ValueEnum;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// The format to use when a size value is displayed.
 #[derive(Debug, Clone, Copy)]
@@ -19,6 +21,22 @@ pub enum SizeDisplayFormat {
     Binary,
 }
 
+/// The size that is measured for each directory item.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum SizeMode {
+    /// The size of the file content in bytes.
+    #[default]
+    Apparent,
+    /// The space allocated for the file on disk, based on the number of allocated blocks. On platforms where
+    /// this is not available the apparent size is used.
+    Allocated,
+    /// The number of entries, i.e. the item itself and all its descendants, rather than a size in bytes. This
+    /// shows which trees use the most inodes.
+    #[cfg_attr(feature = "cli", value(skip))]
+    Count,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 struct SizeDisplayData {
     divisor: u64,
@@ -55,8 +73,23 @@ const METRIC_DISPLAY_DATA: [&SizeDisplayData; 3] = [
     },
 ];
 
+/// The entry count from which counts are abbreviated, as they are shown in full below this.
+const MIN_ABBREVIATED_COUNT: u64 = 1000 * 1000;
+
+const COUNT_DISPLAY_DATA: [&SizeDisplayData; 2] = [
+    &SizeDisplayData {
+        divisor: 1000 * 1000 * 1000,
+        unit: "G",
+    },
+    &SizeDisplayData {
+        divisor: 1000 * 1000,
+        unit: "M",
+    },
+];
+
 /// A directory item size.
 #[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
 pub struct Size {
     value: u64,
 }
@@ -98,6 +131,26 @@ impl Size {
         format!("{} {}", self.value / best_format.divisor, best_format.unit)
     }
 
+    /// Converts the size to string as measured by the specified size mode, i.e. as a number of entries for
+    /// [`SizeMode::Count`] and as bytes, using the specified format, otherwise.
+    pub fn to_string_for_mode(&self, size_mode: SizeMode, format: SizeDisplayFormat) -> String {
+        match size_mode {
+            SizeMode::Count => self.format_count(),
+            SizeMode::Apparent | SizeMode::Allocated => self.to_string(format),
+        }
+    }
+
+    fn format_count(&self) -> String {
+        if self.value < MIN_ABBREVIATED_COUNT {
+            return self.value.to_string();
+        }
+        let best_format = COUNT_DISPLAY_DATA
+            .into_iter()
+            .find(|data| self.value >= data.divisor)
+            .unwrap_or(COUNT_DISPLAY_DATA[COUNT_DISPLAY_DATA.len() - 1]);
+        format!("{} {}", self.value / best_format.divisor, best_format.unit)
+    }
+
     fn get_best_format(size_in_bytes: u64, format: SizeDisplayFormat) -> &'static SizeDisplayData {
         let config = match format {
             SizeDisplayFormat::Binary => BINARY_DISPLAY_DATA,
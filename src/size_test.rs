@@ -1,5 +1,5 @@
 use crate::size::{
-    Size, SizeDisplayData, SizeDisplayFormat, BINARY_DISPLAY_DATA, METRIC_DISPLAY_DATA,
+    Size, SizeDisplayData, SizeDisplayFormat, SizeMode, BINARY_DISPLAY_DATA, METRIC_DISPLAY_DATA,
 };
 use rstest::rstest;
 
@@ -72,6 +72,29 @@ fn get_best_format_returns_correct_display_data(
     assert_eq!(expected_display_data, display_data);
 }
 
+#[rstest]
+#[case(SizeMode::Apparent, 1500, "1 KB")]
+#[case(SizeMode::Allocated, 1500, "1 KB")]
+#[case(SizeMode::Count, 0, "0")]
+#[case(SizeMode::Count, 999_999, "999999")]
+#[case(SizeMode::Count, 1_000_000, "1 M")]
+#[case(SizeMode::Count, 25_500_000, "25 M")]
+#[case(SizeMode::Count, 3_000_000_000, "3 G")]
+fn to_string_for_mode_returns_bytes_or_entry_count(
+    #[case] size_mode: SizeMode,
+    #[case] value: u64,
+    #[case] expected: &str,
+) {
+    // Arrange
+    let size = Size::new(value);
+
+    // Act
+    let text = size.to_string_for_mode(size_mode, SizeDisplayFormat::Metric);
+
+    // Assert
+    assert_eq!(expected, text);
+}
+
 #[test]
 fn get_value_returns_correct_value() {
     // Arrange
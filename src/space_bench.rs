@@ -1,8 +1,13 @@
 use anyhow::bail;
 use clap::{ColorChoice, Parser};
-use cli::{cli_command::CliCommand, environment::DefaultEnvService, view_command::ViewCommand};
+use cli::{
+    cli_command::CliCommand,
+    environment::DefaultEnvService,
+    output_format::OutputFormat,
+    view_command::{ItemSource, OutputSettings, ViewCommand},
+};
 use criterion::Criterion;
-use space_rs::SizeDisplayFormat;
+use space_rs::{Scanner, SizeDisplayFormat, SizeMode};
 use std::{
     io::{self, Write},
     path::PathBuf,
@@ -122,11 +127,17 @@ impl BenchmarkCommand {
             b.iter(|| {
                 ViewCommand::new(
                     Some(self.target_paths.clone()),
+                    ItemSource::Scan,
                     Some(self.size_display_format),
+                    SizeMode::Apparent,
                     self.size_threshold_percentage,
+                    false,
+                    OutputFormat::Table,
+                    OutputSettings::default(),
                     #[cfg(not(test))]
                     true,
                     None,
+                    Arc::new(Scanner::default()),
                     Box::<DefaultEnvService>::default(),
                     should_exit.clone(),
                 )
@@ -0,0 +1,557 @@
+//! Renders directory trees as SVG charts, i.e. a squarified treemap or an icicle chart.
+//!
+//! The output only depends on the items and the options, so the same tree always renders to the same document,
+//! e.g.
+//!
+//! ```no_run
+//! use space_rs::{svg::{render_svg, SvgChart, SvgOptions}, DirectoryItem};
+//! use std::sync::{atomic::AtomicBool, Arc};
+//!
+//! let items = DirectoryItem::build(vec![".".into()], &Arc::new(AtomicBool::new(false)));
+//! let options = SvgOptions::default().chart(SvgChart::Icicle).dimensions(800, 400);
+//! let mut svg = vec![];
+//! render_svg(&items, &options, &mut svg).unwrap();
+//! ```
+
+#[cfg(test)]
+#[path = "./svg_test.rs"]
+mod svg_test;
+
+use crate::{DirectoryItem, SizeDisplayFormat, SizeMode};
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Items that would be drawn narrower or lower than this, in pixels, are not drawn.
+const MIN_CELL_SIZE: f64 = 1.0;
+/// The space between the edge of a treemap cell and the cells of its children, in pixels.
+const CELL_PADDING: f64 = 2.0;
+/// The space between the edge of a cell and its label, in pixels.
+const TEXT_PADDING: f64 = 3.0;
+/// The approximate width of a character relative to the font size, which is used to fit the labels in the cells.
+const CHAR_WIDTH_RATIO: f64 = 0.6;
+
+/// The kind of chart to render.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum SvgChart {
+    /// Nested rectangles with areas proportional to the sizes of the items, laid out so that they are as close to
+    /// square as possible.
+    #[default]
+    Treemap,
+    /// One row per level of the tree, with the roots at the top and each item as wide as its share of its parent,
+    /// like an upside down flame graph.
+    Icicle,
+}
+
+/// A color with red, green and blue components.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SvgColor {
+    /// The red component.
+    pub red: u8,
+    /// The green component.
+    pub green: u8,
+    /// The blue component.
+    pub blue: u8,
+}
+
+impl SvgColor {
+    /// Creates a new instance.
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        SvgColor { red, green, blue }
+    }
+}
+
+impl Display for SvgColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+/// The colors of a chart.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SvgPalette {
+    /// The color behind the items.
+    pub background: SvgColor,
+    /// The color of the labels.
+    pub text: SvgColor,
+    /// The color of the outline of each item.
+    pub border: SvgColor,
+    /// The fill colors of the items, which are used in turn for each level of the tree, so that the items stand
+    /// out from their parents. If empty then the background color is used.
+    pub fills: Vec<SvgColor>,
+}
+
+impl Default for SvgPalette {
+    fn default() -> Self {
+        SvgPalette {
+            background: SvgColor::new(64, 64, 64),
+            text: SvgColor::new(255, 255, 255),
+            border: SvgColor::new(32, 32, 32),
+            fills: vec![SvgColor::new(88, 144, 255), SvgColor::new(56, 100, 192)],
+        }
+    }
+}
+
+/// The options that control how a chart is rendered. Use the builder methods to configure the options and then
+/// call [`render_svg`].
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+    /// The kind of chart.
+    pub chart: SvgChart,
+    /// The width of the chart in pixels.
+    pub width: u32,
+    /// The height of the chart in pixels.
+    pub height: u32,
+    /// The size that is measured for each item.
+    pub size_mode: SizeMode,
+    /// The format of the sizes in the labels.
+    pub size_display_format: SizeDisplayFormat,
+    /// Only the items that are at least this fraction of the total size are labeled. All items that are large
+    /// enough to see are drawn.
+    pub label_threshold_fraction: f32,
+    /// The maximum depth of the items that are drawn, where the roots are at depth 0. `None` means there is no
+    /// limit.
+    pub max_depth: Option<usize>,
+    /// The font size of the labels in pixels.
+    pub font_size: u32,
+    /// The colors of the chart.
+    pub palette: SvgPalette,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            chart: SvgChart::Treemap,
+            width: 1200,
+            height: 800,
+            size_mode: SizeMode::Apparent,
+            size_display_format: SizeDisplayFormat::Metric,
+            label_threshold_fraction: 0.01,
+            max_depth: None,
+            font_size: 12,
+            palette: SvgPalette::default(),
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Sets the kind of chart.
+    pub fn chart(mut self, chart: SvgChart) -> Self {
+        self.chart = chart;
+        self
+    }
+
+    /// Sets the width and height of the chart in pixels.
+    pub fn dimensions(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the size that is measured for each item.
+    pub fn size_mode(mut self, size_mode: SizeMode) -> Self {
+        self.size_mode = size_mode;
+        self
+    }
+
+    /// Sets the format of the sizes in the labels.
+    pub fn size_display_format(mut self, size_display_format: SizeDisplayFormat) -> Self {
+        self.size_display_format = size_display_format;
+        self
+    }
+
+    /// Sets the fraction of the total size from which items are labeled.
+    pub fn label_threshold_fraction(mut self, label_threshold_fraction: f32) -> Self {
+        self.label_threshold_fraction = label_threshold_fraction;
+        self
+    }
+
+    /// Sets the maximum depth of the items that are drawn.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the font size of the labels in pixels.
+    pub fn font_size(mut self, font_size: u32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the colors of the chart.
+    pub fn palette(mut self, palette: SvgPalette) -> Self {
+        self.palette = palette;
+        self
+    }
+}
+
+/// Writes the items as a standalone SVG document with the chart that is specified by the options. Each item is a
+/// group with a tooltip that has its path, size and percentage of the total, which contains the groups of its
+/// children.
+pub fn render_svg<W: Write>(
+    items: &[DirectoryItem],
+    options: &SvgOptions,
+    writer: &mut W,
+) -> io::Result<()> {
+    let width = options.width as f64;
+    let height = options.height as f64;
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" \
+        font-family=\"sans-serif\" font-size=\"{}\">",
+        options.width, options.height, options.width, options.height, options.font_size
+    )?;
+    writeln!(
+        writer,
+        "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        options.width, options.height, options.palette.background
+    )?;
+
+    let total = items
+        .iter()
+        .map(|item| item.get_size(options.size_mode).get_value())
+        .sum();
+    let mut renderer = Renderer {
+        options,
+        writer,
+        total,
+    };
+    let roots = renderer.sort_by_size(items);
+    let area = Rect {
+        x: 0.0,
+        y: 0.0,
+        width,
+        height,
+    };
+    match options.chart {
+        SvgChart::Treemap => renderer.render_treemap(&roots, None, total, area, 0)?,
+        SvgChart::Icicle => {
+            let mut levels = roots
+                .iter()
+                .map(|item| count_levels(item))
+                .max()
+                .unwrap_or(0);
+            if let Some(max_depth) = options.max_depth {
+                levels = levels.min(max_depth + 1);
+            }
+            if levels > 0 {
+                renderer.render_icicle(&roots, None, total, area, height / levels as f64, 0)?;
+            }
+        }
+    }
+
+    writeln!(renderer.writer, "</svg>")?;
+    Ok(())
+}
+
+/// The number of levels in the tree of the item, including the item itself.
+fn count_levels(item: &DirectoryItem) -> usize {
+    1 + item.children.iter().map(count_levels).max().unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Rect {
+    fn is_visible(&self) -> bool {
+        self.width >= MIN_CELL_SIZE && self.height >= MIN_CELL_SIZE
+    }
+}
+
+/// Formats a coordinate with at most two decimals and without trailing zeros, so that the output is compact.
+struct Coordinate(f64);
+
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formatted = format!("{:.2}", self.0);
+        let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+        f.write_str(if formatted == "-0" { "0" } else { formatted })
+    }
+}
+
+struct Renderer<'a, W: Write> {
+    options: &'a SvgOptions,
+    writer: &'a mut W,
+    total: u64,
+}
+
+impl<W: Write> Renderer<'_, W> {
+    /// Returns the items that have a size, by descending size and then by name, regardless of how they were sorted.
+    fn sort_by_size<'i>(&self, items: &'i [DirectoryItem]) -> Vec<&'i DirectoryItem> {
+        let size_mode = self.options.size_mode;
+        let mut sorted: Vec<_> = items
+            .iter()
+            .filter(|item| item.get_size(size_mode).get_value() > 0)
+            .collect();
+        sorted.sort_by(|a, b| a.cmp_by_size(b, size_mode));
+        sorted
+    }
+
+    fn is_in_max_depth(&self, depth: usize) -> bool {
+        self.options
+            .max_depth
+            .map_or(true, |max_depth| depth <= max_depth)
+    }
+
+    /// Draws the items in the area, which represents `parent_size`. If the items are smaller in total, e.g. when
+    /// counting entries, then part of the area is left empty.
+    fn render_treemap(
+        &mut self,
+        items: &[&DirectoryItem],
+        parent_path: Option<&Path>,
+        parent_size: u64,
+        area: Rect,
+        depth: usize,
+    ) -> io::Result<()> {
+        let sizes: Vec<_> = items
+            .iter()
+            .map(|item| item.get_size(self.options.size_mode).get_value())
+            .collect();
+        let cells = squarify(&sizes, parent_size, area);
+        let header_height = self.options.font_size as f64 + 2.0 * TEXT_PADDING;
+
+        for (item, cell) in items.iter().zip(cells) {
+            if !cell.is_visible() {
+                continue;
+            }
+            let path = join_path(parent_path, &item.path_segment);
+            self.start_item(item, &path, cell, depth)?;
+
+            let inner = Rect {
+                x: cell.x + CELL_PADDING,
+                y: cell.y + header_height,
+                width: cell.width - 2.0 * CELL_PADDING,
+                height: cell.height - header_height - CELL_PADDING,
+            };
+            if self.is_in_max_depth(depth + 1) && inner.is_visible() {
+                let children = self.sort_by_size(&item.children);
+                let size = item.get_size(self.options.size_mode).get_value();
+                self.render_treemap(&children, Some(&path), size, inner, depth + 1)?;
+            }
+
+            writeln!(self.writer, "</g>")?;
+        }
+        Ok(())
+    }
+
+    /// Draws the items side by side in a row of the given height, with the children of each item in the row below.
+    fn render_icicle(
+        &mut self,
+        items: &[&DirectoryItem],
+        parent_path: Option<&Path>,
+        parent_size: u64,
+        area: Rect,
+        row_height: f64,
+        depth: usize,
+    ) -> io::Result<()> {
+        if parent_size == 0 {
+            return Ok(());
+        }
+        let scale = area.width / parent_size as f64;
+        let mut x = area.x;
+
+        for item in items {
+            let size = item.get_size(self.options.size_mode).get_value();
+            let cell = Rect {
+                x,
+                y: area.y,
+                width: size as f64 * scale,
+                height: row_height,
+            };
+            x += cell.width;
+            if !cell.is_visible() {
+                // The items are sorted by descending size, so the rest are not visible either.
+                break;
+            }
+            let path = join_path(parent_path, &item.path_segment);
+            self.start_item(item, &path, cell, depth)?;
+
+            if self.is_in_max_depth(depth + 1) {
+                let children = self.sort_by_size(&item.children);
+                let below = Rect {
+                    y: area.y + row_height,
+                    ..cell
+                };
+                self.render_icicle(&children, Some(&path), size, below, row_height, depth + 1)?;
+            }
+
+            writeln!(self.writer, "</g>")?;
+        }
+        Ok(())
+    }
+
+    /// Opens the group of the item and draws its cell and label. The caller closes the group after drawing the
+    /// children.
+    fn start_item(
+        &mut self,
+        item: &DirectoryItem,
+        path: &Path,
+        cell: Rect,
+        depth: usize,
+    ) -> io::Result<()> {
+        let options = self.options;
+        let size = item.get_size(options.size_mode);
+        let size_text = size.to_string_for_mode(options.size_mode, options.size_display_format);
+        let fraction = size.get_fraction(self.total);
+        let fill = match options.palette.fills.len() {
+            0 => options.palette.background,
+            count => options.palette.fills[depth % count],
+        };
+
+        writeln!(
+            self.writer,
+            "<g><title>{} {} ({:.1}%)</title>",
+            escape_xml(&path.to_string_lossy()),
+            size_text,
+            fraction * 100.0
+        )?;
+        writeln!(
+            self.writer,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"{}\"/>",
+            Coordinate(cell.x),
+            Coordinate(cell.y),
+            Coordinate(cell.width),
+            Coordinate(cell.height),
+            fill,
+            options.palette.border
+        )?;
+
+        let font_size = options.font_size as f64;
+        if fraction >= options.label_threshold_fraction
+            && cell.height >= font_size + 2.0 * TEXT_PADDING
+        {
+            let max_chars = ((cell.width - 2.0 * TEXT_PADDING) / (font_size * CHAR_WIDTH_RATIO))
+                .floor()
+                .max(0.0) as usize;
+            if let Some(label) = fit_label(&item.path_segment, &size_text, max_chars) {
+                writeln!(
+                    self.writer,
+                    "<text x=\"{}\" y=\"{}\" dominant-baseline=\"hanging\" fill=\"{}\">{}</text>",
+                    Coordinate(cell.x + TEXT_PADDING),
+                    Coordinate(cell.y + TEXT_PADDING),
+                    options.palette.text,
+                    escape_xml(&label)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn join_path(parent_path: Option<&Path>, path_segment: &str) -> PathBuf {
+    match parent_path {
+        Some(parent_path) => parent_path.join(path_segment),
+        None => PathBuf::from(path_segment),
+    }
+}
+
+/// Returns the label that fits in the given number of characters, i.e. the name and size, the name only, or the
+/// start of the name followed by an ellipsis, or `None` if not even that fits.
+fn fit_label(name: &str, size_text: &str, max_chars: usize) -> Option<String> {
+    let label = format!("{name} {size_text}");
+    let name_chars = name.chars().count();
+    if label.chars().count() <= max_chars {
+        Some(label)
+    } else if name_chars <= max_chars {
+        Some(name.to_string())
+    } else if max_chars >= 2 {
+        Some(name.chars().take(max_chars - 1).chain(['…']).collect())
+    } else {
+        None
+    }
+}
+
+/// Lays out cells with the given sizes, which are sorted by descending size, in the area using the squarified
+/// treemap algorithm, so that the cells are as close to square as possible. The area represents `total`. Returns
+/// the cells in the same order as the sizes.
+fn squarify(sizes: &[u64], total: u64, area: Rect) -> Vec<Rect> {
+    let mut cells = Vec::with_capacity(sizes.len());
+    if total == 0 || !area.is_visible() {
+        return cells;
+    }
+    let scale = area.width * area.height / total as f64;
+    let mut remaining = area;
+    let mut start = 0;
+
+    while start < sizes.len() {
+        // Add cells to the row along the shorter side for as long as that improves the worst aspect ratio.
+        let side = remaining.width.min(remaining.height);
+        let mut end = start;
+        let (mut sum, mut min, mut max, mut ratio) = (0f64, f64::MAX, 0f64, f64::MAX);
+        while end < sizes.len() {
+            let area = sizes[end] as f64 * scale;
+            let next_ratio = worst_ratio(sum + area, min.min(area), max.max(area), side);
+            if end > start && next_ratio > ratio {
+                break;
+            }
+            sum += area;
+            min = min.min(area);
+            max = max.max(area);
+            ratio = next_ratio;
+            end += 1;
+        }
+
+        let thickness = if side > 0.0 { sum / side } else { 0.0 };
+        let mut offset = 0.0;
+        for &size in &sizes[start..end] {
+            let length = if thickness > 0.0 {
+                size as f64 * scale / thickness
+            } else {
+                0.0
+            };
+            cells.push(if remaining.width >= remaining.height {
+                Rect {
+                    x: remaining.x,
+                    y: remaining.y + offset,
+                    width: thickness,
+                    height: length,
+                }
+            } else {
+                Rect {
+                    x: remaining.x + offset,
+                    y: remaining.y,
+                    width: length,
+                    height: thickness,
+                }
+            });
+            offset += length;
+        }
+
+        if remaining.width >= remaining.height {
+            remaining.x += thickness;
+            remaining.width = (remaining.width - thickness).max(0.0);
+        } else {
+            remaining.y += thickness;
+            remaining.height = (remaining.height - thickness).max(0.0);
+        }
+        start = end;
+    }
+
+    cells
+}
+
+/// Returns the worst aspect ratio of a row of cells with the given total, smallest and largest areas, along a side
+/// of the given length.
+fn worst_ratio(sum: f64, min: f64, max: f64, side: f64) -> f64 {
+    let sum_squared = sum * sum;
+    let side_squared = side * side;
+    (side_squared * max / sum_squared).max(sum_squared / (side_squared * min))
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
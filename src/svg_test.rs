@@ -0,0 +1,348 @@
+use super::{
+    fit_label, render_svg, squarify, Coordinate, Rect, SvgChart, SvgColor, SvgOptions, SvgPalette,
+};
+use crate::{DescendantCounts, DirectoryItem, DirectoryItemType, Size, SizeMode};
+use rstest::rstest;
+use std::path::MAIN_SEPARATOR_STR;
+
+fn make_item(path_segment: &str, size: u64, children: Vec<DirectoryItem>) -> DirectoryItem {
+    let descendant_count = children
+        .iter()
+        .map(|child| child.descendant_count + 1)
+        .sum();
+    DirectoryItem {
+        path_segment: path_segment.to_string(),
+        item_type: if children.is_empty() {
+            DirectoryItemType::File
+        } else {
+            DirectoryItemType::Directory
+        },
+        size_in_bytes: Size::new(size),
+        allocated_size_in_bytes: Size::new(size),
+        descendant_count,
+        descendant_counts: DescendantCounts::default(),
+        children,
+        link_target: None,
+        is_ignored: false,
+        ignored_size_in_bytes: Size::default(),
+        ignored_allocated_size_in_bytes: Size::default(),
+        metadata: None,
+    }
+}
+
+/// A tree of 6 items, with a total size of 1 MB.
+fn make_test_tree() -> Vec<DirectoryItem> {
+    vec![make_item(
+        "/data",
+        1_000_000,
+        vec![
+            make_item(
+                "a",
+                600_000,
+                vec![
+                    make_item("a1", 400_000, vec![]),
+                    make_item("a2", 200_000, vec![]),
+                ],
+            ),
+            make_item("b", 300_000, vec![]),
+            make_item("c", 100_000, vec![]),
+        ],
+    )]
+}
+
+fn make_test_options(chart: SvgChart) -> SvgOptions {
+    SvgOptions::default()
+        .chart(chart)
+        .dimensions(200, 100)
+        .font_size(10)
+        .label_threshold_fraction(0.2)
+}
+
+fn render_to_string(items: &[DirectoryItem], options: &SvgOptions) -> String {
+    let mut output = vec![];
+    render_svg(items, options, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+/// Replaces the `|` placeholders in the expected output with the path separator of the platform.
+fn expected_output(expected: &str) -> String {
+    expected.replace('|', MAIN_SEPARATOR_STR)
+}
+#[rstest]
+fn render_svg_given_treemap_writes_nested_squarified_cells() {
+    // Act
+    let svg = render_to_string(&make_test_tree(), &make_test_options(SvgChart::Treemap));
+
+    // Assert
+    let expected = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 200 100" font-family="sans-serif" font-size="10">
+<rect width="200" height="100" fill="#404040"/>
+<g><title>/data 1000 KB (100.0%)</title>
+<rect x="0" y="0" width="200" height="100" fill="#5890ff" stroke="#202020"/>
+<text x="3" y="3" dominant-baseline="hanging" fill="#ffffff">/data 1000 KB</text>
+<g><title>/data|a 600 KB (60.0%)</title>
+<rect x="2" y="16" width="117.6" height="82" fill="#3864c0" stroke="#202020"/>
+<text x="5" y="19" dominant-baseline="hanging" fill="#ffffff">a 600 KB</text>
+<g><title>/data|a|a1 400 KB (40.0%)</title>
+<rect x="4" y="32" width="75.73" height="64" fill="#5890ff" stroke="#202020"/>
+<text x="7" y="35" dominant-baseline="hanging" fill="#ffffff">a1 400 KB</text>
+</g>
+<g><title>/data|a|a2 200 KB (20.0%)</title>
+<rect x="79.73" y="32" width="37.87" height="64" fill="#5890ff" stroke="#202020"/>
+<text x="82.73" y="35" dominant-baseline="hanging" fill="#ffffff">a2</text>
+</g>
+</g>
+<g><title>/data|b 300 KB (30.0%)</title>
+<rect x="119.6" y="16" width="78.4" height="61.5" fill="#3864c0" stroke="#202020"/>
+<text x="122.6" y="19" dominant-baseline="hanging" fill="#ffffff">b 300 KB</text>
+</g>
+<g><title>/data|c 100 KB (10.0%)</title>
+<rect x="119.6" y="77.5" width="78.4" height="20.5" fill="#3864c0" stroke="#202020"/>
+</g>
+</g>
+</svg>
+"##;
+    assert_eq!(expected_output(expected), svg);
+}
+
+#[rstest]
+fn render_svg_given_icicle_writes_one_row_per_level() {
+    // Act
+    let svg = render_to_string(&make_test_tree(), &make_test_options(SvgChart::Icicle));
+
+    // Assert
+    let expected = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 200 100" font-family="sans-serif" font-size="10">
+<rect width="200" height="100" fill="#404040"/>
+<g><title>/data 1000 KB (100.0%)</title>
+<rect x="0" y="0" width="200" height="33.33" fill="#5890ff" stroke="#202020"/>
+<text x="3" y="3" dominant-baseline="hanging" fill="#ffffff">/data 1000 KB</text>
+<g><title>/data|a 600 KB (60.0%)</title>
+<rect x="0" y="33.33" width="120" height="33.33" fill="#3864c0" stroke="#202020"/>
+<text x="3" y="36.33" dominant-baseline="hanging" fill="#ffffff">a 600 KB</text>
+<g><title>/data|a|a1 400 KB (40.0%)</title>
+<rect x="0" y="66.67" width="80" height="33.33" fill="#5890ff" stroke="#202020"/>
+<text x="3" y="69.67" dominant-baseline="hanging" fill="#ffffff">a1 400 KB</text>
+</g>
+<g><title>/data|a|a2 200 KB (20.0%)</title>
+<rect x="80" y="66.67" width="40" height="33.33" fill="#5890ff" stroke="#202020"/>
+<text x="83" y="69.67" dominant-baseline="hanging" fill="#ffffff">a2</text>
+</g>
+</g>
+<g><title>/data|b 300 KB (30.0%)</title>
+<rect x="120" y="33.33" width="60" height="33.33" fill="#3864c0" stroke="#202020"/>
+<text x="123" y="36.33" dominant-baseline="hanging" fill="#ffffff">b 300 KB</text>
+</g>
+<g><title>/data|c 100 KB (10.0%)</title>
+<rect x="180" y="33.33" width="20" height="33.33" fill="#3864c0" stroke="#202020"/>
+</g>
+</g>
+</svg>
+"##;
+    assert_eq!(expected_output(expected), svg);
+}
+
+#[rstest]
+fn render_svg_output_does_not_depend_on_order_of_children(
+    #[values(SvgChart::Treemap, SvgChart::Icicle)] chart: SvgChart,
+) {
+    // Arrange
+    let mut reversed = make_test_tree();
+    reversed[0].children.reverse();
+    let options = make_test_options(chart);
+
+    // Act
+    let svg = render_to_string(&make_test_tree(), &options);
+    let reversed_svg = render_to_string(&reversed, &options);
+
+    // Assert
+    assert_eq!(svg, reversed_svg);
+}
+
+#[rstest]
+#[case(SvgChart::Treemap, 0f32, 6)]
+#[case(SvgChart::Treemap, 0.35f32, 3)]
+#[case(SvgChart::Treemap, 1f32, 1)]
+#[case(SvgChart::Icicle, 0f32, 6)]
+#[case(SvgChart::Icicle, 0.35f32, 3)]
+fn render_svg_labels_items_from_threshold_that_fit(
+    #[case] chart: SvgChart,
+    #[case] label_threshold_fraction: f32,
+    #[case] expected_label_count: usize,
+) {
+    // Arrange
+    let options = make_test_options(chart).label_threshold_fraction(label_threshold_fraction);
+
+    // Act
+    let svg = render_to_string(&make_test_tree(), &options);
+
+    // Assert
+    assert_eq!(6, svg.matches("<title>").count());
+    assert_eq!(expected_label_count, svg.matches("<text ").count());
+}
+
+#[rstest]
+fn render_svg_given_max_depth_does_not_draw_deeper_items(
+    #[values(SvgChart::Treemap, SvgChart::Icicle)] chart: SvgChart,
+) {
+    // Arrange
+    let options = make_test_options(chart).max_depth(1);
+
+    // Act
+    let svg = render_to_string(&make_test_tree(), &options);
+
+    // Assert
+    assert_eq!(4, svg.matches("<title>").count());
+    assert!(!svg.contains("a1"));
+}
+
+#[rstest]
+fn render_svg_given_icicle_with_max_depth_uses_full_height_for_drawn_levels() {
+    // Arrange
+    let options = make_test_options(SvgChart::Icicle).max_depth(1);
+
+    // Act
+    let svg = render_to_string(&make_test_tree(), &options);
+
+    // Assert
+    assert!(svg.contains(r#"<rect x="0" y="50" width="120" height="50""#));
+}
+
+#[rstest]
+fn render_svg_given_count_size_mode_sizes_items_by_entries() {
+    // Arrange
+    let options = make_test_options(SvgChart::Icicle).size_mode(SizeMode::Count);
+
+    // Act
+    let svg = render_to_string(&make_test_tree(), &options);
+
+    // Assert
+    // The root has 6 entries, and "a" has 3 of them, including itself.
+    assert!(svg.contains("<title>/data 6 (100.0%)</title>"));
+    assert!(svg.contains(r#"<rect x="0" y="33.33" width="100" height="33.33""#));
+}
+
+#[rstest]
+fn render_svg_uses_colors_of_palette() {
+    // Arrange
+    let palette = SvgPalette {
+        background: SvgColor::new(1, 2, 3),
+        text: SvgColor::new(4, 5, 6),
+        border: SvgColor::new(7, 8, 9),
+        fills: vec![SvgColor::new(255, 0, 0)],
+    };
+    let options = make_test_options(SvgChart::Treemap).palette(palette);
+
+    // Act
+    let svg = render_to_string(&make_test_tree(), &options);
+
+    // Assert
+    assert!(svg.contains(r##"<rect width="200" height="100" fill="#010203"/>"##));
+    assert_eq!(
+        6,
+        svg.matches(r##"fill="#ff0000" stroke="#070809""##).count()
+    );
+    assert_eq!(5, svg.matches(r##"fill="#040506""##).count());
+}
+
+#[rstest]
+fn render_svg_escapes_names() {
+    // Arrange
+    let items = vec![make_item("<a & b>", 1_000_000, vec![])];
+
+    // Act
+    let svg = render_to_string(&items, &make_test_options(SvgChart::Treemap));
+
+    // Assert
+    assert!(svg.contains("<title>&lt;a &amp; b&gt; 1000 KB (100.0%)</title>"));
+    assert!(svg.contains(">&lt;a &amp; b&gt; 1000 KB</text>"));
+}
+
+#[rstest]
+fn render_svg_given_no_items_writes_empty_chart(
+    #[values(SvgChart::Treemap, SvgChart::Icicle)] chart: SvgChart,
+) {
+    // Act
+    let svg = render_to_string(&[], &make_test_options(chart));
+
+    // Assert
+    assert_eq!(3, svg.lines().count());
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[rstest]
+fn squarify_gives_each_cell_an_area_proportional_to_its_size() {
+    // Arrange
+    let sizes = [6, 6, 4, 3, 2, 2, 1];
+    let area = Rect {
+        x: 10.0,
+        y: 20.0,
+        width: 60.0,
+        height: 40.0,
+    };
+
+    // Act
+    let cells = squarify(&sizes, 24, area);
+
+    // Assert
+    assert_eq!(sizes.len(), cells.len());
+    for (size, cell) in sizes.iter().zip(&cells) {
+        assert!((cell.width * cell.height - *size as f64 * 100.0).abs() < 1e-6);
+        assert!(cell.x >= area.x && cell.x + cell.width <= area.x + area.width + 1e-6);
+        assert!(cell.y >= area.y && cell.y + cell.height <= area.y + area.height + 1e-6);
+    }
+}
+
+#[rstest]
+fn squarify_given_sizes_less_than_total_leaves_area_empty() {
+    // Arrange
+    let area = Rect {
+        x: 0.0,
+        y: 0.0,
+        width: 100.0,
+        height: 50.0,
+    };
+
+    // Act
+    let cells = squarify(&[1], 2, area);
+
+    // Assert
+    assert_eq!(
+        vec![Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 50.0,
+            height: 50.0
+        }],
+        cells
+    );
+}
+
+#[rstest]
+#[case("report.pdf", 16, Some("report.pdf 1 MB"))]
+#[case("report.pdf", 12, Some("report.pdf"))]
+#[case("report.pdf", 5, Some("repo…"))]
+#[case("report.pdf", 1, None)]
+fn fit_label_shortens_label_to_fit(
+    #[case] name: &str,
+    #[case] max_chars: usize,
+    #[case] expected: Option<&str>,
+) {
+    // Act
+    let label = fit_label(name, "1 MB", max_chars);
+
+    // Assert
+    assert_eq!(expected, label.as_deref());
+}
+
+#[rstest]
+#[case(0.0, "0")]
+#[case(-0.001, "0")]
+#[case(12.0, "12")]
+#[case(12.5, "12.5")]
+#[case(33.333333, "33.33")]
+fn coordinate_formats_at_most_two_decimals(#[case] value: f64, #[case] expected: &str) {
+    // Act
+    let formatted = Coordinate(value).to_string();
+
+    // Assert
+    assert_eq!(expected, formatted);
+}